@@ -28,5 +28,13 @@ echo "Verifying universal binary..."
 file target/universal/maccleanup-rust
 lipo -info target/universal/maccleanup-rust
 
+# Package it for the release and the Homebrew tap
+VERSION=$(grep -m1 '^version' Cargo.toml | cut -d '"' -f2)
+ARCHIVE="maccleanup-rust-${VERSION}-universal-apple-darwin.tar.gz"
+echo "Packaging ${ARCHIVE}..."
+tar -czf "target/universal/${ARCHIVE}" -C target/universal maccleanup-rust
+(cd target/universal && shasum -a 256 "${ARCHIVE}" > "${ARCHIVE}.sha256")
+
 echo "✅ Universal binary created at: target/universal/maccleanup-rust"
-echo "📦 This binary will run on both Intel and Apple Silicon Macs"
\ No newline at end of file
+echo "📦 This binary will run on both Intel and Apple Silicon Macs"
+echo "🍺 Tap archive: target/universal/${ARCHIVE} (sha256 $(cut -d ' ' -f1 "target/universal/${ARCHIVE}.sha256"))"
\ No newline at end of file
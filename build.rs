@@ -0,0 +1,22 @@
+//! Bakes build details into the binary for `maccleanup-rust info`.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rustc-env=MACCLEANUP_BUILD_TARGET={}", env::var("TARGET").unwrap_or_default());
+    println!("cargo:rustc-env=MACCLEANUP_BUILD_PROFILE={}", env::var("PROFILE").unwrap_or_default());
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|name| name.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=MACCLEANUP_BUILD_FEATURES={}", features.join(","));
+
+    // Source tarballs have no git checkout; the version alone identifies those
+    let commit = Command::new("git").args(["rev-parse", "--short", "HEAD"]).output().ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=MACCLEANUP_BUILD_COMMIT={}", commit);
+}
@@ -1,22 +1,23 @@
+# Tap formula: installs the pre-built universal binary from the GitHub release.
+# After tagging, fill in sha256 from the .sha256 file build-universal.sh writes next to the archive.
 class MaccleanupRust < Formula
   desc "🧹 Mac Cleanup Tool (Rust Edition) By Gappa - Clean your Mac system efficiently"
   homepage "https://github.com/gappa55/maccleanup-rust"
-  url "https://github.com/gappa55/maccleanup-rust/archive/refs/tags/v1.0.0.tar.gz"
+  url "https://github.com/gappa55/maccleanup-rust/releases/download/v1.3.0/maccleanup-rust-1.3.0-universal-apple-darwin.tar.gz"
   sha256 "REPLACE_WITH_ACTUAL_SHA256"
   license "MIT"
-  head "https://github.com/gappa55/maccleanup-rust.git", branch: "main"
 
-  depends_on "rust" => :build
+  depends_on :macos
 
   def install
-    system "cargo", "install", "--locked", "--root", prefix, "--path", "."
+    bin.install "maccleanup-rust"
   end
 
   test do
-    # Test that the binary exists and shows help
-    assert_match "Mac Cleanup Tool (Rust Edition) By Gappa", shell_output("#{bin}/maccleanup-rust --help")
-    
+    assert_match version.to_s, shell_output("#{bin}/maccleanup-rust --version")
+    assert_match "\"version\": \"#{version}\"", shell_output("#{bin}/maccleanup-rust info --json")
+
     # Test dry run mode works
     assert_match "DRY RUN mode", shell_output("#{bin}/maccleanup-rust --dry-run")
   end
-end
\ No newline at end of file
+end
@@ -0,0 +1,126 @@
+//! `bench`: time the parallel scanner and the parallel delete on this machine.
+
+use crate::*;
+
+pub fn run_bench(roots: &[String], threads: usize) {
+    let home = home_dir();
+    let roots: Vec<String> = if roots.is_empty() {
+        vec![format!("{}/Library/Caches", home)]
+    } else {
+        roots.to_vec()
+    };
+
+    println!("{}", "⏱️  Scan Benchmark".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    for root in &roots {
+        println!("  {} {}", "•".dimmed(), root);
+    }
+
+    // Warm the metadata cache so both passes measure the walker, not the disk
+    scan_parallel(&roots, threads);
+
+    let mut baseline: Option<f64> = None;
+    let mut thread_counts = vec![1];
+    if threads > 1 {
+        thread_counts.push(threads);
+    }
+
+    for count in thread_counts {
+        let started = Instant::now();
+        let totals = scan_parallel(&roots, count);
+        let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+        let entries = totals.files + totals.dirs;
+
+        println!("\n  {} {} thread(s)", "▶".cyan(), count);
+        println!("    {} {} files, {} dirs, {} ({} unreadable)",
+            "Scanned:".bold(),
+            totals.files,
+            totals.dirs,
+            format_size(totals.bytes, BINARY),
+            totals.errors);
+        println!("    {} {:.3}s ({:.0} entries/s, {}/s)",
+            "Time:".bold(),
+            elapsed,
+            entries as f64 / elapsed,
+            format_size((totals.bytes as f64 / elapsed) as u64, BINARY));
+
+        match baseline {
+            None => baseline = Some(elapsed),
+            Some(single) => println!("    {} {:.2}x",
+                "Speedup:".bold(),
+                single / elapsed),
+        }
+    }
+}
+
+/// Build sibling trees shaped like node_modules folders holding `files` small files in all:
+/// one tree has half of them, seven others share the rest.
+pub fn build_delete_fixture(root: &Path, files: u64) -> io::Result<Vec<PathBuf>> {
+    const PACKAGE_FILES: u64 = 100;
+    let big = files / 2;
+    let small = (files - big) / 7;
+    let mut counts = vec![big];
+    counts.extend(std::iter::repeat_n(small, 6));
+    counts.push(files - big - small * 6);
+
+    let mut trees = Vec::new();
+    for (i, count) in counts.into_iter().enumerate() {
+        let tree = root.join(format!("project-{}", i)).join("node_modules");
+        for file in 0..count {
+            let lib = tree.join(format!("package-{}", file / PACKAGE_FILES)).join("lib");
+            if file % PACKAGE_FILES == 0 {
+                fs::create_dir_all(&lib)?;
+            }
+            fs::write(lib.join(format!("file-{}.js", file % PACKAGE_FILES)), b"module.exports = {};\n")?;
+        }
+        trees.push(tree);
+    }
+    Ok(trees)
+}
+
+/// Time deleting `files` files spread over sibling trees, one tree after another and then
+/// on `threads` workers with the biggest trees first.
+pub fn run_delete_bench(files: u64, threads: usize) {
+    let root = env::temp_dir().join(format!("maccleanup-bench-{}", std::process::id()));
+
+    println!("{}", "⏱️  Delete Benchmark".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {} {} files in 8 sibling trees under {}", "•".dimmed(), files, root.display());
+
+    let mut baseline: Option<f64> = None;
+    let mut thread_counts = vec![1];
+    if threads > 1 {
+        thread_counts.push(threads);
+    }
+
+    for count in thread_counts {
+        let trees = match build_delete_fixture(&root.join(format!("pass-{}", count)), files) {
+            Ok(trees) => trees,
+            Err(err) => {
+                println!("  {} Couldn't build the test trees: {}", "✗".red(), err);
+                let _ = fs::remove_dir_all(&root);
+                return;
+            }
+        };
+
+        let started = Instant::now();
+        let failed = delete_trees_parallel(&trees, count).iter().filter(|result| result.is_err()).count();
+        let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+
+        println!("\n  {} {} thread(s)", "▶".cyan(), count);
+        println!("    {} {:.3}s ({:.0} files/s){}",
+            "Time:".bold(),
+            elapsed,
+            files as f64 / elapsed,
+            if failed > 0 { format!(", {} trees failed", failed).red().to_string() } else { String::new() });
+
+        match baseline {
+            None => baseline = Some(elapsed),
+            Some(single) => println!("    {} {:.2}x",
+                "Speedup:".bold(),
+                single / elapsed),
+        }
+    }
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,127 @@
+//! `clean-app`: reset one app's caches, logs and saved state.
+
+use crate::*;
+
+/// Where `clean-app` looks for one app's data: a folder whose entries are named after apps,
+/// the part of each entry that's cleared, and what that part holds.
+pub const APP_RESET_LOCATIONS: &[(&str, &str, &str)] = &[
+    ("~/Library/Caches", "", "cache"),
+    ("/Library/Caches", "", "cache"),
+    ("~/Library/Containers", "Data/Library/Caches", "container cache"),
+    ("~/Library/Group Containers", "Library/Caches", "group container cache"),
+    ("~/Library/Logs", "", "logs"),
+    ("~/Library/Saved Application State", "", "saved state"),
+];
+
+/// Whether `text` matches `pattern`, where `*` stands for any run of characters and `?` for
+/// exactly one. Case is ignored, as it is for bundle ids.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Last `*` seen and where in `text` it started matching, to retry with one more character
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    star = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+pub struct AppData {
+    pub app: String,
+    pub kind: &'static str,
+    pub path: String,
+    pub size: u64,
+}
+
+/// Caches, logs and saved state of every app whose bundle id, folder or name matches `pattern`.
+pub fn find_app_data(pattern: &str) -> Vec<AppData> {
+    let mut found = Vec::new();
+    for (dir, part, kind) in APP_RESET_LOCATIONS {
+        let Ok(entries) = fs::read_dir(expand_home(dir)) else { continue };
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            let Some(key) = entry_path.to_str().and_then(owning_app) else { continue };
+            let name = app_display_name(&key);
+            if !wildcard_match(pattern, &key) && !wildcard_match(pattern, &name) {
+                continue;
+            }
+            let path = if part.is_empty() { entry_path } else { entry_path.join(part) };
+            if !path.is_dir() || is_ignored(&path) {
+                continue;
+            }
+            let path = path.to_str().unwrap_or("").to_string();
+            let size = get_directory_size(&path);
+            if size > 0 {
+                found.push(AppData { app: name, kind, path, size });
+            }
+        }
+    }
+    found.sort_by(|a, b| a.app.cmp(&b.app).then(b.size.cmp(&a.size)));
+    found
+}
+
+/// `clean-app`: reset one misbehaving app (or every app matching a glob such as `com.spotify.*`)
+/// by clearing its caches, logs and saved state, without a full sweep.
+pub fn run_clean_app(ctx: &CleanupContext, pattern: &str) {
+    println!("{}", format!("🎯 Cleaning {}", pattern).bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let found = find_app_data(pattern);
+    if found.is_empty() {
+        println!("{} No caches, logs or saved state found for {}", "ℹ".blue(), pattern);
+        return;
+    }
+
+    let total: u64 = found.iter().map(|data| data.size).sum();
+    ctx.record_target("clean_app", total);
+    for data in &found {
+        ctx.record_item(&data.path, data.size);
+        println!("  {} {} {} {} ({})", "•".cyan(), data.app.bold(), data.kind, data.path.dimmed(),
+            format_size(data.size, BINARY).red());
+    }
+    println!("\n  {}", "Quit the app first; a running app may write its caches straight back.".dimmed());
+    show_space_preview(total);
+
+    if !ctx.should_proceed(&format!("Clean {} folders for {}?", found.len(), pattern),
+        Some("Caches are rebuilt on the next launch; windows that were open won't be restored".to_string())) {
+        println!("\n{}", "Cleanup cancelled.".yellow());
+        return;
+    }
+
+    let total_stats = ctx.run_target(|| {
+        let stats = CleanupStats::new();
+        for data in &found {
+            ctx.log_action(&format!("Cleaning {} ({} {})", data.path, data.app, data.kind));
+            stats.add(&clean_directory(&data.path, None, ctx));
+        }
+        stats
+    });
+
+    ctx.finish_run();
+    println!("\n{}", "✨ App cleanup complete!".bold().green());
+    if ctx.dry_run {
+        println!("  {} {}", "Would remove:".bold(), total_stats.files_removed().to_string().yellow());
+    } else {
+        println!("  {} {}", "Removed:".bold(), total_stats.files_removed().to_string().yellow());
+    }
+    show_target_results(ctx, &total_stats);
+    append_history(&ctx.manifest.borrow());
+}
@@ -0,0 +1,100 @@
+//! `diff`: compare two runs target by target.
+
+use crate::*;
+
+pub fn format_delta(delta: i64) -> ColoredString {
+    let magnitude = format_size(delta.unsigned_abs(), BINARY);
+    if delta > 0 {
+        format!("+{}", magnitude).red()
+    } else if delta < 0 {
+        format!("-{}", magnitude).green()
+    } else {
+        "±0 B".dimmed()
+    }
+}
+
+pub fn run_diff(before_path: &Path, after_path: &Path) {
+    let (before, after) = match (read_manifest(before_path), read_manifest(after_path)) {
+        (Ok(before), Ok(after)) => (before, after),
+        (Err(err), _) => {
+            println!("  {} Can't read {}: {}", "✗".red(), before_path.display(), err);
+            return;
+        }
+        (_, Err(err)) => {
+            println!("  {} Can't read {}: {}", "✗".red(), after_path.display(), err);
+            return;
+        }
+    };
+
+    println!("{}", "🔀 Manifest Diff".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {} {}", "Before:".bold(), before.created.dimmed());
+    println!("  {} {}", "After: ".bold(), after.created.dimmed());
+
+    let before_targets: HashMap<&str, &ManifestTarget> = before.targets.iter()
+        .map(|target| (target.id.as_str(), target))
+        .collect();
+
+    // Target-level changes, biggest growth first
+    let mut target_changes: Vec<(&str, u64, u64)> = after.targets.iter()
+        .map(|target| {
+            let old_size = before_targets.get(target.id.as_str()).map(|t| t.size).unwrap_or(0);
+            (target.id.as_str(), old_size, target.size)
+        })
+        .collect();
+    for target in &before.targets {
+        if !after.targets.iter().any(|t| t.id == target.id) {
+            target_changes.push((target.id.as_str(), target.size, 0));
+        }
+    }
+    target_changes.sort_by_key(|(_, old, new)| std::cmp::Reverse(*new as i64 - *old as i64));
+
+    println!("\n{}", "📊 Per-target change:".bold().cyan());
+    for (id, old_size, new_size) in &target_changes {
+        let delta = *new_size as i64 - *old_size as i64;
+        println!("  {:>12}  {} ({} → {})",
+            format_delta(delta),
+            id.bold(),
+            format_size(*old_size, BINARY).dimmed(),
+            format_size(*new_size, BINARY));
+    }
+
+    // Individual paths that appeared or grew
+    let mut grown_items = Vec::new();
+    for target in &after.targets {
+        let old_items: HashMap<&str, u64> = before_targets.get(target.id.as_str())
+            .map(|t| t.items.iter().map(|item| (item.path.as_str(), item.size)).collect())
+            .unwrap_or_default();
+
+        for item in &target.items {
+            let old_size = old_items.get(item.path.as_str()).copied();
+            let growth = item.size as i64 - old_size.unwrap_or(0) as i64;
+            if growth > 0 {
+                grown_items.push((target.id.as_str(), item.path.as_str(), old_size.is_none(), growth));
+            }
+        }
+    }
+    grown_items.sort_by_key(|(_, _, _, growth)| std::cmp::Reverse(*growth));
+
+    if !grown_items.is_empty() {
+        println!("\n{}", "📈 Paths that appeared or grew:".bold().cyan());
+        for (id, path, is_new, growth) in grown_items.iter().take(20) {
+            println!("  {:>12}  {} {} {}",
+                format_delta(*growth),
+                if *is_new { "new".yellow() } else { "grew".normal() },
+                format!("[{}]", id).dimmed(),
+                path);
+        }
+        if grown_items.len() > 20 {
+            println!("  {} ... and {} more", "•".dimmed(), grown_items.len() - 20);
+        }
+    }
+
+    let before_total: u64 = before.targets.iter().map(|t| t.size).sum();
+    let after_total: u64 = after.targets.iter().map(|t| t.size).sum();
+    println!("\n  {} {} ({} → {})",
+        "Total change:".bold(),
+        format_delta(after_total as i64 - before_total as i64),
+        format_size(before_total, BINARY),
+        format_size(after_total, BINARY));
+}
@@ -0,0 +1,129 @@
+//! `digest`: a summary of recent runs, printed or mailed.
+
+use crate::*;
+
+/// Totals over the runs in a digest period.
+#[derive(Default)]
+pub struct DigestSummary {
+    pub runs: usize,
+    pub dry_runs: usize,
+    pub freed: u64,
+    pub errors: usize,
+    pub by_target: BTreeMap<String, u64>,
+}
+
+pub fn summarize_history(history: &[Manifest], since: chrono::DateTime<Local>) -> DigestSummary {
+    let mut summary = DigestSummary::default();
+    let recent = history.iter().filter(|run| {
+        chrono::DateTime::parse_from_rfc3339(&run.created).is_ok_and(|created| created >= since)
+    });
+
+    for run in recent {
+        if run.dry_run {
+            summary.dry_runs += 1;
+            continue;
+        }
+        summary.runs += 1;
+        for target in &run.targets {
+            let Some(result) = &target.result else { continue };
+            summary.freed += result.freed;
+            summary.errors += result.errors;
+            *summary.by_target.entry(target.id.clone()).or_insert(0) += result.freed;
+        }
+    }
+    summary
+}
+
+pub fn run_digest(days: i64, html: bool, output: Option<&Path>, mail: Option<&str>) {
+    let since = Local::now() - chrono::Duration::days(days);
+    let summary = summarize_history(&read_history(), since);
+    let host = Command::new("hostname").arg("-s").output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    let disk = get_disk_info();
+    let reclaimable = read_scan_cache().map(|cache| cache.reclaimable);
+    let forecast = forecast_summary(&junk_growth_rates(&read_history()), disk.available);
+
+    let mut by_target: Vec<(&String, &u64)> = summary.by_target.iter().filter(|(_, freed)| **freed > 0).collect();
+    by_target.sort_by_key(|(_, freed)| std::cmp::Reverse(**freed));
+
+    let title = format!("Mac cleanup digest for {} — last {} days", host, days);
+    let mut lines = vec![
+        format!("Cleanup runs: {} ({} dry runs)", summary.runs, summary.dry_runs),
+        format!("Space freed: {}", format_size(summary.freed, BINARY)),
+        format!("Free now: {} of {} ({:.0}% used)", format_size(disk.available, BINARY), format_size(disk.total, BINARY), disk.percent_used),
+    ];
+    if let Some(reclaimable) = reclaimable {
+        lines.push(format!("Still reclaimable at last scan: {}", format_size(reclaimable, BINARY)));
+    }
+    if summary.errors > 0 {
+        lines.push(format!("Items that failed to delete: {}", summary.errors));
+    }
+    if let Some(forecast) = forecast {
+        lines.push(forecast);
+    }
+
+    let digest = if html {
+        let escape = |text: &str| text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        let mut body = format!("<html><body>\n<h2>{}</h2>\n<ul>\n", escape(&title));
+        for line in &lines {
+            body.push_str(&format!("<li>{}</li>\n", escape(line)));
+        }
+        body.push_str("</ul>\n");
+        if !by_target.is_empty() {
+            body.push_str("<table>\n<tr><th align=\"left\">Target</th><th align=\"right\">Freed</th></tr>\n");
+            for (id, freed) in &by_target {
+                body.push_str(&format!("<tr><td>{}</td><td align=\"right\">{}</td></tr>\n", escape(id), format_size(**freed, BINARY)));
+            }
+            body.push_str("</table>\n");
+        }
+        body + "</body></html>\n"
+    } else {
+        let mut body = format!("{}\n{}\n\n", title, "=".repeat(title.chars().count()));
+        for line in &lines {
+            body.push_str(&format!("{}\n", line));
+        }
+        if !by_target.is_empty() {
+            body.push_str("\nFreed by target:\n");
+            for (id, freed) in &by_target {
+                body.push_str(&format!("  {:<24} {:>12}\n", id, format_size(**freed, BINARY)));
+            }
+        }
+        body
+    };
+
+    if let Some(address) = mail {
+        match send_mail(address, &title, &digest, html) {
+            Ok(()) => println!("{} Digest sent to {}", "✓".green(), address),
+            Err(err) => println!("{} Failed to send digest: {}", "✗".red(), err),
+        }
+    }
+
+    match output {
+        Some(path) => match fs::write(path, &digest) {
+            Ok(()) => println!("{} Digest written to {}", "✓".green(), path.display()),
+            Err(err) => println!("{} Failed to write {}: {}", "✗".red(), path.display(), err),
+        },
+        None if mail.is_none() => print!("{}", digest),
+        None => {}
+    }
+}
+
+/// Hand a message to the local MTA; sendmail reads the recipients from the headers.
+pub fn send_mail(address: &str, subject: &str, body: &str, html: bool) -> io::Result<()> {
+    let content_type = if html { "text/html" } else { "text/plain" };
+    let message = format!("To: {}\nSubject: {}\nMIME-Version: 1.0\nContent-Type: {}; charset=utf-8\n\n{}",
+        address, subject, content_type, body);
+
+    let mut child = Command::new("sendmail")
+        .arg("-t")
+        .stdin(std::process::Stdio::piped())
+        .spawn()?;
+    child.stdin.take().expect("stdin is piped").write_all(message.as_bytes())?;
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("sendmail exited with {}", status)))
+    }
+}
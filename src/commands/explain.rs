@@ -0,0 +1,138 @@
+//! `explain` and `info`: what each target deletes, and what this build is.
+
+use crate::*;
+
+pub fn run_explain(id: Option<&str>, json: bool) {
+    let Some(id) = id else {
+        if json {
+            let targets: Vec<serde_json::Value> = TARGETS.iter().map(explain_json).collect();
+            println!("{}", serde_json::to_string_pretty(&targets).unwrap_or_default());
+            return;
+        }
+        println!("{}", "🔎 Targets".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        for target in TARGETS {
+            println!("  {:<20} {:<34} {}", target.id, target.name, target.risk.label());
+        }
+        println!("\n  {} Run `maccleanup-rust explain <id>` for the details of one", "ℹ".blue());
+        return;
+    };
+
+    let Some(target) = target_info(id) else {
+        println!("{} No target named {:?}; run `maccleanup-rust explain` to list them", "✗".red(), id);
+        std::process::exit(1);
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&explain_json(target)).unwrap_or_default());
+        return;
+    }
+
+    println!("{}", format!("🔎 {} ({})", target.name, target.id).bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {} {}", "Risk:".bold(), target.risk.label());
+
+    let pack = target.app.map(app_pack);
+    let min_os = pack.and_then(|pack| pack.min_os.as_deref());
+    let applies = pack.is_none_or(|pack| pack.supports_this_os());
+    println!("  {} {}{}",
+        "macOS:".bold(),
+        min_os.map(|min_os| format!("{} or later", min_os)).unwrap_or_else(|| "any supported version".to_string()),
+        match macos_version() {
+            Some(current) if applies => format!(" (this Mac runs {}, applies)", current).dimmed(),
+            Some(current) => format!(" (this Mac runs {}, doesn't apply)", current).yellow(),
+            None => "".normal(),
+        });
+    if let Some(warning) = target.warning {
+        println!("  {} {}", "⚠".yellow(), warning);
+    }
+
+    if let Some(pack) = pack {
+        println!("\n  {} {}", "Paths".bold(), format!("(from the {} pack in src/apps.toml)", pack.name).dimmed());
+        for pack_path in &pack.paths {
+            println!("    {} {} {}", "•".dimmed(), pack_path.path, format!("({})", pack_path.safety).dimmed());
+        }
+    }
+    if !target.rules.is_empty() {
+        println!("\n  {}", if pack.is_some() { "Also" } else { "What it does" }.bold());
+        for rule in target.rules {
+            println!("    {} {}", "•".dimmed(), rule);
+        }
+    }
+    println!("\n  {} Paths in ~/.maccleanupignore are never touched; skip the whole target with `skip` in {}",
+        "ℹ".blue(), config_path().display());
+}
+
+/// What's running, for `info`: version, build and the paths it reads and writes.
+pub fn build_info() -> serde_json::Value {
+    let non_empty = |value: &'static str| (!value.is_empty()).then_some(value);
+    let features: Vec<&str> = env!("MACCLEANUP_BUILD_FEATURES").split(',').filter(|f| !f.is_empty()).collect();
+    serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "commit": non_empty(env!("MACCLEANUP_BUILD_COMMIT")),
+        "target": env!("MACCLEANUP_BUILD_TARGET"),
+        "profile": env!("MACCLEANUP_BUILD_PROFILE"),
+        "features": features,
+        "arch": env::consts::ARCH,
+        "macos": macos_version(),
+        "targets": TARGETS.len(),
+        "app_packs": app_packs().len(),
+        "paths": {
+            "config": config_path(),
+            "state": state_dir(),
+            "history": history_path(),
+            "runs": runs_dir(),
+            "quarantine": state_dir().join("quarantine"),
+            "snapshot_mount": state_dir().join("snapshot"),
+            "ignore_file": format!("{}/.maccleanupignore", home_dir()),
+            "serve_socket": state_dir().join("serve.sock"),
+        },
+        "project_roots": project_roots(),
+    })
+}
+
+pub fn run_info(json: bool) {
+    let info = build_info();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info).unwrap_or_default());
+        return;
+    }
+
+    let field = |key: &str| info[key].as_str().unwrap_or("unknown").to_string();
+    println!("{}", format!("ℹ️  maccleanup-rust {}", field("version")).bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {} {}", "Commit:".bold(), field("commit"));
+    println!("  {} {} ({}, running on {})", "Build:".bold(), field("target"), field("profile"), field("arch"));
+    let features = env!("MACCLEANUP_BUILD_FEATURES");
+    println!("  {} {}", "Features:".bold(), if features.is_empty() { "none" } else { features });
+    println!("  {} {}", "macOS:".bold(), field("macos"));
+    println!("  {} {} targets, {} app packs", "Knows:".bold(), TARGETS.len(), app_packs().len());
+
+    println!("\n  {}", "Paths".bold());
+    if let Some(paths) = info["paths"].as_object() {
+        for (name, path) in paths {
+            println!("    {:<14} {}", name, path.as_str().unwrap_or(""));
+        }
+    }
+    println!("\n  {}", "Searched for projects".bold());
+    for root in project_roots() {
+        println!("    {}", root);
+    }
+}
+
+/// Machine-readable description of a target for `explain --json`.
+pub fn explain_json(target: &TargetInfo) -> serde_json::Value {
+    let pack = target.app.map(app_pack);
+    serde_json::json!({
+        "id": target.id,
+        "name": target.name,
+        "risk": target.risk,
+        "warning": target.warning,
+        "min_os": pack.and_then(|pack| pack.min_os.as_deref()),
+        "applies_to_this_mac": pack.is_none_or(|pack| pack.supports_this_os()),
+        "paths": pack.map(|pack| pack.paths.iter()
+            .map(|pack_path| serde_json::json!({ "path": pack_path.path, "safety": pack_path.safety.to_string() }))
+            .collect::<Vec<_>>())
+            .unwrap_or_default(),
+        "rules": target.rules,
+    })
+}
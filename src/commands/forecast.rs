@@ -0,0 +1,80 @@
+//! `forecast`: junk growth rates from the run history.
+
+use crate::*;
+
+/// Average growth of each target's junk in bytes per day, fastest first. Between two runs
+/// that both measured a target, it grew by what the later run found minus what the earlier
+/// one left behind. Targets seen over less than a day have no rate yet.
+pub fn junk_growth_rates(history: &[Manifest]) -> Vec<(String, f64)> {
+    let mut last_seen: BTreeMap<&str, (chrono::DateTime<chrono::FixedOffset>, u64)> = BTreeMap::new();
+    let mut totals: BTreeMap<&str, (u64, f64)> = BTreeMap::new();
+    for run in history {
+        let Ok(created) = chrono::DateTime::parse_from_rfc3339(&run.created) else { continue };
+        for target in &run.targets {
+            if let Some((previous, left)) = last_seen.get(target.id.as_str()) {
+                let days = (created - *previous).num_seconds() as f64 / 86400.0;
+                if days > 0.0 {
+                    let entry = totals.entry(&target.id).or_insert((0, 0.0));
+                    entry.0 += target.size.saturating_sub(*left);
+                    entry.1 += days;
+                }
+            }
+            let freed = match &target.result {
+                Some(result) if !run.dry_run => result.freed,
+                _ => 0,
+            };
+            last_seen.insert(&target.id, (created, target.size.saturating_sub(freed)));
+        }
+    }
+
+    let mut rates: Vec<(String, f64)> = totals.into_iter()
+        .filter(|(_, (bytes, days))| *bytes > 0 && *days >= 1.0)
+        .map(|(id, (bytes, days))| (id.to_string(), bytes as f64 / days))
+        .collect();
+    rates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    rates
+}
+
+/// "Junk grows ~6 GiB/week; at this rate the disk is full in 23 days", if there's a rate.
+pub fn forecast_summary(rates: &[(String, f64)], available: u64) -> Option<String> {
+    let per_day: f64 = rates.iter().map(|(_, rate)| rate).sum();
+    if per_day < 1.0 {
+        return None;
+    }
+    let weekly = format_size((per_day * 7.0) as u64, BINARY);
+    let days_left = (available as f64 / per_day).floor() as u64;
+    Some(if days_left > 365 {
+        format!("Junk grows ~{}/week; at this rate the disk lasts more than a year", weekly)
+    } else {
+        format!("Junk grows ~{}/week; at this rate the disk is full in {} days", weekly, days_left)
+    })
+}
+
+/// `forecast`: turn the run history into growth rates and when cleanup will be needed.
+pub fn run_forecast(top: usize) {
+    println!("{}", "🔮 Junk Forecast".bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let rates = junk_growth_rates(&read_history());
+    if rates.is_empty() {
+        println!("  {} Not enough history yet: a forecast needs runs at least a day apart (dry runs count)", "ℹ".blue());
+        return;
+    }
+
+    for (id, per_day) in rates.iter().take(top) {
+        let name = target_info(id).map_or(id.as_str(), |target| target.name);
+        println!("  {:>12}/week  {}", format_size((per_day * 7.0) as u64, BINARY).red(), name);
+    }
+
+    let disk = get_disk_info();
+    if let Some(summary) = forecast_summary(&rates, disk.available) {
+        println!("\n  {} {} ({} free now)", "📈".cyan(), summary, format_size(disk.available, BINARY));
+    }
+    let (fastest, per_day) = &rates[0];
+    let weekly = (per_day * 7.0) as u64;
+    // Worth its own schedule once it grows by a gigabyte a week
+    if weekly >= 1 << 30 {
+        println!("  {} Cleaning {} weekly keeps it in check: maccleanup-rust --force --only {}",
+            "💡".yellow(), target_info(fastest).map_or(fastest.as_str(), |target| target.name), fastest);
+    }
+}
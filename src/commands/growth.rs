@@ -0,0 +1,99 @@
+//! `growth`: which directories grew since the last snapshot of their sizes.
+
+use crate::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SizeSnapshot {
+    pub created: String,
+    pub sizes: BTreeMap<String, u64>,
+}
+
+/// Sizes of the first two directory levels under `~` and `/Library`.
+pub fn snapshot_directory_sizes() -> BTreeMap<String, u64> {
+    let home = home_dir();
+    let mut sizes = BTreeMap::new();
+
+    for root in [home.as_str(), "/Library"] {
+        let Ok(level_one) = fs::read_dir(root) else { continue };
+        for entry in level_one.flatten() {
+            let Ok(file_type) = entry.file_type() else { continue };
+            if !file_type.is_dir() {
+                continue;
+            }
+
+            let dir = entry.path();
+            let mut dir_size = 0;
+            if let Ok(level_two) = fs::read_dir(&dir) {
+                for child in level_two.flatten() {
+                    match child.file_type() {
+                        Ok(child_type) if child_type.is_dir() => {
+                            let child_path = child.path().to_str().unwrap_or("").to_string();
+                            let child_size = scan_parallel(std::slice::from_ref(&child_path), 4).bytes;
+                            dir_size += child_size;
+                            sizes.insert(child_path, child_size);
+                        }
+                        Ok(child_type) if child_type.is_file() => {
+                            dir_size += child.metadata().map(|m| m.len()).unwrap_or(0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            sizes.insert(dir.to_str().unwrap_or("").to_string(), dir_size);
+        }
+    }
+
+    sizes
+}
+
+pub fn run_growth(reset: bool, top: usize) {
+    let baseline_path = state_dir().join("baseline.json");
+
+    println!("{}", "🔎 Free Space Forensics".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {} Measuring directory sizes under ~ and /Library...", "ℹ".blue());
+    let current = SizeSnapshot {
+        created: Local::now().to_rfc3339(),
+        sizes: snapshot_directory_sizes(),
+    };
+
+    let baseline: Option<SizeSnapshot> = if reset {
+        None
+    } else {
+        fs::read_to_string(&baseline_path).ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+    };
+
+    let Some(baseline) = baseline else {
+        match serde_json::to_string(&current).map(|json| fs::write(&baseline_path, json)) {
+            Ok(Ok(())) => println!("  {} Baseline recorded ({} directories). Run again later to see what grew.",
+                "✓".green(), current.sizes.len()),
+            _ => println!("  {} Failed to write {}", "✗".red(), baseline_path.display()),
+        }
+        return;
+    };
+
+    println!("  {} Comparing against baseline from {}", "ℹ".blue(), baseline.created.dimmed());
+
+    let mut changes: Vec<(&str, Option<u64>, u64)> = current.sizes.iter()
+        .map(|(path, size)| (path.as_str(), baseline.sizes.get(path).copied(), *size))
+        .filter(|(_, old, new)| *new > old.unwrap_or(0))
+        .collect();
+    changes.sort_by_key(|(_, old, new)| std::cmp::Reverse(new - old.unwrap_or(0)));
+
+    if changes.is_empty() {
+        println!("  {} Nothing grew since the baseline", "✓".green());
+        return;
+    }
+
+    println!("\n{}", "📈 Biggest growth since baseline:".bold().cyan());
+    for (path, old, new) in changes.iter().take(top) {
+        let growth = (new - old.unwrap_or(0)) as i64;
+        println!("  {:>12}  {}{}",
+            format_delta(growth),
+            path,
+            if old.is_none() { " (new)".yellow() } else { "".normal() });
+    }
+
+    println!("\n  {} Run with --reset to make today the new baseline", "ℹ".blue());
+}
@@ -0,0 +1,109 @@
+//! `memory status` and `memory top`: RAM, swap and the processes holding most of it.
+
+use crate::*;
+
+pub fn run_memory_status() {
+    println!("{}", "🧠 Memory Status".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    show_ram_status();
+    show_swap_status();
+}
+
+/// Swap from `sysctl vm.swapusage`, e.g. "total = 2048.00M  used = 1117.50M  free = 930.50M".
+pub fn show_swap_status() {
+    let Ok(output) = Command::new("sysctl").args(["-n", "vm.swapusage"]).output() else { return };
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let field = |name: &str| -> Option<String> {
+        let rest = output_str.split(&format!("{} = ", name)).nth(1)?;
+        rest.split_whitespace().next().map(|value| value.to_string())
+    };
+
+    if let (Some(total), Some(used)) = (field("total"), field("used")) {
+        println!("  {} {} / {}", "Swap Used:".bold(), used.yellow(), total);
+    }
+
+    // memory_pressure ends with "System-wide memory free percentage: 42%"
+    if let Ok(output) = Command::new("memory_pressure").output() {
+        let pressure = String::from_utf8_lossy(&output.stdout);
+        if let Some(free) = pressure.lines().find_map(|line| line.strip_prefix("System-wide memory free percentage:")) {
+            println!("  {} {}", "Memory Free:".bold(), free.trim().green());
+        }
+    }
+}
+
+/// Resident memory of one app (all its helper processes) or one standalone process.
+pub struct MemoryHog {
+    pub name: String,
+    pub is_app: bool,
+    pub rss: u64,
+    pub processes: usize,
+}
+
+pub fn list_memory_hogs() -> Vec<MemoryHog> {
+    let Ok(output) = Command::new("ps").args(["-axo", "rss=,comm="]).output() else { return Vec::new() };
+    let mut hogs: BTreeMap<(String, bool), (u64, usize)> = BTreeMap::new();
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((rss, command)) = line.trim().split_once(' ') else { continue };
+        let Ok(rss_kb) = rss.parse::<u64>() else { continue };
+        let command = command.trim();
+
+        // Helpers live inside their app's bundle, so group by the outermost .app
+        let key = match command.split('/').find(|part| part.ends_with(".app")) {
+            Some(bundle) => (bundle.trim_end_matches(".app").to_string(), true),
+            None => (command.rsplit('/').next().unwrap_or(command).to_string(), false),
+        };
+        let entry = hogs.entry(key).or_insert((0, 0));
+        entry.0 += rss_kb * 1024;
+        entry.1 += 1;
+    }
+
+    let mut hogs: Vec<MemoryHog> = hogs.into_iter()
+        .map(|((name, is_app), (rss, processes))| MemoryHog { name, is_app, rss, processes })
+        .collect();
+    hogs.sort_by_key(|hog| std::cmp::Reverse(hog.rss));
+    hogs
+}
+
+pub fn run_memory_top(count: usize, quit: bool) {
+    println!("{}", "🐷 Top Memory Users".bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let hogs: Vec<MemoryHog> = list_memory_hogs().into_iter().take(count).collect();
+    if hogs.is_empty() {
+        println!("  {} Could not list processes", "✗".red());
+        return;
+    }
+
+    for (i, hog) in hogs.iter().enumerate() {
+        println!("    {} {:<32} {:>10}  {}",
+            format!("{:>2}.", i + 1).dimmed(),
+            if hog.is_app { hog.name.bold() } else { hog.name.normal() },
+            format_size(hog.rss, BINARY).red(),
+            format!("{} process{}", hog.processes, if hog.processes == 1 { "" } else { "es" }).dimmed());
+    }
+
+    if !quit {
+        println!("\n  {} Use --quit to close selected apps", "ℹ".blue());
+        return;
+    }
+
+    for i in select_items("Select apps to quit", hogs.len()) {
+        let hog = &hogs[i];
+        if !hog.is_app {
+            println!("  {} {} is not an app; quit it from Activity Monitor", "ℹ".blue(), hog.name);
+            continue;
+        }
+
+        // Ask the app to quit so it can save documents, rather than killing it
+        let script = format!("quit app \"{}\"", hog.name.replace('"', "\\\""));
+        let quit_ok = Command::new("osascript").args(["-e", &script]).status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if quit_ok {
+            println!("  {} Quit {}, freeing about {}", "✓".green(), hog.name, format_size(hog.rss, BINARY));
+        } else {
+            println!("  {} {} did not quit (it may be showing a save dialog)", "✗".red(), hog.name);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Subcommands besides the cleanup run itself, one module each.
+
+pub mod bench;
+pub mod clean_app;
+pub mod diff;
+pub mod digest;
+pub mod explain;
+pub mod forecast;
+pub mod growth;
+pub mod memory;
+pub mod privacy;
+pub mod python_packages;
+pub mod restore;
+pub mod serve;
+pub mod setup;
+pub mod snapshot;
+pub mod status;
+pub mod system_data;
+pub mod watch;
+
+pub use bench::*;
+pub use clean_app::*;
+pub use diff::*;
+pub use digest::*;
+pub use explain::*;
+pub use forecast::*;
+pub use growth::*;
+pub use memory::*;
+pub use privacy::*;
+pub use python_packages::*;
+pub use restore::*;
+pub use serve::*;
+pub use setup::*;
+pub use snapshot::*;
+pub use status::*;
+pub use system_data::*;
+pub use watch::*;
@@ -0,0 +1,174 @@
+//! `privacy`: browser and clipboard history, the pasteboard and Quick Look thumbnails.
+
+use crate::*;
+
+/// Clipboard manager packs in `apps.toml` whose history the privacy sweep clears.
+pub const CLIPBOARD_MANAGERS: &[&str] = &["maccy", "clipy", "paste", "pastebot", "flycut"];
+
+/// History-class paths of the browser packs: history databases, visited links, top sites.
+pub fn find_browser_history() -> Vec<String> {
+    ["safari", "chrome"].iter()
+        .flat_map(|id| app_pack(id).existing_paths())
+        .filter(|(_, safety)| *safety == SafetyClass::History)
+        .map(|(path, _)| path)
+        .collect()
+}
+
+pub fn find_clipboard_history() -> Vec<String> {
+    CLIPBOARD_MANAGERS.iter()
+        .flat_map(|id| app_pack(id).existing_paths())
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Empty the pasteboard with `pbcopy < /dev/null`. An empty clipboard is also what nearby
+/// devices get over Universal Clipboard, so whatever was copied last stops being offered.
+/// `pbcopy` acts on the session it runs in, so only the invoking user's can be cleared.
+pub fn clear_pasteboard(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    if ctx.dry_run {
+        println!("    {} Would clear the clipboard", "→".yellow());
+        return stats;
+    }
+
+    let cleared = Command::new("pbcopy")
+        .stdin(std::process::Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+    if cleared {
+        ctx.log_success("Cleared the clipboard");
+    } else {
+        stats.record_error();
+        ctx.log_error("Couldn't clear the clipboard with pbcopy");
+    }
+    stats
+}
+
+/// QuickLook's thumbnail cache, which keeps previews of files long after they're deleted.
+/// It lives in the per-user temporary cache dir, so only the invoking user's can be found.
+pub fn quicklook_cache_dir() -> Option<String> {
+    if TARGET_USER.get().is_some() {
+        return None;
+    }
+    let output = Command::new("getconf").arg("DARWIN_USER_CACHE_DIR").output().ok()?;
+    let cache_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let dir = format!("{}/com.apple.QuickLook.thumbnailcache", cache_dir.trim_end_matches('/'));
+    (output.status.success() && Path::new(&dir).exists()).then_some(dir)
+}
+
+/// The thumbnail database is held open by quicklookd, so it's reset through qlmanage
+/// rather than deleted underneath it.
+pub fn clean_quicklook_cache(ctx: &CleanupContext, dir: &str) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let size = get_directory_size(dir);
+    ctx.record_item(dir, size);
+
+    if ctx.dry_run {
+        println!("    {} Would reset {} ({})", "→".yellow(), dir, format_size(size, BINARY));
+        stats.record_removed(size);
+        return stats;
+    }
+
+    ctx.log_action("Resetting the QuickLook thumbnail cache...");
+    match Command::new("qlmanage").args(["-r", "cache"]).output() {
+        Ok(output) if output.status.success() => {
+            let freed = size.saturating_sub(get_directory_size(dir));
+            stats.record_removed(freed);
+            ctx.log_success(&format!("Reset QuickLook thumbnails, freed {}", format_size(freed, BINARY)));
+        }
+        Ok(output) => {
+            stats.record_error();
+            ctx.log_error(&format!("qlmanage failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Err(err) => {
+            stats.record_error();
+            ctx.log_error(&format!("Couldn't run qlmanage: {}", err));
+        }
+    }
+    stats
+}
+
+/// `privacy`: clear traces of what was browsed, opened and copied in one confirmed pass,
+/// independent of the space-recovery targets and their risk settings.
+pub fn run_privacy(ctx: &CleanupContext) {
+    println!("{}", "🕵️  Privacy Sweep".bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let history = find_browser_history();
+    let clipboard = find_clipboard_history();
+    let recent_items = tasks::recent_items::find_recent_items(&ctx.keep_recents);
+    let quicklook_dir = quicklook_cache_dir();
+
+    let history_size = get_paths_size(&history);
+    let cookies_size = tasks::cookies::estimate_cookies_size();
+    let recent_size = get_paths_size(&recent_items);
+    let quicklook_size = quicklook_dir.as_deref().map(get_directory_size).unwrap_or(0);
+    let clipboard_size = get_paths_size(&clipboard);
+
+    let steps = [
+        ("Browsing history", history_size, "Safari and Chrome history, visited links and top sites"),
+        ("Cookies & web data", cookies_size, "Signs you out of websites"),
+        ("Recent items", recent_size, "Recent Items and every app's Open Recent menu"),
+        ("QuickLook thumbnails", quicklook_size, "Previews of files you've looked at, even deleted ones"),
+        ("Clipboard history", clipboard_size, "Everything clipboard managers remember being copied"),
+    ];
+    for (name, size, explanation) in &steps {
+        println!("  {} {:<22} {:>10}  {}", "•".cyan(), name, format_size(*size, BINARY), explanation.dimmed());
+    }
+    let own_account = TARGET_USER.get().is_none();
+    if own_account {
+        println!("  {} {:<22} {:>10}  {}", "•".cyan(), "Clipboard", "", "What's copied now, also shared over Universal Clipboard".dimmed());
+    } else {
+        println!("  {} QuickLook thumbnails and the clipboard can only be cleared for your own account", "ℹ".blue());
+    }
+
+    let total: u64 = steps.iter().map(|(_, size, _)| size).sum();
+    if total == 0 && !own_account {
+        println!("\n{} Nothing to clear.", "✓".green());
+        return;
+    }
+    println!("\n  {}", "This frees little space; run maccleanup-rust without `privacy` for that.".dimmed());
+
+    if !ctx.should_proceed("Run the privacy sweep?", Some(format!("Clears {} of history, cookies and recents", format_size(total, BINARY)))) {
+        println!("\n{}", "Privacy sweep cancelled.".yellow());
+        return;
+    }
+
+    let total_stats = CleanupStats::new();
+
+    ctx.record_target("privacy_history", history_size);
+    total_stats.add(&ctx.run_target(|| clean_paths(ctx, &history, "history files")));
+
+    ctx.record_target("cookies", cookies_size);
+    total_stats.add(&ctx.run_target(|| tasks::cookies::clean_cookies(ctx)));
+
+    ctx.record_target("recent_items", recent_size);
+    total_stats.add(&ctx.run_target(|| tasks::recent_items::clean_recent_items(ctx, &recent_items)));
+
+    if let Some(dir) = &quicklook_dir {
+        ctx.record_target("quicklook", quicklook_size);
+        total_stats.add(&ctx.run_target(|| clean_quicklook_cache(ctx, dir)));
+    }
+
+    ctx.record_target("clipboard", clipboard_size);
+    total_stats.add(&ctx.run_target(|| clean_paths(ctx, &clipboard, "clipboard histories")));
+
+    if own_account {
+        total_stats.add(&clear_pasteboard(ctx));
+    }
+
+    ctx.finish_run();
+    println!("\n{}", "✨ Privacy sweep complete!".bold().green());
+    if ctx.dry_run {
+        println!("  {} {}", "Would remove:".bold(), total_stats.files_removed().to_string().yellow());
+    } else {
+        println!("  {} {}", "Removed:".bold(), total_stats.files_removed().to_string().yellow());
+    }
+    show_target_results(ctx, &total_stats);
+    if !ctx.dry_run {
+        println!("\n  {}", "Browsers and clipboard managers that were open may keep some history until they quit.".dimmed());
+    }
+    append_history(&ctx.manifest.borrow());
+}
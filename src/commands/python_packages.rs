@@ -0,0 +1,129 @@
+//! `python-packages`: the largest installed Python packages per interpreter.
+
+use crate::*;
+
+/// Global and user site-packages for system, python.org, Homebrew and pyenv interpreters.
+pub fn find_site_packages() -> Vec<(String, PathBuf)> {
+    let home = home_dir();
+    let mut found = Vec::new();
+
+    let mut add_matches = |label: &str, base: &str| {
+        // <base>/lib/python3.x/site-packages
+        let lib = Path::new(base).join("lib");
+        for entry in fs::read_dir(&lib).into_iter().flat_map(|entries| entries.flatten()) {
+            let name = entry.file_name().to_str().unwrap_or("").to_string();
+            let site = entry.path().join("site-packages");
+            if name.starts_with("python") && site.is_dir() {
+                found.push((format!("{} ({})", label, name), site));
+            }
+        }
+    };
+
+    add_matches("Homebrew", "/opt/homebrew");
+    add_matches("Homebrew", "/usr/local");
+    for (label, parent) in [
+        ("pyenv", format!("{}/.pyenv/versions", home)),
+        ("python.org", "/Library/Frameworks/Python.framework/Versions".to_string()),
+        ("user", format!("{}/Library/Python", home)),
+    ] {
+        for entry in fs::read_dir(&parent).into_iter().flat_map(|entries| entries.flatten()) {
+            let version = entry.file_name().to_str().unwrap_or("").to_string();
+            add_matches(&format!("{} {}", label, version), entry.path().to_str().unwrap_or(""));
+        }
+    }
+
+    found
+}
+
+pub struct PythonPackage {
+    pub name: String,
+    pub version: String,
+    pub interpreter: String,
+    pub size: u64,
+    pub last_used_days: Option<u64>,
+}
+
+/// Packages in one site-packages dir, read from `*.dist-info` metadata.
+pub fn list_site_packages(interpreter: &str, site: &Path) -> Vec<PythonPackage> {
+    let mut packages = Vec::new();
+
+    for entry in fs::read_dir(site).into_iter().flat_map(|entries| entries.flatten()) {
+        let dir_name = entry.file_name().to_str().unwrap_or("").to_string();
+        let Some(stem) = dir_name.strip_suffix(".dist-info") else { continue };
+        let (name, version) = stem.rsplit_once('-').unwrap_or((stem, ""));
+
+        // top_level.txt names the importable modules; fall back to the normalized name
+        let modules: Vec<String> = fs::read_to_string(entry.path().join("top_level.txt"))
+            .map(|contents| contents.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect())
+            .unwrap_or_else(|_| vec![name.to_lowercase().replace('-', "_")]);
+
+        let mut size = get_directory_size(entry.path().to_str().unwrap_or(""));
+        let mut last_used = None;
+        for module in &modules {
+            let module_dir = site.join(module);
+            size += get_directory_size(module_dir.to_str().unwrap_or(""));
+            // Importing reads the package's __init__, which bumps its access time
+            if let Ok(accessed) = fs::metadata(module_dir.join("__init__.py")).and_then(|m| m.accessed()) {
+                last_used = last_used.max(Some(accessed));
+            }
+        }
+
+        packages.push(PythonPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            interpreter: interpreter.to_string(),
+            size,
+            last_used_days: last_used.and_then(|t| t.elapsed().ok()).map(|e| e.as_secs() / 86400),
+        });
+    }
+
+    packages
+}
+
+pub fn run_python_packages(min_size: u64) {
+    println!("{}", "🐍 Python Package Audit".bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let interpreters = find_site_packages();
+    if interpreters.is_empty() {
+        println!("  {} No Python site-packages directories found", "ℹ".blue());
+        return;
+    }
+
+    let mut packages = Vec::new();
+    for (interpreter, site) in &interpreters {
+        let found = list_site_packages(interpreter, site);
+        let total: u64 = found.iter().map(|package| package.size).sum();
+        println!("  {} {} {} ({} packages, {})",
+            "•".dimmed(), interpreter.bold(), site.display().to_string().dimmed(), found.len(), format_size(total, BINARY));
+        packages.extend(found);
+    }
+
+    // How many interpreters carry each package, to spot duplicated giants
+    let mut copies: HashMap<String, usize> = HashMap::new();
+    for package in &packages {
+        *copies.entry(package.name.to_lowercase()).or_default() += 1;
+    }
+
+    packages.retain(|package| package.size >= min_size);
+    packages.sort_by_key(|package| std::cmp::Reverse(package.size));
+
+    println!("\n{}", format!("📦 Packages of {} or more:", format_size(min_size, BINARY)).bold().cyan());
+    for package in &packages {
+        let duplicates = copies.get(&package.name.to_lowercase()).copied().unwrap_or(1);
+        println!("  {:>12}  {} {} {}{}{}",
+            format_size(package.size, BINARY).red(),
+            package.name.bold(),
+            package.version.dimmed(),
+            format!("[{}]", package.interpreter).dimmed(),
+            match package.last_used_days {
+                Some(days) if days >= 90 => format!(" unused {} days", days).yellow(),
+                _ => "".normal(),
+            },
+            if duplicates > 1 { format!(" in {} interpreters", duplicates).yellow() } else { "".normal() });
+    }
+
+    let total: u64 = packages.iter().map(|package| package.size).sum();
+    println!("\n  {} {} in {} packages; remove with `<python> -m pip uninstall <name>`",
+        "ℹ".blue(), format_size(total, BINARY), packages.len());
+}
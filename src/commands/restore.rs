@@ -0,0 +1,119 @@
+//! `restore`: put back what a run moved to the Trash or the quarantine folder.
+
+use crate::*;
+
+/// Runs with a deletion manifest, oldest first (run ids start with their timestamp).
+pub fn runs_with_deletions() -> Vec<String> {
+    let mut runs: Vec<String> = fs::read_dir(state_dir().join("quarantine")).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| entry.file_name().to_str().map(String::from))
+        .filter(|run| !read_deletions(run).is_empty())
+        .collect();
+    runs.sort();
+    runs
+}
+
+/// `restore`: put back what a run moved to the Trash or staged with `--recoverable`.
+/// Without a run, lists the runs that have something to restore.
+pub fn run_restore(id: Option<&str>, last: bool, purge: bool) {
+    println!("{}", "↩️  Restore".bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let runs = runs_with_deletions();
+    if purge {
+        let staged: Vec<PathBuf> = runs.iter()
+            .map(|run| quarantine_dir(run).join("files"))
+            .filter(|files| files.exists())
+            .collect();
+        let size = staged.iter().map(|files| get_directory_size(files.to_str().unwrap_or(""))).sum::<u64>();
+        let removed = staged.iter().filter(|files| fs::remove_dir_all(files).is_ok()).count();
+        println!("  {} Freed {} staged by {} runs; their deletion manifests are kept",
+            "✓".green(), format_size(size, BINARY), removed);
+        return;
+    }
+
+    let run = match id {
+        Some(id) => id.to_string(),
+        None if last => match runs.last() {
+            Some(run) => run.clone(),
+            None => {
+                println!("  {} No run has recorded any deletions yet", "ℹ".blue());
+                return;
+            }
+        },
+        None => {
+            if runs.is_empty() {
+                println!("  {} No run has recorded any deletions yet", "ℹ".blue());
+            }
+            for run in &runs {
+                let records = read_deletions(run);
+                let restorable = records.iter()
+                    .filter(|record| record.moved_to.as_deref().is_some_and(|dest| Path::new(dest).exists()))
+                    .count();
+                println!("  {} {}  {} removed, {} restorable", "•".dimmed(), run, records.len(), restorable);
+            }
+            println!("\n  {} Run `maccleanup-rust restore <id>` or `restore --last`", "ℹ".blue());
+            return;
+        }
+    };
+
+    if read_deletions(&run).is_empty() {
+        println!("  {} Run {} has no deletion manifest", "✗".red(), run);
+        return;
+    }
+    println!("  {} Restoring run {}\n", "ℹ".blue(), run.bold());
+
+    let (restored, permanent) = restore_run(&run);
+    println!("\n  {} Restored {} items", "✓".green(), restored);
+    if permanent > 0 {
+        println!("  {} {} items were deleted outright and can't be restored; run with --recoverable to keep them",
+            "ℹ".blue(), permanent);
+        println!("  {} A local Time Machine snapshot from before the run may still have them: `maccleanup-rust snapshot browse {}`",
+            "ℹ".blue(), run);
+    }
+}
+
+/// Move what run `run` put in the Trash or the quarantine folder back to the original paths,
+/// one line per item. Returns how many came back and how many had been deleted outright.
+pub fn restore_run(run: &str) -> (usize, usize) {
+    let records = read_deletions(run);
+    let mut restored = 0;
+    let mut remaining = Vec::new();
+    let mut permanent = 0;
+    for record in records {
+        let Some(moved_to) = record.moved_to.clone() else {
+            permanent += 1;
+            remaining.push(record);
+            continue;
+        };
+        let original = Path::new(&record.path);
+        if !Path::new(&moved_to).exists() {
+            println!("    {} {} {}", "–".dimmed(), record.path.dimmed(), "(no longer in the Trash)".dimmed());
+            remaining.push(record);
+            continue;
+        }
+        if fs::symlink_metadata(original).is_ok() {
+            println!("    {} {} already exists; its copy stays in {}", "⚠".yellow(), record.path, moved_to);
+            remaining.push(record);
+            continue;
+        }
+        let moved_back = original.parent().map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| move_path(Path::new(&moved_to), original));
+        match moved_back {
+            Ok(()) => {
+                println!("    {} {}", "✓".green(), record.path);
+                restored += 1;
+            }
+            Err(err) => {
+                println!("    {} {}: {}", "✗".red(), record.path, err);
+                remaining.push(record);
+            }
+        }
+    }
+
+    // Keep only what wasn't put back, so restoring twice doesn't report the same items
+    let lines: Vec<String> = remaining.iter().filter_map(|record| serde_json::to_string(record).ok()).collect();
+    let _ = fs::write(quarantine_dir(run).join("deletions.jsonl"),
+        if lines.is_empty() { String::new() } else { lines.join("\n") + "\n" });
+    (restored, permanent)
+}
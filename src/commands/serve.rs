@@ -0,0 +1,172 @@
+//! `serve`: JSON-RPC over a Unix socket for menu bar apps and scripts.
+
+use crate::*;
+
+/// Listen for newline-delimited JSON-RPC 2.0 requests, one client at a time.
+///
+/// Only the owner can open the socket, and every request must carry the token from
+/// `~/.maccleanup/serve.token` in `params.token`.
+pub fn run_serve(socket: &str) {
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+
+    let socket = expand_home(socket);
+    // A socket left by an earlier server that didn't shut down cleanly
+    if fs::symlink_metadata(&socket).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        let _ = fs::remove_file(&socket);
+    }
+
+    let token = match new_serve_token() {
+        Ok(token) => token,
+        Err(err) => {
+            println!("{} Failed to write {}: {}", "✗".red(), serve_token_path().display(), err);
+            std::process::exit(1);
+        }
+    };
+    // Create the socket owner-only, so no one else can connect before its mode is set
+    // SAFETY: umask only swaps the process' file mode mask and cannot fail
+    let previous_umask = unsafe { libc::umask(0o077) };
+    let bound = UnixListener::bind(&socket);
+    // SAFETY: as above
+    unsafe { libc::umask(previous_umask) };
+    let listener = match bound {
+        Ok(listener) => listener,
+        Err(err) => {
+            println!("{} Failed to listen on {}: {}", "✗".red(), socket, err);
+            std::process::exit(1);
+        }
+    };
+    let _ = fs::set_permissions(&socket, fs::Permissions::from_mode(0o600));
+
+    println!("{} Listening on {}", "🔌".cyan(), socket.bold());
+    println!("  {} Clients authenticate with the token in {}", "ℹ".blue(), serve_token_path().display());
+    println!("  {} Methods: targets, scan, clean, undo (Ctrl-C to stop)", "ℹ".blue());
+
+    for stream in listener.incoming().flatten() {
+        let Ok(mut writer) = stream.try_clone() else { continue };
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = handle_rpc(&line, &token);
+            if writeln!(writer, "{}", response).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+pub fn serve_token_path() -> PathBuf {
+    state_dir().join("serve.token")
+}
+
+/// Fresh random token, readable only by the owner; a new one for every server start.
+pub fn new_serve_token() -> io::Result<String> {
+    use std::io::Read;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut bytes = [0u8; 32];
+    fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    let token: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    let path = serve_token_path();
+    let _ = fs::remove_file(&path);
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).mode(0o600).open(&path)?;
+    file.write_all(token.as_bytes())?;
+    Ok(token)
+}
+
+/// Answer one JSON-RPC request line.
+pub fn handle_rpc(line: &str, token: &str) -> serde_json::Value {
+    let error = |id: &serde_json::Value, code: i64, message: &str| serde_json::json!({
+        "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message },
+    });
+
+    let Ok(request) = serde_json::from_str::<serde_json::Value>(line) else {
+        return error(&serde_json::Value::Null, -32700, "parse error");
+    };
+    let id = &request["id"];
+    let params = &request["params"];
+    if params["token"].as_str() != Some(token) {
+        return error(id, -32001, "missing or wrong token");
+    }
+
+    let result = match request["method"].as_str().unwrap_or("") {
+        "targets" => Ok(serde_json::Value::Array(TARGETS.iter().map(explain_json).collect())),
+        "scan" => Ok(rpc_scan()),
+        "clean" => rpc_clean(params),
+        "undo" => rpc_undo(params),
+        _ => Err((-32601, "method not found".to_string())),
+    };
+    match result {
+        Ok(result) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error(id, code, &message),
+    }
+}
+
+/// Estimated reclaimable bytes for every target the server can clean.
+pub fn rpc_scan() -> serde_json::Value {
+    let targets: Vec<serde_json::Value> = SERVICE_TARGETS.iter()
+        .filter_map(|id| tasks::task(id))
+        .map(|task| serde_json::json!({
+            "id": task.id(),
+            "risk": task.risk(),
+            "size": task.estimate(),
+        }))
+        .collect();
+    serde_json::json!({ "targets": targets })
+}
+
+/// Clean `params.targets` without prompts, honoring the config, `max_risk` and `max_delete`.
+pub fn rpc_clean(params: &serde_json::Value) -> Result<serde_json::Value, (i64, String)> {
+    let ids: Vec<&str> = params["targets"].as_array()
+        .map(|ids| ids.iter().filter_map(|id| id.as_str()).collect())
+        .unwrap_or_default();
+    if ids.is_empty() {
+        return Err((-32602, "params.targets must list target ids".to_string()));
+    }
+    let dry_run = params["dry_run"].as_bool().unwrap_or(false);
+    let max_risk: Risk = serde_json::from_value(params["max_risk"].clone()).unwrap_or(Risk::Moderate);
+    let max_delete = params["max_delete"].as_u64();
+
+    let _run_lock = if dry_run { None } else { Some(acquire_run_lock(false).map_err(|message| (-32003, message))?) };
+    let mut ctx = CleanupContext::unattended(dry_run, max_risk, max_delete);
+    // Staged rather than deleted, so a client can take the clean back with `undo`
+    ctx.recoverable = true;
+    let mut refused = Vec::new();
+    for id in ids {
+        let Some(task) = tasks::task(id).filter(|_| SERVICE_TARGETS.contains(&id)) else {
+            refused.push(serde_json::json!({ "id": id, "reason": "not available over the socket" }));
+            continue;
+        };
+        if !ctx.wants(id) {
+            refused.push(serde_json::json!({ "id": id, "reason": "skipped by config or max_risk" }));
+            continue;
+        }
+        ctx.record_target(id, task.estimate());
+        ctx.run_target(|| task.clean(&ctx));
+    }
+
+    let manifest = ctx.manifest.borrow();
+    if !dry_run {
+        append_history(&manifest);
+    }
+    let run_id = ctx.run.borrow().id.clone();
+    Ok(serde_json::json!({ "run_id": run_id, "dry_run": dry_run, "targets": manifest.targets, "refused": refused }))
+}
+
+/// Put back what `params.run_id` removed, or the most recent run that removed anything.
+pub fn rpc_undo(params: &serde_json::Value) -> Result<serde_json::Value, (i64, String)> {
+    let run = match params["run_id"].as_str() {
+        Some(run) => run.to_string(),
+        None => runs_with_deletions().pop().ok_or((-32002, "no run has recorded any deletions".to_string()))?,
+    };
+    if read_deletions(&run).is_empty() {
+        return Err((-32002, format!("run {} has nothing left to undo", run)));
+    }
+
+    let _run_lock = acquire_run_lock(false).map_err(|message| (-32003, message))?;
+    let (restored, permanent) = restore_run(&run);
+    Ok(serde_json::json!({ "run_id": run, "restored": restored, "permanent": permanent }))
+}
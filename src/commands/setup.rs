@@ -0,0 +1,104 @@
+//! `setup`: the first-run wizard.
+
+use crate::*;
+
+pub fn ask_yes_no(question: &str) -> bool {
+    print!("  {} {} {} ", "?".cyan(), question, "(y/N):".yellow());
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes"
+}
+
+/// First-run wizard: show what's installed, explain the risky targets and save which to skip.
+pub fn run_setup() -> Config {
+    println!("{}", "👋 First-Time Setup".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  A few questions so you're only asked about things you want cleaned.\n");
+
+    let command_works = |program: &str, arg: &str| Command::new(program).arg(arg).output().is_ok();
+    let tools = [
+        ("Xcode", tasks::xcode::check_xcode_installed()),
+        ("Homebrew", tasks::homebrew::check_homebrew_installed()),
+        ("Google Chrome", app_pack("chrome").is_installed()),
+        ("Docker", tasks::docker::check_docker_installed()),
+        ("Podman", tasks::podman::check_podman_installed()),
+        ("Vagrant", command_works("vagrant", "--version")),
+        ("kind / k3d", command_works("kind", "version") || command_works("k3d", "version")),
+    ];
+    println!("  {}", "Detected on this Mac:".bold());
+    for (name, installed) in tools {
+        if installed {
+            println!("    {} {}", "✓".green(), name);
+        } else {
+            println!("    {} {}", "–".dimmed(), format!("{} (not installed, skipped)", name).dimmed());
+        }
+    }
+
+    let installed = |id: &str| match id {
+        "docker" => tasks::docker::check_docker_installed(),
+        "podman" => tasks::podman::check_podman_installed(),
+        "kubernetes" => command_works("kind", "version") || command_works("k3d", "version"),
+        _ => true,
+    };
+    let risky: Vec<(&TargetInfo, &str)> = TARGETS.iter()
+        .filter_map(|target| Some((target, target.warning?)))
+        .filter(|(target, _)| installed(target.id))
+        .collect();
+
+    println!("\n  {}", "These targets delete things you might miss:".bold());
+    for (target, warning) in &risky {
+        println!("    {} {} {}: {}", "⚠".yellow(), target.risk.label(), target.name.bold(), warning.dimmed());
+    }
+
+    println!("\n  {}", "How careful should cleanup be?".bold());
+    println!("    1. Careful  - only caches, logs and other files that are recreated (never offer the above)");
+    println!("    2. Balanced - let me pick which of the above to offer");
+    println!("    3. Thorough - offer everything");
+    print!("  {} {} ", "?".cyan(), "Choose 1-3 (Enter for Careful):".yellow());
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+
+    // Only the wizard's answers are replaced; everything else stays as the user wrote it
+    let previous = load_config().unwrap_or_default();
+    let config = match input.trim() {
+        "3" => Config::default(),
+        "2" => {
+            println!();
+            let skip = risky.iter()
+                .filter(|(target, _)| !ask_yes_no(&format!("Offer {}?", target.name)))
+                .map(|(target, _)| target.id.to_string())
+                .collect();
+            Config { skip, ..Config::default() }
+        }
+        _ => Config { max_risk: Some(Risk::Safe), ..Config::default() },
+    };
+    let config = Config {
+        mode: previous.mode,
+        only: previous.only,
+        budgets: previous.budgets,
+        keep_recents: previous.keep_recents,
+        keep_device_support: previous.keep_device_support,
+        failure_cooldown_days: previous.failure_cooldown_days,
+        max_age_days: previous.max_age_days,
+        extra_paths: previous.extra_paths,
+        backup_command: previous.backup_command,
+        ..config
+    };
+
+    match save_config(&config) {
+        Ok(()) => println!("\n  {} Saved to {} (run `maccleanup-rust setup` to change it)",
+            "✓".green(), config_path().display()),
+        Err(err) => println!("\n  {} Failed to save {}: {}", "✗".red(), config_path().display(), err),
+    }
+    if let Some(max_risk) = config.max_risk {
+        println!("  {} Only targets up to {} risk will be offered", "ℹ".blue(), max_risk.label());
+    }
+    if !config.skip.is_empty() {
+        println!("  {} {}", "ℹ".blue(), format!("Skipping: {}", config.skip.join(", ")).dimmed());
+    }
+    println!();
+    config
+}
@@ -0,0 +1,121 @@
+//! `snapshot browse`: copy items a run deleted back out of a local APFS snapshot.
+
+use crate::*;
+
+/// The volume holding user data; its local Time Machine snapshots are what `snapshot browse` mounts.
+pub const DATA_VOLUME: &str = "/System/Volumes/Data";
+
+/// Local Time Machine snapshots with the time each was taken, oldest first.
+pub fn local_snapshots() -> Vec<(String, chrono::NaiveDateTime)> {
+    let Ok(output) = Command::new("tmutil").args(["listlocalsnapshots", "/"]).output() else { return Vec::new() };
+    let mut snapshots: Vec<(String, chrono::NaiveDateTime)> = String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| {
+            // com.apple.TimeMachine.2024-05-02-101500.local
+            let stamp = line.trim().strip_prefix("com.apple.TimeMachine.")?.strip_suffix(".local")?;
+            let taken = chrono::NaiveDateTime::parse_from_str(stamp, "%Y-%m-%d-%H%M%S").ok()?;
+            Some((line.trim().to_string(), taken))
+        })
+        .collect();
+    snapshots.sort_by_key(|(_, taken)| *taken);
+    snapshots
+}
+
+/// `snapshot browse`: mount the newest local snapshot taken before a run read-only, list what
+/// the run deleted outright that the snapshot still has, and copy chosen items back. The
+/// snapshot stays mounted for diffing by hand until Enter is pressed.
+pub fn run_snapshot_browse(id: Option<&str>) {
+    println!("{}", "📸 Snapshot Browser".bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let Some(run) = id.map(String::from).or_else(|| runs_with_deletions().pop()) else {
+        println!("  {} No run has recorded any deletions yet", "ℹ".blue());
+        return;
+    };
+    // Run ids start with the local time the run started
+    let Some(started) = run.get(..15).and_then(|stamp| chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d-%H%M%S").ok()) else {
+        println!("  {} {} isn't a run id (see `maccleanup-rust restore`)", "✗".red(), run);
+        return;
+    };
+    let Some((snapshot, taken)) = local_snapshots().into_iter().rfind(|(_, taken)| *taken <= started) else {
+        println!("  {} No local Time Machine snapshot from before run {}; macOS thins them within a day or so",
+            "ℹ".blue(), run);
+        return;
+    };
+
+    // Items moved to the Trash or staged can still come back with `restore`
+    let records: Vec<DeletionRecord> = read_deletions(&run).into_iter()
+        .filter(|record| record.moved_to.as_deref().is_none_or(|dest| !Path::new(dest).exists()))
+        .collect();
+
+    let mount_point = state_dir().join("snapshot");
+    let _ = fs::create_dir_all(&mount_point);
+    println!("  {} Mounting {} (taken {}, before run {}) read-only (requires sudo)",
+        "ℹ".blue(), snapshot, taken.format("%Y-%m-%d %H:%M"), run.bold());
+    let mounted = Command::new("sudo")
+        .args(["mount_apfs", "-o", "rdonly,nobrowse", "-s", &snapshot, DATA_VOLUME])
+        .arg(&mount_point)
+        .status()
+        .is_ok_and(|status| status.success());
+    if !mounted {
+        println!("  {} Couldn't mount {}", "✗".red(), snapshot);
+        return;
+    }
+
+    let found: Vec<(&DeletionRecord, PathBuf)> = records.iter()
+        .map(|record| (record, mount_point.join(record.path.trim_start_matches('/'))))
+        .filter(|(_, before)| fs::symlink_metadata(before).is_ok())
+        .collect();
+    if found.is_empty() {
+        println!("\n  {} The snapshot has none of the {} items run {} deleted outright", "ℹ".blue(), records.len(), run);
+    } else {
+        println!("\n  {} deleted by run {} are in the snapshot:", format!("{} items", found.len()).bold(), run);
+    }
+    for (i, (record, _)) in found.iter().enumerate() {
+        let now = if fs::symlink_metadata(&record.path).is_ok() {
+            format!("recreated, {} now", format_size(get_directory_size(&record.path), BINARY))
+        } else {
+            "gone".to_string()
+        };
+        println!("    {} {} ({} before, {}) {}",
+            format!("{:>2}.", i + 1).dimmed(),
+            record.path,
+            format_size(record.size, BINARY).yellow(),
+            now,
+            format!("[{}]", record.target).dimmed());
+    }
+
+    if !found.is_empty() {
+        let mut restored = 0;
+        for i in select_items("Select items to copy back", found.len()) {
+            let (record, before) = &found[i];
+            if fs::symlink_metadata(&record.path).is_ok() {
+                println!("    {} {} exists again; compare it with: diff -r {:?} {:?}",
+                    "⚠".yellow(), record.path, before, record.path);
+                continue;
+            }
+            // ditto creates missing parent folders and keeps permissions and extended attributes
+            let copied = Command::new("ditto").arg(before).arg(&record.path).status()
+                .is_ok_and(|status| status.success());
+            if copied {
+                println!("    {} {}", "✓".green(), record.path);
+                restored += 1;
+            } else {
+                println!("    {} Couldn't copy back {}", "✗".red(), record.path);
+            }
+        }
+        println!("\n  {} Copied back {} items", "✓".green(), restored);
+    }
+
+    print!("\n  {} Snapshot mounted at {}; browse or diff it, then press Enter to unmount ",
+        "?".cyan(), mount_point.display());
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    let _ = io::stdin().read_line(&mut input);
+    let unmounted = Command::new("sudo").arg("umount").arg(&mount_point).status()
+        .is_ok_and(|status| status.success());
+    if unmounted {
+        println!("  {} Unmounted {}", "✓".green(), snapshot);
+    } else {
+        println!("  {} Couldn't unmount {}; run: sudo umount {:?}", "✗".red(), mount_point.display(), mount_point);
+    }
+}
@@ -0,0 +1,50 @@
+//! `status`: a one-line summary of reclaimable space from the last scan.
+
+use crate::*;
+
+/// Bare line like `⚠︎ 38GB reclaimable`; prints nothing until a run has been cached.
+pub fn print_status_line() {
+    if let Some(cache) = read_scan_cache() {
+        let size = format_size(cache.reclaimable, DECIMAL.decimal_places(0).space_after_value(false));
+        let icon = if cache.reclaimable >= 10_000_000_000 { "⚠︎" } else { "✓" };
+        println!("{} {} reclaimable", icon, size);
+    }
+}
+
+/// `status`, or with `quick` a fresh shallow estimate of every target instead of the last scan.
+pub fn run_status(quick: bool) {
+    if quick {
+        println!("{}", "📊 Quick Estimate (approximate)".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let mut targets: Vec<(&str, u64)> = tasks::TASKS.iter()
+            .filter(|task| !task.opt_in())
+            .map(|task| (task.id(), quick_estimate(*task, None)))
+            .filter(|(_, size)| *size > 0)
+            .collect();
+        targets.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        let total: u64 = targets.iter().map(|(_, size)| size).sum();
+        println!("  {} ≈ {}", "Reclaimable:".bold(), format_size(total, BINARY).yellow());
+        for (id, size) in targets {
+            println!("    {} {:<20} ≈ {}", "•".dimmed(), id, format_size(size, BINARY));
+        }
+        println!("\n  {}", format!("Folders were sized {} levels deep only; run --dry-run for exact figures", SHALLOW_SCAN_DEPTH).dimmed());
+        return;
+    }
+
+    println!("{}", "📊 Last Scan".bold());
+    println!("{}", "─".repeat(40).dimmed());
+
+    let Some(cache) = read_scan_cache() else {
+        println!("  {} No scan cached yet - run `maccleanup-rust --dry-run` first", "ℹ".blue());
+        return;
+    };
+
+    println!("  {} {}", "Scanned:".bold(), cache.created.dimmed());
+    println!("  {} {}", "Reclaimable:".bold(), format_size(cache.reclaimable, BINARY).yellow());
+
+    let mut targets: Vec<(&String, &u64)> = cache.targets.iter().filter(|(_, size)| **size > 0).collect();
+    targets.sort_by_key(|(_, size)| std::cmp::Reverse(**size));
+    for (id, size) in targets {
+        println!("    {} {:<20} {}", "•".dimmed(), id, format_size(*size, BINARY));
+    }
+}
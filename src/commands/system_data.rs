@@ -0,0 +1,108 @@
+//! `system-data`: what macOS counts as System Data, measured by category.
+
+use crate::*;
+
+/// One slice of macOS' opaque "System Data" storage category.
+pub struct SystemDataCategory {
+    pub label: &'static str,
+    pub paths: &'static [&'static str],
+    /// Targets that reclaim part of it; each appears under one category only
+    pub targets: &'static [&'static str],
+    /// What to do about it instead, when no target reclaims it all
+    pub advice: &'static str,
+}
+
+/// Where "System Data" usually goes. VM disks and local snapshots are measured separately.
+pub const SYSTEM_DATA_CATEGORIES: &[SystemDataCategory] = &[
+    SystemDataCategory {
+        label: "Caches", paths: &["~/Library/Caches", "/Library/Caches"],
+        targets: &["caches", "chromium_caches", "coreml_caches"], advice: "",
+    },
+    SystemDataCategory {
+        label: "Logs", paths: &["~/Library/Logs", "/Library/Logs", "/private/var/log"],
+        targets: &["logs"], advice: "",
+    },
+    SystemDataCategory {
+        label: "Unified logs & diagnostics", paths: &["/private/var/db/diagnostics", "/private/var/db/uuidtext"],
+        targets: &[], advice: "macOS rotates these itself; `sudo log erase --all` clears them",
+    },
+    SystemDataCategory {
+        label: "Swap & sleep image", paths: &["/private/var/vm"],
+        targets: &[], advice: "Shrinks after a restart",
+    },
+    SystemDataCategory {
+        label: "Developer tools", paths: &["~/Library/Developer"],
+        targets: &["xcode", "xcode_previews", "simulators", "instruments"], advice: "",
+    },
+    SystemDataCategory {
+        label: "App containers", paths: &["~/Library/Containers", "~/Library/Group Containers"],
+        targets: &[], advice: "App data; uninstalling an app with its leftovers removes its container",
+    },
+    SystemDataCategory {
+        label: "iOS device backups", paths: &["~/Library/Application Support/MobileSync/Backup"],
+        targets: &["ios_backups"], advice: "Delete old ones in Finder → your device → Manage Backups",
+    },
+    SystemDataCategory {
+        label: "Software updates & assets", paths: &["/Library/Updates", "/System/Library/AssetsV2"],
+        targets: &["speech_assets"], advice: "Installing or cancelling pending updates clears /Library/Updates",
+    },
+];
+
+/// Break "System Data" down into measured categories and what this tool can reclaim of each.
+pub fn run_system_data() {
+    println!("{}", "🗄️  System Data".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {}", "Measuring, this can take a minute...".dimmed());
+
+    // (label, size, reclaimable, targets, advice)
+    let mut rows: Vec<(&str, u64, u64, &[&str], &str)> = Vec::new();
+    let vm_disks = find_vm_disks();
+    for category in SYSTEM_DATA_CATEGORIES {
+        let paths: Vec<String> = category.paths.iter().map(|path| expand_home(path)).collect();
+        let mut size = get_paths_size(&paths);
+        // VM disks inside a container get their own row
+        size = size.saturating_sub(vm_disks.iter()
+            .filter(|(disk, _)| paths.iter().any(|path| disk.starts_with(path.as_str())))
+            .map(|(_, disk_size)| disk_size)
+            .sum());
+        let reclaimable: u64 = category.targets.iter()
+            .filter_map(|id| tasks::task(id))
+            .map(|task| task.estimate())
+            .sum();
+        rows.push((category.label, size, reclaimable.min(size), category.targets, category.advice));
+    }
+    let vm_size: u64 = vm_disks.iter().map(|(_, size)| size).sum();
+    let vm_targets: &[&str] = &["docker", "podman", "vagrant"];
+    let vm_reclaimable: u64 = vm_targets.iter().filter_map(|id| tasks::task(id)).map(|task| task.estimate()).sum();
+    rows.push(("Virtual machine disks", vm_size, vm_reclaimable.min(vm_size), vm_targets,
+        "Delete unused VMs from their app"));
+    rows.sort_by_key(|(_, size, ..)| std::cmp::Reverse(*size));
+
+    for (label, size, reclaimable, targets, advice) in rows.iter().filter(|(_, size, ..)| *size > 0) {
+        println!("  {:<28} {:>11}", label, format_size(*size, BINARY).red());
+        if *reclaimable > 0 {
+            println!("    {} {} reclaimable ({})", "✓".green(), format_size(*reclaimable, BINARY).green(), targets.join(", "));
+        }
+        if !advice.is_empty() {
+            println!("    {}", advice.dimmed());
+        }
+    }
+
+    let snapshots = local_snapshot_count();
+    if snapshots > 0 {
+        println!("  {:<28} {:>11}", "Local Time Machine snapshots", format!("{} snapshots", snapshots).yellow());
+        println!("    {}", "Hold deleted files until macOS thins them: tmutil thinlocalsnapshots / 999999999999 4".dimmed());
+    }
+
+    let measured: u64 = rows.iter().map(|(_, size, ..)| size).sum();
+    let reclaimable: u64 = rows.iter().map(|(_, _, reclaimable, ..)| reclaimable).sum();
+    let ids: Vec<&str> = rows.iter()
+        .filter(|(_, _, reclaimable, ..)| *reclaimable > 0)
+        .flat_map(|(_, _, _, targets, _)| targets.iter().copied())
+        .collect();
+    println!("\n  {} Measured {}; this tool can reclaim {} of it",
+        "📊".cyan(), format_size(measured, BINARY).bold(), format_size(reclaimable, BINARY).green().bold());
+    if !ids.is_empty() {
+        println!("  {} Preview it with: maccleanup-rust --dry-run --only {}", "💡".yellow(), ids.join(","));
+    }
+}
@@ -0,0 +1,137 @@
+//! `watch` and the run conditions (`--when-idle`, `--on-ac-power`, `--when-locked`) it shares with normal runs.
+
+use crate::*;
+
+/// Seconds since the last keyboard or mouse input, from the HID system's `HIDIdleTime`.
+pub fn hid_idle_seconds() -> Option<u64> {
+    let output = Command::new("ioreg").args(["-c", "IOHIDSystem", "-d", "4"]).output().ok()?;
+    String::from_utf8_lossy(&output.stdout).lines()
+        .find(|line| line.contains("\"HIDIdleTime\""))
+        .and_then(|line| line.rsplit('=').next())
+        .and_then(|nanos| nanos.trim().parse::<u64>().ok())
+        .map(|nanos| nanos / 1_000_000_000)
+}
+
+/// Whether the Mac is on AC power; desktops always are.
+pub fn on_ac_power() -> bool {
+    Command::new("pmset").args(["-g", "batt"]).output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("'AC Power'"))
+}
+
+/// Whether the console session's screen is locked.
+pub fn screen_locked() -> bool {
+    Command::new("ioreg").args(["-n", "Root", "-d1"]).output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("\"CGSSessionScreenIsLocked\"=Yes"))
+}
+
+/// Whether someone is viewing or controlling this Mac through Screen Sharing; `screensharingd`
+/// only runs while a session is connected.
+pub fn screen_sharing_active() -> bool {
+    Command::new("pgrep").args(["-x", "screensharingd"]).output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// When unattended work may start: `--when-idle`, `--on-ac-power` and `--when-locked`.
+/// A screen share always holds it back.
+#[derive(Debug, Clone, Default)]
+pub struct RunConditions {
+    pub idle_minutes: Option<u64>,
+    pub on_ac_power: bool,
+    pub when_locked: bool,
+}
+
+impl RunConditions {
+    /// Why heavy work should wait right now, or None when it may start.
+    pub fn unmet(&self) -> Option<String> {
+        if screen_sharing_active() {
+            return Some("a screen sharing session is connected".to_string());
+        }
+        if let Some(minutes) = self.idle_minutes {
+            let idle = hid_idle_seconds().unwrap_or(0);
+            if idle < minutes * 60 {
+                return Some(format!("idle for {}m, waiting for {}m", idle / 60, minutes));
+            }
+        }
+        if self.on_ac_power && !on_ac_power() {
+            return Some("running on battery".to_string());
+        }
+        if self.when_locked && !screen_locked() {
+            return Some("the screen isn't locked".to_string());
+        }
+        None
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut conditions = Vec::new();
+        if let Some(minutes) = self.idle_minutes {
+            conditions.push(format!("idle for {}m", minutes));
+        }
+        if self.on_ac_power {
+            conditions.push("on AC power".to_string());
+        }
+        if self.when_locked {
+            conditions.push("screen locked".to_string());
+        }
+        conditions.push("no screen share".to_string());
+        conditions
+    }
+}
+
+/// Poll free space and directory budgets until interrupted, alerting once per crossing.
+/// Budget checks walk whole directories, so they only run while `conditions` are met.
+pub fn run_watch(interval: u64, min_free: u64, budgets: &[(String, u64)], conditions: &RunConditions) {
+    println!("{}", "👀 Watching Free Space".bold());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {} Alert below {} free", "ℹ".blue(), format_size(min_free, BINARY).yellow());
+    for (path, budget) in budgets {
+        println!("  {} Alert when {} exceeds {}", "ℹ".blue(), path, format_size(*budget, BINARY).yellow());
+    }
+    if !budgets.is_empty() {
+        println!("  {} Checking budgets only when: {}", "ℹ".blue(), conditions.describe().join(", "));
+    }
+    println!("  {} Checking every {}s (Ctrl-C to stop)\n", "ℹ".blue(), interval);
+
+    // Alert on the way over a limit, not on every check while it stays over
+    let mut low_space = false;
+    let mut over_budget: Vec<String> = Vec::new();
+    loop {
+        let available = available_space(&home_dir());
+        let mut line = format!("{} free", format_size(available, BINARY));
+
+        if available < min_free && !low_space {
+            send_alert(&format!("Only {} free on your disk", format_size(available, BINARY)));
+        }
+        low_space = available < min_free;
+
+        let deferred = if budgets.is_empty() { None } else { conditions.unmet() };
+        if let Some(reason) = &deferred {
+            line.push_str(&format!(", budgets deferred ({})", reason));
+        }
+
+        for (path, budget) in budgets.iter().filter(|_| deferred.is_none()) {
+            let size = get_directory_size(path);
+            let name = Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path);
+            line.push_str(&format!(", {} {}/{}", name, format_size(size, BINARY), format_size(*budget, BINARY)));
+
+            let was_over = over_budget.contains(path);
+            if size > *budget && !was_over {
+                send_alert(&format!("{} is {}, over its {} budget", path, format_size(size, BINARY), format_size(*budget, BINARY)));
+                over_budget.push(path.clone());
+            } else if size <= *budget && was_over {
+                over_budget.retain(|over| over != path);
+            }
+        }
+
+        let warn = low_space || !over_budget.is_empty();
+        println!("  {} {}", Local::now().format("%H:%M").to_string().dimmed(), if warn { line.yellow() } else { line.normal() });
+        thread::sleep(Duration::from_secs(interval.max(1)));
+    }
+}
+
+/// Ring the terminal bell and post a Notification Center banner.
+pub fn send_alert(message: &str) {
+    println!("\x07  {} {}", "⚠".yellow(), message.bold());
+    let script = format!("display notification \"{}\" with title \"maccleanup-rust\"",
+        message.replace('\\', "\\\\").replace('"', "\\\""));
+    let _ = Command::new("osascript").args(["-e", &script]).output();
+}
@@ -0,0 +1,3755 @@
+//! The cleanup engine behind `maccleanup-rust`: targets, the cleanup context, manifests,
+//! config and the JSON-RPC server. Each target lives in `tasks` and implements `CleanupTask`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::io::{self, Write};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use colored::*;
+use clap::ValueEnum;
+use chrono::Local;
+use humansize::{format_size, BINARY, DECIMAL};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+pub mod commands;
+pub mod tasks;
+pub mod tui;
+
+pub use commands::*;
+pub use tasks::CleanupTask;
+
+/// How much cleaning a target can cost the user if they didn't mean it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Risk {
+    /// Recreated automatically: caches, logs, build products
+    Safe,
+    /// Re-downloadable or reinstallable, or history the user may miss
+    Moderate,
+    /// Can't be brought back: old documents, the Trash, logins, container volumes
+    Aggressive,
+}
+
+impl Risk {
+    pub fn label(self) -> ColoredString {
+        match self {
+            Risk::Safe => "[safe]".green(),
+            Risk::Moderate => "[moderate]".yellow(),
+            Risk::Aggressive => "[aggressive]".red(),
+        }
+    }
+}
+
+/// A cleanup target as the config, `--max-risk` and the setup wizard see it.
+pub struct TargetInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub risk: Risk,
+    /// What the user loses, for targets worth explaining before the first run
+    pub warning: Option<&'static str>,
+    /// App pack in `apps.toml` whose paths this target cleans
+    pub app: Option<&'static str>,
+    /// What else the target deletes or runs, in plain words, for `explain`
+    pub rules: &'static [&'static str],
+}
+
+pub const TARGETS: &[TargetInfo] = &[
+    TargetInfo {
+        id: "budgets", name: "Directory size budgets", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "Oldest items directly inside each directory listed under [budgets] in config.toml, until it fits its budget",
+            "With --force, other targets leave budgeted directories alone",
+        ],
+    },
+    TargetInfo {
+        id: "caches", name: "System & user caches", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Files in ~/Library/Caches and ~/.cache not modified for 1 day",
+            "Files in /Library/Caches and /System/Library/Caches not modified for 7 days",
+            "max_age_days.caches in config.toml replaces both ages",
+        ],
+    },
+    TargetInfo {
+        id: "logs", name: "System logs", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Files in ~/Library/Logs, ~/.npm/_logs, /Library/Logs and /var/log not modified for 7 days",
+            "max_age_days.logs in config.toml changes the age",
+        ],
+    },
+    TargetInfo {
+        id: "downloads", name: "Old downloads", risk: Risk::Aggressive, app: None,
+        warning: Some("Deletes anything in ~/Downloads untouched for 30 days, including installers and documents you meant to keep"),
+        rules: &[
+            "Files in ~/Downloads not modified for 30 days",
+            "max_age_days.downloads in config.toml changes the age",
+            "Moved to the Trash rather than deleted, unless --no-trash is given",
+        ],
+    },
+    TargetInfo {
+        id: "partial_downloads", name: "Interrupted downloads", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            ".download, .crdownload and .part files in ~/Downloads and ~/Desktop, any age",
+        ],
+    },
+    TargetInfo {
+        id: "extra_paths", name: "Extra paths", risk: Risk::Moderate, app: None,
+        warning: Some("Deletes old files in every directory listed under extra_paths in config.toml"),
+        rules: &[
+            "Files in each directory listed under extra_paths in config.toml not modified for 7 days",
+            "max_age_days.extra_paths in config.toml changes the age",
+            "The home folder itself is never accepted as an extra path",
+        ],
+    },
+    TargetInfo {
+        id: "unquarantine", name: "Quarantine flags on kept downloads", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "Removes the com.apple.quarantine attribute from files in ~/Downloads you select (--unquarantine)",
+        ],
+    },
+    TargetInfo {
+        id: "trash", name: "Trash", risk: Risk::Aggressive, app: None,
+        warning: Some("Empties the Trash on every volume; there is no undo"),
+        rules: &[
+            "Everything in ~/.Trash",
+            "/Volumes/*/.Trashes/<your uid>",
+            "/private/var/root/.Trash when running as root or with --sudo-estimates",
+        ],
+    },
+    TargetInfo {
+        id: "xcode", name: "Xcode derived data & archives", risk: Risk::Moderate, app: Some("xcode"),
+        warning: None,
+        rules: &[],
+    },
+    TargetInfo {
+        id: "xcode_previews", name: "Xcode previews & playgrounds", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Contents of ~/Library/Developer/Xcode/UserData/Previews (SwiftUI preview simulators)",
+            "Contents of ~/Library/Developer/XCPGDevices (playground simulators)",
+            "Swift Playgrounds' caches in its container",
+        ],
+    },
+    TargetInfo {
+        id: "device_support", name: "Xcode device support symbols", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Folders in ~/Library/Developer/Xcode/iOS DeviceSupport, and the watchOS, tvOS and visionOS ones, except the newest 2 OS versions of each",
+            "keep_device_support in config.toml changes how many versions are kept",
+        ],
+    },
+    TargetInfo {
+        id: "swift_packages", name: "CocoaPods, Carthage & SwiftPM caches", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Contents of ~/Library/Caches/CocoaPods, ~/Library/Caches/org.carthage.CarthageKit and ~/Library/Caches/org.swift.swiftpm (from the cocoapods, carthage and swiftpm packs in src/apps.toml)",
+            "Each cache is asked about separately in an interactive run",
+        ],
+    },
+    TargetInfo {
+        id: "instruments", name: "Instruments traces & device logs", risk: Risk::Moderate, app: None,
+        warning: Some("Deletes .trace recordings untouched for 30 days; export anything you still need"),
+        rules: &[
+            "Contents of ~/Library/Caches/com.apple.dt.Instruments",
+            "Files in ~/Library/Developer/Xcode/iOS Device Logs not modified for 30 days",
+            ".trace documents under the home folder (found with Spotlight) not modified for 30 days; picked by hand in an interactive run",
+            "max_age_days.instruments in config.toml changes the age",
+        ],
+    },
+    TargetInfo {
+        id: "provisioning_profiles", name: "Provisioning profiles", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Profiles in ~/Library/MobileDevice/Provisioning Profiles and ~/Library/Developer/Xcode/UserData/Provisioning Profiles that expired",
+            "Profiles whose signing certificates were all revoked, per `security find-identity`",
+            "Apple WWDR intermediate certificates installed more than once are only reported",
+        ],
+    },
+    TargetInfo {
+        id: "simulators", name: "Simulator content & settings", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "`xcrun simctl erase <udid>` for shut-down simulators; devices and runtimes stay installed",
+            "`xcrun simctl delete unavailable` for simulators whose runtime is gone",
+            "Folders in ~/Library/Developer/CoreSimulator/Devices that simctl no longer lists",
+            "`xcrun simctl delete <udid>` for simulators you pick, with those not booted for 90 days marked; never with --force",
+            "max_age_days.simulators in config.toml changes the age",
+        ],
+    },
+    TargetInfo {
+        id: "configurator", name: "Apple Configurator firmware", risk: Risk::Safe, app: Some("configurator"),
+        warning: None,
+        rules: &[],
+    },
+    TargetInfo {
+        id: "homebrew", name: "Homebrew cache", risk: Risk::Safe, app: Some("homebrew"),
+        warning: None,
+        rules: &[
+            "`brew cleanup -s`: old formula versions and the download cache",
+        ],
+    },
+    TargetInfo {
+        id: "node_modules", name: "node_modules", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "node_modules folders up to 3 levels below ~/Desktop, ~/Documents, ~/Developer and ~/Projects",
+            "Interactive runs list the projects, least recently modified first, and remove only the ones you pick",
+        ],
+    },
+    TargetInfo {
+        id: "js_caches", name: "npm, Yarn & pnpm caches", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Contents of ~/.npm/_cacache, ~/Library/Caches/Yarn, ~/.yarn/berry/cache and ~/Library/pnpm/store (from the npm, yarn and pnpm packs in src/apps.toml)",
+            "pnpm store files still hard-linked into a project's node_modules free no space until that project is removed",
+        ],
+    },
+    TargetInfo {
+        id: "cargo", name: "Cargo registry & git caches", risk: Risk::Safe, app: Some("cargo"),
+        warning: None,
+        rules: &[],
+    },
+    TargetInfo {
+        id: "cargo_targets", name: "Stale Cargo target directories", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "Only with --cargo-targets",
+            "target/ folders next to a Cargo.toml, up to 3 levels below ~/Desktop, ~/Documents, ~/Developer and ~/Projects",
+            "Only ones not built into for 30 days (newest entry inside); max_age_days.cargo_targets in config.toml changes it",
+            "Interactive runs list them with their last build date and remove only the ones you pick",
+        ],
+    },
+    TargetInfo {
+        id: "docker", name: "Docker", risk: Risk::Aggressive, app: None,
+        warning: Some("Prunes stopped containers, unused images and volumes; volumes often hold local databases"),
+        rules: &[
+            "`docker system prune -a -f --volumes`: stopped containers, unused images, networks and volumes",
+            "Optionally trims Docker.raw with docker/desktop-reclaim-space",
+        ],
+    },
+    TargetInfo {
+        id: "podman", name: "Podman", risk: Risk::Aggressive, app: None,
+        warning: Some("Prunes stopped containers, unused images and volumes, like Docker"),
+        rules: &[
+            "`podman system prune -a -f --volumes`",
+            "Cached machine images in ~/.local/share/containers/podman/machine",
+        ],
+    },
+    TargetInfo {
+        id: "vagrant", name: "Vagrant & VirtualBox", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "`vagrant box prune`: box versions older than the newest",
+            "Folders in ~/VirtualBox VMs that VirtualBox doesn't list, only when selected",
+        ],
+    },
+    TargetInfo {
+        id: "kubernetes", name: "Local Kubernetes", risk: Risk::Aggressive, app: None,
+        warning: Some("Deletes stopped kind/k3d clusters and everything deployed in them"),
+        rules: &[
+            "`kind delete cluster` / `k3d cluster delete` for stopped clusters",
+            "Superseded plugin versions in ~/.krew/store",
+        ],
+    },
+    TargetInfo {
+        id: "disk_images", name: "Disk images", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "`hdiutil compact` on .sparseimage/.sparsebundle files over 1GB in your home",
+            "Deletes only the images you select",
+        ],
+    },
+    TargetInfo {
+        id: "safari", name: "Safari cache & history", risk: Risk::Moderate, app: Some("safari"),
+        warning: Some("Clears browsing history and Top Sites along with the cache"),
+        rules: &[],
+    },
+    TargetInfo {
+        id: "chrome", name: "Chrome & Chromium browser caches", risk: Risk::Safe, app: Some("chrome"),
+        warning: None,
+        rules: &[
+            "Only the cache paths; history is cleared by `privacy`",
+            "The same for Brave, Microsoft Edge, Arc, Opera and Vivaldi (their packs in src/apps.toml), each confirmed on its own",
+        ],
+    },
+    TargetInfo {
+        id: "chromium_caches", name: "Electron & Chromium app caches", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Cache, Code Cache, GPUCache and Service Worker/CacheStorage folders next to a Chromium \"Local State\" or \"Preferences\" file, up to 3 levels below ~/Library/Application Support",
+            "The same folders of Slack, Discord, Teams, Notion, VS Code and other well-known Electron apps, marker file or not",
+            "Interactive runs list the apps with their cache sizes and clear only the ones you pick",
+        ],
+    },
+    TargetInfo {
+        id: "coreml_caches", name: "CoreML compiled model caches", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Compiled .mlmodelc models in ~/Library/Caches/com.apple.dt.Xcode",
+            "Compiled .mlmodelc models in sandboxed apps' caches (~/Library/Containers/*/Data/Library/Caches)",
+        ],
+    },
+    TargetInfo {
+        id: "sync_caches", name: "Dropbox, Google Drive & OneDrive caches", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Contents of ~/Dropbox/.dropbox.cache, Google Drive's per-account DriveFS content_cache and OneDrive's cache folders (from the dropbox, google_drive and onedrive packs in src/apps.toml)",
+            "A client that's running is skipped, since it may still be uploading from its cache",
+            "Synced folders themselves are never touched",
+        ],
+    },
+    TargetInfo {
+        id: "extension_leftovers", name: "Extension leftovers", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "Chrome profile storage (Local/Sync/Managed Extension Settings, IndexedDB) for extension ids no longer installed",
+            "Legacy Safari extension data",
+        ],
+    },
+    TargetInfo {
+        id: "python", name: "Python cache", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "__pycache__ folders and .pyc/.pyo files up to 4 levels below ~/Desktop, ~/Documents, ~/Developer and ~/Projects",
+        ],
+    },
+    TargetInfo {
+        id: "runtimes", name: "Runtime versions", risk: Risk::Moderate, app: None,
+        warning: Some("Removes pyenv/rbenv/nvm versions unused for 90 days; projects pinned to one must reinstall it"),
+        rules: &[
+            "pyenv, rbenv and nvm versions unused for 90 days, never the default",
+            "Any version you select",
+        ],
+    },
+    TargetInfo {
+        id: "mail_attachments", name: "Mail attachments", risk: Risk::Moderate, app: None,
+        warning: Some("Attachments are only available again once Mail can reach the server"),
+        rules: &[
+            "Per-account storage in ~/Library/Mail, with advice on Mail's Download Attachments setting",
+            "Attachments folders of messages older than 30 days, only in IMAP, iCloud, Gmail, Yahoo, AOL and Exchange accounts, whose server keeps the originals",
+            "max_age_days.mail_attachments in config.toml changes the age",
+            "Only accounts you pick interactively, and only while Mail isn't running; --force just reports",
+        ],
+    },
+    TargetInfo {
+        id: "ios_backups", name: "iPhone & iPad backups", risk: Risk::Aggressive, app: None,
+        warning: Some("A deleted backup is gone for good; a device can only be restored from one that's left"),
+        rules: &[
+            "Folders in ~/Library/Application Support/MobileSync/Backup, listed oldest first with the device name, model and date from Info.plist",
+            "Only backups you pick interactively; --force just reports",
+        ],
+    },
+    TargetInfo {
+        id: "cookies", name: "Cookies & web data", risk: Risk::Aggressive, app: None,
+        warning: Some("Signs you out of websites and drops their saved settings and offline data"),
+        rules: &[
+            "~/Library/Cookies, ~/Library/HTTPStorages",
+            "Safari LocalStorage and Databases",
+            "Chrome Default profile Cookies and Local Storage",
+            "Cache records only in ~/Library/WebKit/<app>",
+        ],
+    },
+    TargetInfo {
+        id: "recent_items", name: "Recent items", risk: Risk::Moderate, app: None,
+        warning: Some("Empties Open Recent menus and the Apple menu's Recent Items"),
+        rules: &[
+            "Recent documents, apps and servers lists in ~/Library/Application Support/com.apple.sharedfilelist",
+            "Each app's list in ApplicationRecentDocuments, except bundle ids in keep_recents in the config",
+            "Never the Finder sidebar favorites, volumes or tags stored alongside them",
+            "Restarts sharedfilelistd so the lists aren't written back",
+        ],
+    },
+    TargetInfo {
+        id: "printer_drivers", name: "Printer drivers", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "Vendor folders in /Library/Printers with no configured printer (sudo)",
+        ],
+    },
+    TargetInfo {
+        id: "install_leftovers", name: "Failed install & translocation leftovers", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "App Translocation folders in the per-user temp dir that are no longer mounted",
+            "Entries in ~/Library/Caches/com.apple.appstoreagent untouched for 7 days",
+            "Package sandboxes in /Library/InstallerSandboxes/.PKInstallSandboxManager older than a day (sudo); macOS update sandboxes are left alone",
+        ],
+    },
+    TargetInfo {
+        id: "sound_packs", name: "GarageBand & Logic sound packs", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "Sound library folders you select in /Library/Application Support/GarageBand, /Library/Application Support/Logic and /Library/Audio/Apple Loops/Apple (sudo)",
+        ],
+    },
+    TargetInfo {
+        id: "shell_history", name: "Shell history & terminal bloat", risk: Risk::Moderate, app: None,
+        warning: None,
+        rules: &[
+            "Shell histories over 5MB trimmed to their last 50,000 lines",
+            "~/.zsh_sessions and iTerm2 saved state older than 7 days",
+            "Superseded ~/.zcompdump* files",
+        ],
+    },
+    TargetInfo {
+        id: "ds_store", name: ".DS_Store files", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Every .DS_Store under the home folder (or --path), outside ~/Library, app bundles and hidden folders (--ds-store)",
+            "Finder recreates them without the folders' custom view settings",
+        ],
+    },
+    TargetInfo {
+        id: "speech_assets", name: "Speech & Siri assets (report only)", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Nothing is deleted: sizes dictation, Siri and voice assets managed by macOS",
+        ],
+    },
+    TargetInfo {
+        id: "media_libraries", name: "Photos & iMovie libraries (report only)", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "Nothing is deleted: lists libraries when there's more than one and flags likely stale duplicates",
+        ],
+    },
+    TargetInfo {
+        id: "font_caches", name: "Font caches (reset)", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "`atsutil databases -remove`, then restarts the font server; fonts themselves stay installed",
+            "Only when confirmed interactively; a logout afterwards is recommended",
+        ],
+    },
+    TargetInfo {
+        id: "ram", name: "Inactive RAM", risk: Risk::Safe, app: None,
+        warning: None,
+        rules: &[
+            "`sudo purge`: drops inactive memory; nothing on disk",
+        ],
+    },
+];
+
+pub fn target_info(id: &str) -> Option<&'static TargetInfo> {
+    TARGETS.iter().find(|target| target.id == id)
+}
+
+/// Names accepted by `--only` and `--skip` for several targets at once.
+pub const TARGET_GROUPS: &[(&str, &[&str])] = &[
+    ("containers", &["docker", "podman", "vagrant", "kubernetes"]),
+    ("browsers", &["safari", "chrome", "chromium_caches", "extension_leftovers"]),
+    ("maintenance", &["install_leftovers", "printer_drivers", "speech_assets", "font_caches"]),
+    ("developer", &["xcode", "xcode_previews", "device_support", "swift_packages", "instruments", "provisioning_profiles", "simulators", "configurator", "homebrew", "node_modules", "js_caches", "cargo", "cargo_targets", "python", "runtimes", "coreml_caches"]),
+];
+
+/// Expand `--only`/`--skip` values into target ids, rejecting names that are neither.
+pub fn resolve_target_ids(names: &[String]) -> Result<Vec<String>, String> {
+    let mut ids = Vec::new();
+    for name in names {
+        let name = name.trim().to_lowercase().replace('-', "_");
+        if let Some((_, members)) = TARGET_GROUPS.iter().find(|(group, _)| *group == name) {
+            ids.extend(members.iter().map(|id| id.to_string()));
+        } else if target_info(&name).is_some() {
+            ids.push(name);
+        } else {
+            let groups: Vec<&str> = TARGET_GROUPS.iter().map(|(group, _)| *group).collect();
+            return Err(format!("No target named {:?}; run `maccleanup-rust explain` to list them (groups: {})",
+                name, groups.join(", ")));
+        }
+    }
+    Ok(ids)
+}
+
+/// Counters for one target (or the whole run); atomic so cleaning threads can share one.
+#[derive(Debug, Default)]
+pub struct CleanupStats {
+    pub files_removed: AtomicUsize,
+    pub space_freed: AtomicU64,
+    pub errors: AtomicUsize,
+    pub skipped: AtomicUsize,
+    pub duration_ms: AtomicU64,
+}
+
+impl CleanupStats {
+    pub fn new() -> Self {
+        CleanupStats::default()
+    }
+
+    pub fn add(&self, other: &CleanupStats) {
+        self.files_removed.fetch_add(other.files_removed(), Ordering::Relaxed);
+        self.space_freed.fetch_add(other.space_freed(), Ordering::Relaxed);
+        self.errors.fetch_add(other.errors(), Ordering::Relaxed);
+        self.skipped.fetch_add(other.skipped(), Ordering::Relaxed);
+        self.duration_ms.fetch_add(other.duration_ms(), Ordering::Relaxed);
+    }
+
+    pub fn record_removed(&self, size: u64) {
+        self.files_removed.fetch_add(1, Ordering::Relaxed);
+        self.space_freed.fetch_add(size, Ordering::Relaxed);
+    }
+
+    /// Space reclaimed without removing files, e.g. by compaction or an external tool.
+    pub fn add_freed(&self, size: u64) {
+        self.space_freed.fetch_add(size, Ordering::Relaxed);
+    }
+
+    pub fn record_error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_skipped(&self) {
+        self.skipped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn files_removed(&self) -> usize {
+        self.files_removed.load(Ordering::Relaxed)
+    }
+
+    pub fn space_freed(&self) -> u64 {
+        self.space_freed.load(Ordering::Relaxed)
+    }
+
+    pub fn errors(&self) -> usize {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.skipped.load(Ordering::Relaxed)
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        self.duration_ms.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+pub struct DiskInfo {
+    pub total: u64,
+    pub available: u64,
+    pub used: u64,
+    pub percent_used: f32,
+    /// Space macOS frees on demand (caches, local snapshots, iCloud copies) that df counts as
+    /// used; only filled in by `get_disk_info_with_purgeable`
+    pub purgeable: u64,
+}
+
+impl DiskInfo {
+    /// What Finder calls available: free space plus what macOS would purge for it.
+    pub fn available_with_purgeable(&self) -> u64 {
+        self.available + self.purgeable
+    }
+
+    /// Space freed since `before`, counting purgeable space on both sides so macOS purging
+    /// (or refilling) its caches mid-run doesn't show up as space freed or lost.
+    pub fn freed_since(&self, before: &DiskInfo) -> u64 {
+        self.available_with_purgeable().saturating_sub(before.available_with_purgeable())
+    }
+}
+
+pub struct CleanupContext {
+    pub interactive: bool,
+    pub dry_run: bool,
+    pub force: bool,
+    pub verbose: bool,
+    pub manifest: RefCell<Manifest>,
+    pub max_delete: Option<u64>,
+    pub deleted_bytes: Cell<u64>,
+    pub left_by_cap: RefCell<Vec<(String, u64)>>,
+    pub permission_denied: RefCell<Vec<String>>,
+    pub syslog: bool,
+    pub run: RefCell<RunState>,
+    pub pending_action: RefCell<Option<String>>,
+    pub target_stats: CleanupStats,
+    pub scan_mark: Cell<Instant>,
+    /// Target ids the config limits a run to; all targets when empty
+    pub only: Vec<String>,
+    /// Target ids the config says never to offer
+    pub skip: Vec<String>,
+    /// Opt-in targets asked for with their flag, e.g. "shell_history"
+    pub opted_in: Vec<&'static str>,
+    /// Bundle ids whose Open Recent lists the recent items target leaves alone
+    pub keep_recents: Vec<String>,
+    /// OS versions per platform whose DeviceSupport symbols are kept
+    pub keep_device_support: usize,
+    pub max_risk: Risk,
+    /// Risk of the target being prompted for, shown next to the question
+    pub current_risk: Cell<Option<Risk>>,
+    /// Paths the running target deleted, with the size claimed for each
+    pub removed_paths: RefCell<Vec<(String, u64)>>,
+    /// Directory size budgets; unattended runs only trim these, never wipe them
+    pub budgets: Vec<(String, u64)>,
+    pub trimming: Cell<bool>,
+    /// Per-target ages from the config, see `Config::max_age`
+    pub max_age_days: BTreeMap<String, u64>,
+    /// Directories from the config's `extra_paths`, already expanded
+    pub extra_paths: Vec<String>,
+    /// Move files to the Trash instead of deleting them: always (`--to-trash`), never
+    /// (`--no-trash`), or `None` for only the targets in `TRASHED_BY_DEFAULT`
+    pub to_trash: Option<bool>,
+    /// Bytes the running target moved to the Trash
+    pub trashed_bytes: Cell<u64>,
+    /// Move deleted items into the run's quarantine folder so `restore` can put them back
+    pub recoverable: bool,
+    /// Bytes the running target moved into the quarantine folder
+    pub staged_bytes: Cell<u64>,
+    /// `backup_command` from the config, run before each target deletes anything
+    pub backup_command: Option<String>,
+    /// Set once the backup command failed; nothing else is deleted in this run
+    pub backup_failed: Cell<bool>,
+    /// Paths the backup command has been handed; anything under one of them is covered too
+    pub backed_up: RefCell<std::collections::HashSet<String>>,
+    /// A tool the running target needs that isn't installed, which puts it on cooldown
+    pub missing_tool: RefCell<Option<String>>,
+}
+
+/// Progress of one run, saved after every prompt so `resume` can pick up where it stopped.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunState {
+    pub id: String,
+    pub started: String,
+    pub args: Vec<String>,
+    pub answers: BTreeMap<String, bool>,
+    pub completed: Vec<String>,
+    pub finished: bool,
+}
+
+/// Snapshot of what each target found, written with `--manifest` and compared by `diff`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub created: String,
+    pub dry_run: bool,
+    pub targets: Vec<ManifestTarget>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestTarget {
+    pub id: String,
+    pub size: u64,
+    #[serde(default)]
+    pub needs_escalation: bool,
+    #[serde(default)]
+    pub items: Vec<ManifestItem>,
+    #[serde(default)]
+    pub result: Option<TargetResult>,
+    /// Time spent finding and sizing candidates, before any prompt
+    #[serde(default)]
+    pub scan_ms: u64,
+}
+
+/// What cleaning a target actually did, as opposed to its up-front estimate.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TargetResult {
+    pub removed: usize,
+    pub freed: u64,
+    pub errors: usize,
+    pub skipped: usize,
+    pub duration_ms: u64,
+    /// Bytes measured gone from the deleted paths (or from the volume, for tool-driven targets)
+    #[serde(default)]
+    pub verified: u64,
+    /// Growth in the volume's free space while the target ran
+    #[serde(default)]
+    pub disk_freed: u64,
+    /// Part of `freed` that was moved to the Trash, so it's only free once the Trash is emptied
+    #[serde(default)]
+    pub trashed: u64,
+    /// Part of `freed` kept in the quarantine folder by `--recoverable`
+    #[serde(default)]
+    pub staged: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestItem {
+    pub path: String,
+    pub size: u64,
+}
+
+impl CleanupContext {
+    /// Whether the config and --max-risk leave this target enabled.
+    pub fn wants(&self, id: &str) -> bool {
+        (self.only.is_empty() || self.only.iter().any(|only| only == id))
+            && !self.skip.iter().any(|skipped| skipped == id)
+            && target_info(id).map_or(Risk::Aggressive, |target| target.risk) <= self.max_risk
+    }
+
+    /// Days before files of target `id` count as old, or `default` when not configured.
+    pub fn max_age(&self, id: &str, default: u64) -> u64 {
+        self.max_age_days.get(id).copied().unwrap_or(default)
+    }
+
+    /// Make following prompts show the risk of target `id`.
+    pub fn begin_target(&self, id: &str) {
+        self.current_risk.set(target_info(id).map(|target| target.risk));
+    }
+
+    pub fn should_proceed(&self, action: &str, details: Option<String>) -> bool {
+        let answer = self.ask(action, details);
+        // Time spent at the prompt isn't scanning time for the next target
+        self.scan_mark.set(Instant::now());
+        answer
+    }
+
+    pub fn ask(&self, action: &str, details: Option<String>) -> bool {
+        // Dry run walks the same deletion logic; remove_path only simulates
+        let risk = self.current_risk.get().map(|risk| format!("{} ", risk.label())).unwrap_or_default();
+        if self.dry_run {
+            println!("  {} [DRY RUN] {}Would {}", "→".yellow(), risk, action);
+            if let Some(detail) = details {
+                println!("    {}", detail.dimmed());
+            }
+            return true;
+        }
+
+        // Reaching the next prompt means the previous approved action finished
+        self.complete_pending();
+
+        if let Some(answer) = self.previous_answer(action) {
+            return answer;
+        }
+
+        let answer = if self.force {
+            true
+        } else if self.interactive {
+            print!("  {} {}{} {} ", "?".cyan(), risk, action, "Proceed? (y/N):".yellow());
+            io::stdout().flush().unwrap();
+            
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).unwrap();
+            
+            input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes"
+        } else {
+            true
+        };
+
+        let mut run = self.run.borrow_mut();
+        run.answers.insert(action.to_string(), answer);
+        if answer {
+            *self.pending_action.borrow_mut() = Some(action.to_string());
+        }
+        save_run_state(&run);
+        answer
+    }
+
+    /// Answer recorded for `action` by the run being resumed, if any.
+    pub fn previous_answer(&self, action: &str) -> Option<bool> {
+        let run = self.run.borrow();
+        let answer = *run.answers.get(action)?;
+
+        if run.completed.iter().any(|done| done == action) {
+            println!("  {} Already done in run {}: {}", "✓".green(), run.id, action.dimmed());
+            Some(false)
+        } else if !answer {
+            println!("  {} Declined in run {}: {}", "ℹ".blue(), run.id, action.dimmed());
+            Some(false)
+        } else {
+            println!("  {} Approved in run {}, continuing: {}", "→".green(), run.id, action.dimmed());
+            *self.pending_action.borrow_mut() = Some(action.to_string());
+            Some(true)
+        }
+    }
+
+    pub fn complete_pending(&self) {
+        if let Some(action) = self.pending_action.borrow_mut().take() {
+            let mut run = self.run.borrow_mut();
+            run.completed.push(action);
+            save_run_state(&run);
+        }
+    }
+
+    pub fn finish_run(&self) {
+        if self.dry_run {
+            return;
+        }
+        self.complete_pending();
+        let mut run = self.run.borrow_mut();
+        run.finished = true;
+        save_run_state(&run);
+    }
+
+    pub fn record_target(&self, id: &str, size: u64) {
+        self.begin_target(id);
+        let restricted: Vec<String> = RESTRICTED_PATHS.lock().unwrap().drain(..).collect();
+        for path in &restricted {
+            if SUDO_ESTIMATES.load(Ordering::Relaxed) {
+                println!("  {} {} needs sudo to read (sized with sudo du)", "⚠".yellow(), path.dimmed());
+            } else {
+                println!("  {} {} needs sudo to read; estimate may be low (try --sudo-estimates)", "⚠".yellow(), path.dimmed());
+            }
+        }
+
+        self.manifest.borrow_mut().targets.push(ManifestTarget {
+            id: id.to_string(),
+            size,
+            needs_escalation: !restricted.is_empty(),
+            items: Vec::new(),
+            result: None,
+            scan_ms: self.scan_mark.get().elapsed().as_millis() as u64,
+        });
+        self.scan_mark.set(Instant::now());
+    }
+
+    /// Run one cleaner for the most recently recorded target, timing it and keeping its counters.
+    pub fn run_target(&self, clean: impl FnOnce() -> CleanupStats) -> CleanupStats {
+        if !self.dry_run && !self.run_backup() {
+            return CleanupStats::new();
+        }
+        self.target_stats.errors.store(0, Ordering::Relaxed);
+        self.target_stats.skipped.store(0, Ordering::Relaxed);
+        self.removed_paths.borrow_mut().clear();
+        self.trashed_bytes.set(0);
+        self.staged_bytes.set(0);
+        let available_before = available_space(&home_dir());
+        let started = Instant::now();
+
+        let stats = clean();
+        stats.errors.fetch_add(self.target_stats.errors(), Ordering::Relaxed);
+        stats.skipped.fetch_add(self.target_stats.skipped(), Ordering::Relaxed);
+        stats.duration_ms.fetch_add(started.elapsed().as_millis() as u64, Ordering::Relaxed);
+        if self.dry_run {
+            show_by_app(self);
+        }
+
+        // Re-measure instead of trusting the sizes taken before deleting
+        let disk_freed = available_space(&home_dir()).saturating_sub(available_before);
+        let removed = self.removed_paths.take();
+        let verified = if removed.is_empty() {
+            disk_freed
+        } else {
+            removed.iter()
+                .map(|(path, size)| {
+                    let remaining = if Path::new(path).exists() { get_paths_size(std::slice::from_ref(path)) } else { 0 };
+                    size.saturating_sub(remaining)
+                })
+                .sum()
+        };
+
+        // Some targets run more than one cleaner, so accumulate
+        if let Some(target) = self.manifest.borrow_mut().targets.last_mut() {
+            let result = target.result.get_or_insert_with(TargetResult::default);
+            result.removed += stats.files_removed();
+            result.freed += stats.space_freed();
+            result.errors += stats.errors();
+            result.skipped += stats.skipped();
+            result.duration_ms += stats.duration_ms();
+            if !self.dry_run {
+                result.verified += verified;
+                result.disk_freed += disk_freed;
+                result.trashed += self.trashed_bytes.get();
+                result.staged += self.staged_bytes.get();
+            }
+        }
+        self.scan_mark.set(Instant::now());
+        stats
+    }
+
+    /// Hand the running target's recorded candidates to `backup_command` up front, in one go.
+    /// Targets that only find their candidates while cleaning are covered by `backed_up_before_removal`.
+    /// Returns false once the command has failed, so the rest of the run deletes nothing.
+    pub fn run_backup(&self) -> bool {
+        if self.backup_command.is_none() {
+            return true;
+        }
+        if self.backup_failed.get() {
+            return false;
+        }
+
+        let paths = match self.manifest.borrow().targets.last() {
+            Some(target) => target.items.iter().map(|item| item.path.clone()).collect::<Vec<_>>(),
+            None => return true,
+        };
+        paths.is_empty() || self.hand_to_backup(&paths)
+    }
+
+    /// Check that `backup_command` has seen each of `paths`, or a folder holding it, handing it
+    /// the ones it hasn't. When that fails the paths are kept, and so is everything after them.
+    pub fn backed_up_before_removal(&self, paths: &[&str]) -> bool {
+        if self.backup_command.is_none() {
+            return true;
+        }
+
+        let missing: Vec<String> = {
+            let handed = self.backed_up.borrow();
+            paths.iter()
+                .filter(|path| !Path::new(path).ancestors().any(|dir| dir.to_str().is_some_and(|dir| handed.contains(dir))))
+                .map(|path| path.to_string())
+                .collect()
+        };
+        let covered = !self.backup_failed.get() && (missing.is_empty() || self.hand_to_backup(&missing));
+        if !covered {
+            for path in paths {
+                self.log_kept(path, "not handed to the backup command");
+                self.target_stats.record_skipped();
+            }
+        }
+        covered
+    }
+
+    /// Run `backup_command` with `paths` one per line on stdin and the run manifest so far at
+    /// `$MACCLEANUP_MANIFEST`, remembering them once it succeeds.
+    fn hand_to_backup(&self, paths: &[String]) -> bool {
+        let Some(command) = &self.backup_command else { return true };
+        let target = self.manifest.borrow().targets.last()
+            .map(|target| target.id.clone())
+            .unwrap_or_else(|| "general".to_string());
+        let dir = quarantine_dir(&self.run.borrow().id);
+        let manifest_path = dir.join(format!("backup-{}.json", target));
+        let backed_up = fs::create_dir_all(&dir)
+            .and_then(|()| write_manifest(&manifest_path, &self.manifest.borrow()))
+            .and_then(|()| {
+                println!("  {} Backing up {} candidates with the backup command...", "💾".cyan(), paths.len());
+                let mut child = Command::new("/bin/sh")
+                    .args(["-c", command])
+                    .env("MACCLEANUP_MANIFEST", &manifest_path)
+                    .env("MACCLEANUP_TARGET", &target)
+                    .stdin(std::process::Stdio::piped())
+                    .spawn()?;
+                let list: String = paths.iter().map(|path| format!("{}\n", path)).collect();
+                // A command that doesn't read the list closes stdin early; that's its choice
+                let _ = child.stdin.take().expect("stdin is piped").write_all(list.as_bytes());
+                let status = child.wait()?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(io::Error::other(format!("exited with {}", status)))
+                }
+            });
+
+        match backed_up {
+            Ok(()) => {
+                self.audit(&format!("backed up {} candidates of {}", paths.len(), target));
+                self.backed_up.borrow_mut().extend(paths.iter().cloned());
+                true
+            }
+            Err(err) => {
+                self.log_error(&format!("Backup command failed ({}); nothing more will be deleted in this run", err));
+                self.backup_failed.set(true);
+                false
+            }
+        }
+    }
+
+    /// Attach a candidate path to the most recently recorded target.
+    pub fn record_item(&self, path: &str, size: u64) {
+        if let Some(target) = self.manifest.borrow_mut().targets.last_mut() {
+            if !target.items.iter().any(|item| item.path == path) {
+                target.items.push(ManifestItem { path: path.to_string(), size });
+            }
+        }
+    }
+
+    pub fn delete_cap_reached(&self) -> bool {
+        self.max_delete.is_some_and(|cap| self.deleted_bytes.get() >= cap)
+    }
+
+    /// Check `size` against the --max-delete cap, remembering the path if it must be left alone.
+    pub fn within_delete_cap(&self, path: &str, size: u64) -> bool {
+        self.within_delete_cap_after(path, size, 0)
+    }
+
+    /// Like `within_delete_cap`, with `pending` bytes already admitted but not yet deleted.
+    pub fn within_delete_cap_after(&self, path: &str, size: u64, pending: u64) -> bool {
+        match self.max_delete {
+            Some(cap) if self.deleted_bytes.get() + pending + size > cap => {
+                self.log_kept(path, "over the --max-delete cap");
+                self.left_by_cap.borrow_mut().push((path.to_string(), size));
+                self.target_stats.record_skipped();
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// In unattended runs, budgeted directories are left to the budget trim instead of
+    /// being emptied by whichever target would otherwise reach them.
+    pub fn protected_by_budget(&self, path: &Path) -> bool {
+        let protected = self.force && !self.trimming.get()
+            && self.budgets.iter().any(|(dir, _)| path.starts_with(dir) || Path::new(dir).starts_with(path));
+        if protected {
+            self.log_kept(path.to_str().unwrap_or(""), "left for its size budget");
+            self.target_stats.record_skipped();
+        }
+        protected
+    }
+
+    pub fn remove_path(&self, path: &Path, size: u64) -> bool {
+        if self.protected_by_budget(path) {
+            return false;
+        }
+        if !self.within_delete_cap(path.to_str().unwrap_or(""), size) {
+            return false;
+        }
+        if self.dry_run {
+            self.simulate_removal(path.to_str().unwrap_or(""), size);
+            return true;
+        }
+        if !self.backed_up_before_removal(&[path.to_str().unwrap_or("")]) {
+            return false;
+        }
+        if self.moves_to_trash() && !is_in_trash(path) {
+            return self.trash_path(path, size);
+        }
+        if self.recoverable {
+            return self.stage_path(path, size);
+        }
+
+        let result = if path.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        };
+        self.finish_removal(path, size, result)
+    }
+
+    /// Remove sibling paths as `remove_path` would, returning which went. When they're deleted
+    /// for good the deletions run on `DELETE_THREADS` workers, trees with the most entries
+    /// first; free space is still measured once for the whole target, not per tree.
+    pub fn remove_paths(&self, paths: &[(String, u64)]) -> Vec<bool> {
+        // One backup command run for the whole batch rather than one per path
+        let all: Vec<&str> = paths.iter().map(|(path, _)| path.as_str()).collect();
+        if !self.dry_run && !self.backed_up_before_removal(&all) {
+            return vec![false; paths.len()];
+        }
+        if self.dry_run || self.recoverable || self.moves_to_trash() || paths.len() < 2 {
+            return paths.iter().map(|(path, size)| self.remove_path(Path::new(path), *size)).collect();
+        }
+
+        let mut removed = vec![false; paths.len()];
+        // Nothing is counted as deleted until the workers finish, so count what's admitted so far
+        let mut admitted_bytes = 0;
+        let admitted: Vec<usize> = (0..paths.len())
+            .filter(|&i| {
+                let (path, size) = &paths[i];
+                let admit = !self.protected_by_budget(Path::new(path)) && self.within_delete_cap_after(path, *size, admitted_bytes);
+                if admit {
+                    admitted_bytes += size;
+                }
+                admit
+            })
+            .collect();
+        let trees: Vec<PathBuf> = admitted.iter().map(|&i| PathBuf::from(&paths[i].0)).collect();
+        for (&i, result) in admitted.iter().zip(delete_trees_parallel(&trees, DELETE_THREADS)) {
+            removed[i] = self.finish_removal(Path::new(&paths[i].0), paths[i].1, result);
+        }
+        removed
+    }
+
+    /// Bookkeeping after deleting `path` for good, or recording why it couldn't be.
+    fn finish_removal(&self, path: &Path, size: u64, result: io::Result<()>) -> bool {
+        match result {
+            Ok(()) => {
+                self.deleted_bytes.set(self.deleted_bytes.get() + size);
+                self.removed_paths.borrow_mut().push((path.to_str().unwrap_or("").to_string(), size));
+                self.record_deletion(path.to_str().unwrap_or(""), size, None);
+                self.audit(&format!("deleted {} ({} bytes)", path.display(), size));
+                true
+            }
+            Err(err) => {
+                // EPERM/EACCES: worth one escalated retry at the end of the run
+                if err.kind() == io::ErrorKind::PermissionDenied {
+                    self.log_kept(path.to_str().unwrap_or(""), "permission denied, offered for a sudo retry at the end");
+                    self.permission_denied.borrow_mut().push(path.to_str().unwrap_or("").to_string());
+                } else {
+                    self.log_kept(path.to_str().unwrap_or(""), &err.to_string());
+                }
+                self.target_stats.record_error();
+                false
+            }
+        }
+    }
+
+    /// Whether the running target moves files to the Trash instead of deleting them.
+    pub fn moves_to_trash(&self) -> bool {
+        self.to_trash.unwrap_or_else(|| {
+            self.manifest.borrow().targets.last().is_some_and(|target| TRASHED_BY_DEFAULT.contains(&target.id.as_str()))
+        })
+    }
+
+    fn trash_path(&self, path: &Path, size: u64) -> bool {
+        match move_to_trash(path) {
+            Ok(destination) => {
+                self.deleted_bytes.set(self.deleted_bytes.get() + size);
+                self.trashed_bytes.set(self.trashed_bytes.get() + size);
+                self.removed_paths.borrow_mut().push((path.to_str().unwrap_or("").to_string(), size));
+                self.record_deletion(path.to_str().unwrap_or(""), size, Some(&destination));
+                self.audit(&format!("moved {} to {} ({} bytes)", path.display(), destination.display(), size));
+                true
+            }
+            Err(err) => {
+                if err.kind() == io::ErrorKind::PermissionDenied {
+                    self.permission_denied.borrow_mut().push(path.to_str().unwrap_or("").to_string());
+                }
+                self.log_error(&format!("Couldn't move {} to the Trash: {}", path.display(), err));
+                self.target_stats.record_error();
+                false
+            }
+        }
+    }
+
+    /// Move `path` somewhere it can be put back from before replacing it: the Trash when the
+    /// target moves files there, otherwise the run's quarantine folder whatever `--recoverable` says.
+    pub fn set_aside(&self, path: &Path, size: u64) -> bool {
+        if !self.backed_up_before_removal(&[path.to_str().unwrap_or("")]) {
+            false
+        } else if self.moves_to_trash() {
+            self.trash_path(path, size)
+        } else {
+            self.stage_path(path, size)
+        }
+    }
+
+    /// `--recoverable`: move the item under the run's quarantine folder at its original path.
+    fn stage_path(&self, path: &Path, size: u64) -> bool {
+        let staging = quarantine_dir(&self.run.borrow().id).join("files");
+        let relative = path.strip_prefix("/").unwrap_or(path);
+        let destination = match relative.parent() {
+            Some(parent) => unique_destination(&staging.join(parent), path.file_name().unwrap_or_default()),
+            None => staging.join(relative),
+        };
+        let moved = destination.parent().map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| move_path(path, &destination));
+        match moved {
+            Ok(()) => {
+                self.deleted_bytes.set(self.deleted_bytes.get() + size);
+                self.staged_bytes.set(self.staged_bytes.get() + size);
+                self.removed_paths.borrow_mut().push((path.to_str().unwrap_or("").to_string(), size));
+                self.record_deletion(path.to_str().unwrap_or(""), size, Some(&destination));
+                self.audit(&format!("staged {} in {} ({} bytes)", path.display(), destination.display(), size));
+                true
+            }
+            Err(err) => {
+                if err.kind() == io::ErrorKind::PermissionDenied {
+                    self.permission_denied.borrow_mut().push(path.to_str().unwrap_or("").to_string());
+                }
+                self.log_error(&format!("Couldn't stage {} for recovery: {}", path.display(), err));
+                self.target_stats.record_error();
+                false
+            }
+        }
+    }
+
+    /// Append a removed path to the run's deletion manifest, read by `restore`.
+    pub fn record_deletion(&self, path: &str, size: u64, moved_to: Option<&Path>) {
+        let record = DeletionRecord {
+            path: path.to_string(),
+            size,
+            target: self.manifest.borrow().targets.last().map(|target| target.id.clone()).unwrap_or_default(),
+            moved_to: moved_to.map(|dest| dest.to_str().unwrap_or("").to_string()),
+        };
+        let dir = quarantine_dir(&self.run.borrow().id);
+        let Ok(line) = serde_json::to_string(&record) else { return };
+        let appended = fs::create_dir_all(&dir).and_then(|()| {
+            fs::OpenOptions::new().create(true).append(true).open(dir.join("deletions.jsonl"))
+                .and_then(|mut file| writeln!(file, "{}", line))
+        });
+        if let Err(err) = appended {
+            self.log_error(&format!("Couldn't record {} in the deletion manifest: {}", path, err));
+        }
+    }
+
+    pub fn remove_path_with_sudo(&self, path: &str, size: u64) -> bool {
+        if self.protected_by_budget(Path::new(path)) {
+            return false;
+        }
+        if !self.within_delete_cap(path, size) {
+            return false;
+        }
+        if self.dry_run {
+            self.simulate_removal(path, size);
+            return true;
+        }
+        if !self.backed_up_before_removal(&[path]) {
+            return false;
+        }
+
+        let removed = remove_with_sudo(path);
+        if removed {
+            self.deleted_bytes.set(self.deleted_bytes.get() + size);
+            self.removed_paths.borrow_mut().push((path.to_string(), size));
+            self.record_deletion(path, size, None);
+            self.audit(&format!("deleted {} with sudo ({} bytes)", path, size));
+        } else {
+            self.target_stats.record_error();
+        }
+        removed
+    }
+
+    /// Dry-run stand-in for a deletion: list the candidate and count it against the cap.
+    pub fn simulate_removal(&self, path: &str, size: u64) {
+        let action = if self.moves_to_trash() && !is_in_trash(Path::new(path)) { "Would move to Trash" } else { "Would remove" };
+        println!("    {} {} {} ({})", "•".dimmed(), action, path.dimmed(), format_size(size, BINARY));
+        self.deleted_bytes.set(self.deleted_bytes.get() + size);
+        self.record_item(path, size);
+    }
+
+    /// With --syslog, send a line tagged with the current target to the system log.
+    pub fn audit(&self, message: &str) {
+        if !self.syslog {
+            return;
+        }
+
+        let target = self.manifest.borrow().targets.last()
+            .map(|target| target.id.clone())
+            .unwrap_or_else(|| "general".to_string());
+        if let Ok(line) = std::ffi::CString::new(format!("[{}] {}", target, message)) {
+            // SAFETY: both pointers are valid NUL-terminated strings; "%s" keeps the message literal
+            unsafe { libc::syslog(libc::LOG_NOTICE, c"%s".as_ptr(), line.as_ptr()) };
+        }
+    }
+
+    pub fn log_action(&self, message: &str) {
+        if self.verbose {
+            println!("  {} {}", "→".green(), message);
+        }
+    }
+
+    pub fn log_error(&self, message: &str) {
+        println!("  {} {}", "✗".red(), message);
+    }
+
+    /// Log that `tool` isn't installed, which puts the running target on cooldown.
+    pub fn log_missing_tool(&self, tool: &str) {
+        let message = format!("{} is not installed", tool);
+        self.log_error(&message);
+        self.missing_tool.borrow_mut().get_or_insert(message);
+    }
+
+    pub fn log_success(&self, message: &str) {
+        if self.dry_run {
+            println!("  {} {} {}", "✓".yellow(), "[DRY RUN]".yellow(), message);
+        } else {
+            println!("  {} {}", "✓".green(), message);
+        }
+    }
+
+    pub fn log_info(&self, message: &str) {
+        println!("  {} {}", "ℹ".blue(), message);
+    }
+
+    /// In verbose runs, say why a candidate survived, so a target that freed less than its
+    /// estimate can be traced to the files it left.
+    pub fn log_kept(&self, path: &str, reason: &str) {
+        if self.verbose {
+            println!("  {} Kept {}: {}", "↷".yellow(), path, reason.dimmed());
+        }
+    }
+}
+
+/// Library folders whose entries are named after the app that owns them.
+pub const APP_DATA_DIRS: &[&str] = &[
+    "Library/Containers", "Library/Group Containers", "Library/Caches", "Library/Application Support",
+    "Library/Logs", "Library/Saved Application State", "Library/HTTPStorages", "Library/WebKit",
+    "Library/Preferences", ".cache",
+];
+
+/// Folders holding several apps of one vendor, e.g. Application Support/Google/Chrome.
+pub const VENDOR_DIRS: &[&str] = &["Google", "Microsoft", "JetBrains", "Adobe", "Mozilla"];
+
+/// The app a path belongs to, from the Library folder it sits in: a bundle id such as
+/// "com.spotify.client" or a folder name such as "Slack".
+pub fn owning_app(path: &str) -> Option<String> {
+    let home = home_dir();
+    let path = Path::new(path);
+    let relative = path.strip_prefix(&home).or_else(|_| path.strip_prefix("/")).ok()?;
+    if relative.starts_with("Library/Developer") {
+        return Some("Xcode".to_string());
+    }
+    let rest = APP_DATA_DIRS.iter().find_map(|dir| relative.strip_prefix(dir).ok())?;
+    let mut components = rest.components().filter_map(|component| component.as_os_str().to_str());
+    let first = components.next()?;
+    if VENDOR_DIRS.contains(&first) {
+        if let Some(second) = components.next() {
+            return Some(format!("{} {}", first, second));
+        }
+    }
+
+    let name = first.trim_end_matches(".savedState").trim_end_matches(".plist");
+    // Group containers start with a team id or "group.": UBF8T346G9.com.microsoft.teams
+    let name = name.split_once('.')
+        .filter(|(team, _)| team.len() == 10 && team.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()))
+        .map_or(name, |(_, rest)| rest);
+    Some(name.trim_start_matches("group.").to_string())
+}
+
+/// Name of the installed app known by `key` (bundle id, bundle name or folder), else `key`.
+pub fn app_display_name(key: &str) -> String {
+    static APPS: OnceLock<Vec<(Vec<String>, String)>> = OnceLock::new();
+    APPS.get_or_init(tasks::chromium_caches::installed_app_names).iter()
+        .find(|(names, _)| names.iter().any(|name| name.eq_ignore_ascii_case(key)))
+        .map_or_else(|| key.to_string(), |(_, display)| display.clone())
+}
+
+/// Candidate sizes summed per owning app, largest first; paths no app owns count as "Other".
+pub fn group_by_app(items: &[ManifestItem]) -> Vec<(String, u64)> {
+    let mut by_app: BTreeMap<String, u64> = BTreeMap::new();
+    for item in items {
+        let app = owning_app(&item.path).map_or_else(|| "Other".to_string(), |key| app_display_name(&key));
+        *by_app.entry(app).or_default() += item.size;
+    }
+    let mut by_app: Vec<(String, u64)> = by_app.into_iter().collect();
+    by_app.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    by_app
+}
+
+/// Per-app subtotals of what the current target listed, when it spans several apps.
+pub fn show_by_app(ctx: &CleanupContext) {
+    let manifest = ctx.manifest.borrow();
+    let Some(target) = manifest.targets.last() else { return };
+    let by_app = group_by_app(&target.items);
+    if by_app.len() < 2 {
+        return;
+    }
+
+    println!("    {}", "By app:".bold());
+    for (app, size) in by_app.iter().take(10) {
+        println!("      {:<32} {:>11}", app, format_size(*size, BINARY).red());
+    }
+    if by_app.len() > 10 {
+        let rest: u64 = by_app[10..].iter().map(|(_, size)| size).sum();
+        println!("      {:<32} {:>11}", format!("... {} more", by_app.len() - 10).dimmed(), format_size(rest, BINARY));
+    }
+}
+
+/// Prefix of the last line of every run, for wrappers that capture the whole session.
+pub const RESULT_LINE_PREFIX: &str = "MACCLEANUP_RESULT";
+
+/// One uncolored `MACCLEANUP_RESULT {...}` line summing up the run, printed after the report
+/// so expect scripts and terminal recordings can pick the result out without parsing it.
+/// `status` is "completed", "cancelled" or "backup-failed"; `disk_freed` is the measured
+/// growth in free space, `None` for dry runs. Runs that never start use `print_skipped_result_line`.
+pub fn print_result_line(ctx: &CleanupContext, total_stats: &CleanupStats, status: &str, disk_freed: Option<u64>) {
+    let manifest = ctx.manifest.borrow();
+    let results: Vec<&TargetResult> = manifest.targets.iter().filter_map(|target| target.result.as_ref()).collect();
+    let mode = if ctx.dry_run { "dry-run" } else if ctx.force { "force" } else { "interactive" };
+    let line = serde_json::json!({
+        "status": status,
+        "run_id": ctx.run.borrow().id,
+        "mode": mode,
+        "removed": total_stats.files_removed(),
+        "freed": total_stats.space_freed(),
+        "disk_freed": disk_freed,
+        "trashed": results.iter().map(|result| result.trashed).sum::<u64>(),
+        "staged": results.iter().map(|result| result.staged).sum::<u64>(),
+        "errors": total_stats.errors(),
+        "skipped": total_stats.skipped(),
+        "targets": results.iter().filter(|result| result.removed > 0).count(),
+    });
+    println!("{} {}", RESULT_LINE_PREFIX, line);
+}
+
+/// The result line of a `--force` run held back by `--when-idle` and friends, with the same
+/// fields as a run that found nothing to do plus the `reason`.
+pub fn print_skipped_result_line(reason: &str) {
+    let line = serde_json::json!({
+        "status": "skipped",
+        "reason": reason,
+        "run_id": serde_json::Value::Null,
+        "mode": "force",
+        "removed": 0,
+        "freed": 0,
+        "disk_freed": 0,
+        "trashed": 0,
+        "staged": 0,
+        "errors": 0,
+        "skipped": 0,
+        "targets": 0,
+    });
+    println!("{} {}", RESULT_LINE_PREFIX, line);
+}
+
+/// Error/skip totals and a per-target breakdown of the targets that actually ran.
+pub fn show_target_results(ctx: &CleanupContext, total_stats: &CleanupStats) {
+    if total_stats.errors() > 0 {
+        println!("  {} {}", "Errors:".bold(), total_stats.errors().to_string().red());
+    }
+    if total_stats.skipped() > 0 {
+        println!("  {} {}", "Skipped:".bold(), total_stats.skipped().to_string().yellow());
+    }
+    println!("  {} {:.1}s", "Time spent cleaning:".bold(), total_stats.duration_ms() as f64 / 1000.0);
+
+    let manifest = ctx.manifest.borrow();
+    let results: Vec<(&String, &TargetResult)> = manifest.targets.iter()
+        .filter_map(|target| Some((&target.id, target.result.as_ref()?)))
+        .collect();
+    if results.is_empty() {
+        return;
+    }
+
+    let trashed: u64 = results.iter().map(|(_, result)| result.trashed).sum();
+    if trashed > 0 {
+        println!("  {} {} moved to the Trash; it's freed once the Trash is emptied",
+            "🗑".dimmed(), format_size(trashed, BINARY).yellow());
+    }
+    let staged: u64 = results.iter().map(|(_, result)| result.staged).sum();
+    if staged > 0 {
+        println!("  {} {} kept in {}; `maccleanup-rust restore --last` puts it back, `restore --purge` frees it",
+            "↩".dimmed(), format_size(staged, BINARY).yellow(), quarantine_dir(&ctx.run.borrow().id).display());
+    }
+
+    println!("\n  {}", "Per target:".bold());
+    let mut discrepancies = Vec::new();
+    for (id, result) in results {
+        let mut line = format!("{:<20} {:>6} items {:>11} {:>7.1}s",
+            id, result.removed, format_size(result.freed, BINARY), result.duration_ms as f64 / 1000.0);
+        if !ctx.dry_run {
+            line.push_str(&format!("  verified {:>11}", format_size(result.verified, BINARY)));
+        }
+        if result.errors > 0 {
+            line.push_str(&format!(", {} errors", result.errors));
+        }
+        if result.skipped > 0 {
+            line.push_str(&format!(", {} skipped", result.skipped));
+        }
+        if result.trashed > 0 {
+            line.push_str(&format!(", {} in the Trash", format_size(result.trashed, BINARY)));
+        }
+        if result.staged > 0 {
+            line.push_str(&format!(", {} staged", format_size(result.staged, BINARY)));
+        }
+
+        match verification_problem(result).filter(|_| !ctx.dry_run) {
+            Some(problem) => {
+                println!("    {} {}", "!".red(), line.red());
+                discrepancies.push((id, problem));
+            }
+            None => println!("    {} {}", "•".dimmed(), line),
+        }
+    }
+
+    for (id, problem) in discrepancies {
+        println!("  {} {}: {}", "⚠".yellow(), id, problem);
+    }
+}
+
+/// Below this, differences between reported and measured sizes are noise.
+pub const VERIFY_TOLERANCE: u64 = 10 * 1024 * 1024;
+
+/// Why a target's reported freed space doesn't match what was measured, if it doesn't.
+pub fn verification_problem(result: &TargetResult) -> Option<String> {
+    if result.freed.saturating_sub(result.verified) > VERIFY_TOLERANCE.max(result.freed / 10) {
+        return Some(format!("only {} of the reported {} is actually gone; some items were recreated or only partly deleted",
+            format_size(result.verified, BINARY), format_size(result.freed, BINARY)));
+    }
+    if result.verified.saturating_sub(result.disk_freed + result.trashed + result.staged) > VERIFY_TOLERANCE.max(result.verified / 2) {
+        return Some(format!("free space grew by only {}; local Time Machine snapshots may still hold the rest",
+            format_size(result.disk_freed, BINARY)));
+    }
+    None
+}
+
+/// List what --max-delete kept from being deleted.
+pub fn show_left_by_cap(ctx: &CleanupContext) {
+    let left_by_cap = ctx.left_by_cap.borrow();
+    let Some(cap) = ctx.max_delete.filter(|_| !left_by_cap.is_empty()) else { return };
+
+    let left_size: u64 = left_by_cap.iter().map(|(_, size)| size).sum();
+    println!("\n{}", format!("🛑 Stopped at the --max-delete cap of {}", format_size(cap, BINARY)).bold().yellow());
+    println!("  {} {} items ({}) {} left in place:",
+        "ℹ".blue(),
+        left_by_cap.len(),
+        format_size(left_size, BINARY).yellow(),
+        if ctx.dry_run { "would be" } else { "were" });
+    for (path, size) in left_by_cap.iter().take(10) {
+        println!("    {} {} ({})", "•".dimmed(), path.dimmed(), format_size(*size, BINARY));
+    }
+    if left_by_cap.len() > 10 {
+        println!("    {} ... and {} more", "•".dimmed(), left_by_cap.len() - 10);
+    }
+}
+
+/// Account whose home the per-user targets clean, set by `--user`.
+pub struct TargetUser {
+    pub name: String,
+    pub home: String,
+    pub uid: u32,
+}
+
+pub static TARGET_USER: OnceLock<TargetUser> = OnceLock::new();
+
+/// Home directory of the account being cleaned.
+pub fn home_dir() -> String {
+    match TARGET_USER.get() {
+        Some(user) => user.home.clone(),
+        None => env::var("HOME").unwrap_or_else(|_| String::from("/")),
+    }
+}
+
+/// Folders searched for code projects (node_modules, __pycache__, Cargo target dirs).
+/// Set by `--path`: the one folder project targets search instead of the usual roots.
+pub static SCOPE_PATH: OnceLock<String> = OnceLock::new();
+
+/// Targets that look for build artifacts and caches inside project folders; the ones a
+/// `--path` run offers.
+pub const PROJECT_TARGETS: &[&str] = &["node_modules", "cargo_targets", "python", "ds_store"];
+
+pub fn project_roots() -> Vec<String> {
+    if let Some(path) = SCOPE_PATH.get() {
+        return vec![path.clone()];
+    }
+    let home = home_dir();
+    ["Desktop", "Documents", "Developer", "Projects"].iter()
+        .map(|dir| format!("{}/{}", home, dir))
+        .collect()
+}
+
+pub fn target_uid() -> u32 {
+    match TARGET_USER.get() {
+        Some(user) => user.uid,
+        // SAFETY: getuid has no preconditions and cannot fail
+        None => unsafe { libc::getuid() },
+    }
+}
+
+pub fn lookup_user(name: &str) -> Option<TargetUser> {
+    let c_name = std::ffi::CString::new(name).ok()?;
+    // SAFETY: c_name is NUL-terminated; the returned record is copied out before any other passwd call
+    let entry = unsafe { libc::getpwnam(c_name.as_ptr()) };
+    if entry.is_null() {
+        return None;
+    }
+    // SAFETY: entry is non-null and pw_dir points to a NUL-terminated string owned by libc
+    let (home, uid) = unsafe { (std::ffi::CStr::from_ptr((*entry).pw_dir).to_string_lossy().into_owned(), (*entry).pw_uid) };
+
+    // Service accounts (uid < 500 on macOS) have homes like `/` or `/var/empty`
+    if uid < 500 || Path::new(&home) == Path::new("/") || !Path::new(&home).is_dir() {
+        return None;
+    }
+    Some(TargetUser { name: name.to_string(), home, uid })
+}
+
+/// Take the exclusive lock on `~/.maccleanup/run.lock`, recording our pid in it.
+/// The kernel drops the lock when the process exits, so a crashed run never leaves it stuck.
+pub fn acquire_run_lock(wait: bool) -> Result<fs::File, String> {
+    use std::os::unix::io::AsRawFd;
+
+    let path = state_dir().join("run.lock");
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+        .map_err(|err| format!("Failed to open {}: {}", path.display(), err))?;
+
+    // SAFETY: the descriptor belongs to `file`, which outlives the call
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+        let holder = fs::read_to_string(&path).unwrap_or_default().trim().to_string();
+        let holder = if holder.is_empty() { String::from("unknown pid") } else { format!("pid {}", holder) };
+        if !wait {
+            return Err(format!("Another maccleanup-rust instance is running ({}); pass --wait to run after it", holder));
+        }
+
+        println!("{} Waiting for another maccleanup-rust instance ({}) to finish...", "⏳".yellow(), holder);
+        // SAFETY: as above; without LOCK_NB this blocks until the other run exits
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(format!("Failed to lock {}: {}", path.display(), io::Error::last_os_error()));
+        }
+    }
+
+    let _ = file.set_len(0);
+    let _ = writeln!(file, "{}", std::process::id());
+    Ok(file)
+}
+
+/// Refuse to run when per-user paths would resolve to system directories.
+pub fn validate_environment(allow_root: bool) -> Result<(), String> {
+    let home = env::var("HOME").unwrap_or_default();
+    if home.trim().is_empty() {
+        return Err("HOME is not set; refusing to run because user cache paths would point at system directories".to_string());
+    }
+
+    if allow_root {
+        return Ok(());
+    }
+
+    if Path::new(&home) == Path::new("/") {
+        return Err("HOME is `/`; refusing to run (pass --allow-root if this is intended)".to_string());
+    }
+
+    // SAFETY: geteuid has no preconditions and cannot fail
+    if unsafe { libc::geteuid() } == 0 {
+        return Err("Running as root; per-user targets would clean root's home. Run as your user (sudo is requested when needed) or pass --allow-root".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn get_disk_info() -> DiskInfo {
+    // -h rather than -H: powers of 1024 ("460Gi"), the same units `parse_size_arg` reads
+    let output = Command::new("df")
+        .args(["-h", "/"])
+        .output()
+        .expect("Failed to get disk info");
+    
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = output_str.lines().collect();
+    
+    if lines.len() >= 2 {
+        let parts: Vec<&str> = lines[1].split_whitespace().collect();
+        if parts.len() >= 5 {
+            let total = parse_size_arg(parts[1]).unwrap_or(0);
+            let used = parse_size_arg(parts[2]).unwrap_or(0);
+            let available = parse_size_arg(parts[3]).unwrap_or(0);
+            let percent_str = parts[4].trim_end_matches('%');
+            let percent_used = percent_str.parse::<f32>().unwrap_or(0.0);
+            
+            return DiskInfo {
+                total,
+                available,
+                used,
+                percent_used,
+                purgeable: 0,
+            };
+        }
+    }
+    
+    DiskInfo {
+        total: 0,
+        available: 0,
+        used: 0,
+        percent_used: 0.0,
+        purgeable: 0,
+    }
+}
+
+/// Purgeable space on the volume holding `path`: the capacity the volume reports as
+/// available for important use, which macOS reaches by purging, less what statfs calls free.
+/// df doesn't report it, so it's read through Foundation with osascript.
+pub fn purgeable_space(path: &str) -> u64 {
+    let script = format!("ObjC.import('Foundation'); \
+        const value = Ref(); \
+        $.NSURL.fileURLWithPath({:?}).getResourceValueForKeyError(value, $.NSURLVolumeAvailableCapacityForImportantUsageKey, null); \
+        value[0].js", path);
+    let important = Command::new("osascript").args(["-l", "JavaScript", "-e", &script]).output().ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8_lossy(&output.stdout).trim().parse::<f64>().ok())
+        .unwrap_or(0.0) as u64;
+    important.saturating_sub(available_space(path))
+}
+
+/// `get_disk_info` plus purgeable space, for the before and after status. Takes an osascript
+/// call, so previews shown per target stick to `get_disk_info`.
+pub fn get_disk_info_with_purgeable() -> DiskInfo {
+    DiskInfo { purgeable: purgeable_space("/"), ..get_disk_info() }
+}
+
+/// Bytes available to this user on the volume holding `path`, to the byte unlike `df -H`.
+pub fn available_space(path: &str) -> u64 {
+    let Ok(c_path) = std::ffi::CString::new(path) else { return 0 };
+    // SAFETY: statvfs is plain data, and the call only writes into it
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a valid out-pointer
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return 0;
+    }
+    stat.f_bavail as u64 * stat.f_frsize as u64
+}
+
+/// Parse a human size like `20GB`, `500M`, `1.5GiB` or df's `460Gi`. Every size flag and
+/// config value goes through here; units are powers of 1024, as sizes are shown everywhere.
+pub fn parse_size_arg(value: &str) -> Result<u64, String> {
+    let upper = value.trim().to_uppercase();
+    let unit_start = upper.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(upper.len());
+    let (number, unit) = upper.split_at(unit_start);
+
+    if number.is_empty() {
+        return Err(format!("'{}' has no number (try 500MB or 20GB)", value));
+    }
+    let number: f64 = number.parse()
+        .map_err(|_| format!("invalid number in '{}' (try 500MB or 1.5GB)", value))?;
+    let multiplier: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "K" | "KB" | "KI" | "KIB" => 1024,
+        "M" | "MB" | "MI" | "MIB" => 1_048_576,
+        "G" | "GB" | "GI" | "GIB" => 1_073_741_824,
+        "T" | "TB" | "TI" | "TIB" => 1_099_511_627_776,
+        _ => return Err(format!("unknown size unit '{}' in '{}' (use B, KB, MB, GB or TB)", unit.trim(), value)),
+    };
+
+    let bytes = number * multiplier as f64;
+    if bytes >= u64::MAX as f64 {
+        return Err(format!("size '{}' is too large", value));
+    }
+    Ok(bytes as u64)
+}
+
+/// `PATH=SIZE`, with `~/` expanded against the home being cleaned.
+pub fn parse_budget_arg(value: &str) -> Result<(String, u64), String> {
+    let (path, size) = value.rsplit_once('=')
+        .ok_or_else(|| format!("invalid budget '{}' (try ~/Library/Developer/Xcode/DerivedData=20GB)", value))?;
+    Ok((expand_home(path.trim()), parse_size_arg(size)?))
+}
+
+pub fn show_disk_status(disk: &DiskInfo, title: &str) {
+    println!("{}", title.bold().cyan());
+    
+    let used_bar_length = (disk.percent_used / 100.0 * 30.0) as usize;
+    let free_bar_length = 30 - used_bar_length;
+    
+    let bar = format!("{}{}",
+        "█".repeat(used_bar_length).red(),
+        "░".repeat(free_bar_length).dimmed()
+    );
+    
+    println!("  {} [{}] {:.1}%", 
+        "Disk Usage:".bold(),
+        bar,
+        disk.percent_used
+    );
+    
+    println!("  {} {} / {} ({})", 
+        "Space:".bold(),
+        format_size(disk.used, BINARY).red(),
+        format_size(disk.total, BINARY),
+        format!("{} free", format_size(disk.available, BINARY)).green()
+    );
+
+    if disk.purgeable > 0 {
+        println!("  {} {} {}",
+            "Purgeable:".bold(),
+            format_size(disk.purgeable, BINARY).yellow(),
+            format!("(freed by macOS on demand; {} available counting it)", format_size(disk.available_with_purgeable(), BINARY)).dimmed()
+        );
+    }
+}
+
+pub fn show_space_preview(size: u64) {
+    if size > 0 {
+        let disk = get_disk_info();
+        let new_available = disk.available + size;
+        let new_percent_used = if disk.used > size {
+            ((disk.used - size) as f32 / disk.total as f32) * 100.0
+        } else {
+            0.0
+        };
+        
+        println!("  {} {} → {} ({:.1}% → {:.1}%)",
+            "Preview:".dimmed(),
+            format_size(disk.available, BINARY).dimmed(),
+            format_size(new_available, BINARY).green(),
+            disk.percent_used,
+            new_percent_used
+        );
+    }
+}
+
+pub fn show_ram_status() {
+    let output = Command::new("vm_stat")
+        .output()
+        .expect("Failed to get RAM info");
+    
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let mut free_pages = 0u64;
+    let mut inactive_pages = 0u64;
+    let mut active_pages = 0u64;
+    let mut wired_pages = 0u64;
+    let mut compressed_pages = 0u64;
+    
+    for line in output_str.lines() {
+        if line.contains("Pages free:") {
+            free_pages = extract_number_from_line(line);
+        } else if line.contains("Pages inactive:") {
+            inactive_pages = extract_number_from_line(line);
+        } else if line.contains("Pages active:") {
+            active_pages = extract_number_from_line(line);
+        } else if line.contains("Pages wired down:") {
+            wired_pages = extract_number_from_line(line);
+        } else if line.contains("Pages occupied by compressor:") {
+            compressed_pages = extract_number_from_line(line);
+        }
+    }
+    
+    let page_size = 4096u64; // 4KB per page on macOS
+    let free_mb = (free_pages * page_size) / 1_048_576;
+    let inactive_mb = (inactive_pages * page_size) / 1_048_576;
+    let active_mb = (active_pages * page_size) / 1_048_576;
+    let wired_mb = (wired_pages * page_size) / 1_048_576;
+    let compressed_mb = (compressed_pages * page_size) / 1_048_576;
+    
+    let total_ram = get_total_ram();
+    let used_mb = active_mb + wired_mb + compressed_mb;
+    let available_mb = free_mb + inactive_mb;
+    
+    println!("  {} {} / {} MB", 
+        "RAM Usage:".bold(),
+        format!("{} MB", used_mb).red(),
+        total_ram
+    );
+    
+    println!("  {} {} MB ({} MB inactive can be freed)",
+        "Available:".bold(),
+        format!("{}", available_mb).green(),
+        inactive_mb
+    );
+}
+
+pub fn get_total_ram() -> u64 {
+    let output = Command::new("sysctl")
+        .args(["hw.memsize"])
+        .output()
+        .expect("Failed to get total RAM");
+    
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let parts: Vec<&str> = output_str.trim().split(": ").collect();
+    
+    if parts.len() == 2 {
+        let bytes = parts[1].parse::<u64>().unwrap_or(0);
+        return bytes / 1_048_576; // Convert to MB
+    }
+    
+    8192 // Default to 8GB if can't determine
+}
+
+pub fn extract_number_from_line(line: &str) -> u64 {
+    line.split_whitespace()
+        .last()
+        .and_then(|s| s.trim_end_matches('.').parse().ok())
+        .unwrap_or(0)
+}
+
+/// Targets counted in the "total potential cleanup" shown before the first prompt.
+pub const POTENTIAL_TARGETS: &[&str] = &[
+    "caches", "logs", "downloads", "partial_downloads", "extra_paths", "trash", "xcode", "xcode_previews", "device_support", "swift_packages", "instruments",
+    "provisioning_profiles", "homebrew", "js_caches", "cargo", "docker", "podman", "safari", "python", "extension_leftovers", "chrome", "sync_caches", "printer_drivers",
+    "install_leftovers",
+];
+
+/// With `quick`, targets are sized by `quick_estimate` and the total is approximate.
+pub fn calculate_total_cleanup_size(ctx: &CleanupContext, quick: bool) -> u64 {
+    let wanted: Vec<&str> = POTENTIAL_TARGETS.iter().copied().filter(|id| ctx.wants(id)).collect();
+    let cache = if quick { read_scan_cache() } else { None };
+    let total: u64 = wanted.iter()
+        .filter_map(|id| tasks::task(id))
+        .map(|task| if quick { quick_estimate(task, cache.as_ref()) } else { task.estimate() })
+        .sum();
+    // Estimates claimed their folders above; take out what two targets both counted
+    let counted_twice: u64 = wanted.iter().map(|id| claimed_by_others(id, &wanted)).sum();
+    total.saturating_sub(counted_twice)
+}
+
+/// A folder a target's estimate counted, and its size.
+pub struct Claim {
+    pub target: &'static str,
+    pub path: String,
+    pub size: u64,
+}
+
+/// Folders counted by each target's estimate, so bytes in a folder two targets cover
+/// (Chrome's cache inside ~/Library/Caches) are attributed to one of them only.
+pub static CLAIMED_PATHS: Mutex<Vec<Claim>> = Mutex::new(Vec::new());
+
+/// Record that `target`'s estimate counted `size` bytes at `path`, replacing an earlier claim.
+pub fn claim_path(target: &'static str, path: &str, size: u64) {
+    let mut claims = CLAIMED_PATHS.lock().unwrap();
+    claims.retain(|claim| claim.target != target || claim.path != path);
+    claims.push(Claim { target, path: path.to_string(), size });
+}
+
+/// Size each of `paths`, claim it for `target` and return the total.
+pub fn claim_paths_size(target: &'static str, paths: &[String]) -> u64 {
+    paths.iter()
+        .map(|path| {
+            let size = get_paths_size(std::slice::from_ref(path));
+            claim_path(target, path, size);
+            size
+        })
+        .sum()
+}
+
+/// Bytes in `target`'s estimate that sit in a narrower folder claimed by one of `others`.
+/// The narrower target keeps them, so the broad one (caches) reports this much less.
+pub fn claimed_by_others(target: &str, others: &[&str]) -> u64 {
+    let claims = CLAIMED_PATHS.lock().unwrap();
+    let nested = |inner: &Claim, outer: &Claim| inner.path != outer.path && Path::new(&inner.path).starts_with(&outer.path);
+    let own: Vec<&Claim> = claims.iter().filter(|claim| claim.target == target).collect();
+    let foreign: Vec<&Claim> = claims.iter()
+        .filter(|claim| claim.target != target && others.contains(&claim.target))
+        .collect();
+    foreign.iter()
+        .filter(|claim| own.iter().any(|outer| nested(claim, outer)))
+        // A claim inside another foreign claim is already part of that one's size
+        .filter(|claim| !foreign.iter().any(|outer| nested(claim, outer)))
+        .map(|claim| claim.size)
+        .sum()
+}
+
+/// Per-type RECLAIMABLE column of `docker|podman system df`, e.g. ("Images", 1.5GB).
+pub fn system_df_reclaimable(tool: &str) -> Vec<(String, u64)> {
+    let mut reclaimable = Vec::new();
+
+    if let Ok(output) = Command::new(tool).args(["system", "df"]).output() {
+        if output.status.success() {
+            let output_str = String::from_utf8_lossy(&output.stdout);
+            for line in output_str.lines().skip(1) {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                // "Local Volumes" spans two words, so the type ends at the first number
+                let kind: Vec<&str> = parts.iter()
+                    .take_while(|part| !part.starts_with(|c: char| c.is_ascii_digit()))
+                    .copied()
+                    .collect();
+                if let Some(size) = parts.iter().rev().find(|part| !part.starts_with('(')) {
+                    reclaimable.push((kind.join(" "), parse_size_arg(size).unwrap_or(0)));
+                }
+            }
+        }
+    }
+
+    reclaimable
+}
+
+/// What a target's folder needs before this process can delete in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Privacy-protected (TCC); the terminal must be granted Full Disk Access
+    FullDiskAccess,
+    /// Owned by another user, usually root
+    Sudo,
+}
+
+impl Access {
+    pub fn badge(&self) -> &'static str {
+        match self {
+            Access::FullDiskAccess => "needs Full Disk Access",
+            Access::Sudo => "needs sudo",
+        }
+    }
+
+    fn from_error(error: &io::Error) -> Option<Access> {
+        match error.raw_os_error() {
+            // TCC refuses with EPERM where plain file permissions give EACCES
+            Some(libc::EPERM) => Some(Access::FullDiskAccess),
+            Some(libc::EACCES) => Some(Access::Sudo),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `path` can be listed and written to; `None` when it can, or doesn't exist.
+pub fn probe_access(path: &str) -> Option<Access> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = expand_home(path);
+    if !Path::new(&path).exists() {
+        return None;
+    }
+    if let Err(error) = fs::read_dir(&path) {
+        return Access::from_error(&error);
+    }
+    let c_path = std::ffi::CString::new(Path::new(&path).as_os_str().as_bytes()).ok()?;
+    // SAFETY: `c_path` is a valid NUL-terminated string for the duration of the call
+    if unsafe { libc::access(c_path.as_ptr(), libc::W_OK) } != 0 {
+        return Access::from_error(&io::Error::last_os_error());
+    }
+    None
+}
+
+/// Targets whose folder fails `probe_access`, as (name, what it needs).
+pub fn probe_targets(tasks: &[&'static dyn CleanupTask]) -> Vec<(&'static str, Access)> {
+    tasks.iter()
+        .filter_map(|task| Some((task.name(), probe_access(task.probe_path()?)?)))
+        .collect()
+}
+
+/// Permission problems found before the run, with how to fix each kind.
+pub fn show_access_problems(problems: &[(&str, Access)]) {
+    if problems.is_empty() {
+        return;
+    }
+    println!("\n{}", "🔐 Permissions to fix before cleaning:".bold());
+    for (name, access) in problems {
+        println!("  • {} {}", name, format!("[{}]", access.badge()).yellow());
+    }
+    show_access_fixes(problems);
+}
+
+/// How to grant each kind of access in `problems`, one line per kind.
+fn show_access_fixes(problems: &[(&str, Access)]) {
+    if problems.iter().any(|(_, access)| *access == Access::FullDiskAccess) {
+        println!("  {}", "Full Disk Access: System Settings → Privacy & Security → Full Disk Access, add your terminal".dimmed());
+    }
+    if problems.iter().any(|(_, access)| *access == Access::Sudo) {
+        println!("  {}", "sudo: run with sudo, or answer yes when offered a sudo retry at the end".dimmed());
+    }
+}
+
+/// List the targets this run offers, each with what it still needs to be cleaned fully.
+pub fn show_menu(history: &[Manifest], offered: &[&'static dyn CleanupTask], access_problems: &[(&str, Access)]) -> bool {
+    println!("\n{}", "This tool will clean the following:".bold());
+    for task in offered {
+        match access_problems.iter().find(|(name, _)| *name == task.name()) {
+            Some((_, access)) => println!("  • {} {}", task.name(), format!("[{}]", access.badge()).yellow()),
+            None => println!("  • {}", task.name()),
+        }
+    }
+    show_access_fixes(access_problems);
+
+    let efficiency = target_efficiency(history);
+    if !efficiency.is_empty() {
+        println!("\n{}", "⚡ Best value in past runs (space reclaimed per second of scanning and cleaning):".bold());
+        for (id, bytes_per_sec) in efficiency.iter().take(5) {
+            let rating = if *bytes_per_sec >= 1e9 { "★★★" } else if *bytes_per_sec >= 1e8 { "★★☆" } else { "★☆☆" };
+            println!("  {} {:<20} {:>12}/s", rating.yellow(), id, format_size(*bytes_per_sec as u64, BINARY));
+        }
+    }
+    
+    print!("\n{} {} ", "?".cyan(), "Continue with cleanup? (y/N):".yellow().bold());
+    io::stdout().flush().unwrap();
+    
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    
+    input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes"
+}
+
+pub fn clean_directory(path: &str, days_old: Option<u64>, ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let mut candidates: Vec<(String, u64)> = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            
+            // Skip important system files
+            let name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+            if name == ".DS_Store" || name.starts_with(".") {
+                ctx.log_kept(path.to_str().unwrap_or(""), "hidden files are never removed");
+                continue;
+            }
+
+            if is_ignored(&path) {
+                ctx.log_kept(path.to_str().unwrap_or(""), "matches ~/.maccleanupignore");
+                continue;
+            }
+            
+            // Check age if days_old is specified
+            if let Some(days) = days_old {
+                if let Ok(metadata) = entry.metadata() {
+                    if let Ok(modified) = metadata.modified() {
+                        if let Ok(elapsed) = modified.elapsed() {
+                            let days_elapsed = elapsed.as_secs() / 86400;
+                            if days_elapsed < days {
+                                ctx.log_kept(path.to_str().unwrap_or(""),
+                                    &format!("too new, modified {}d ago (age limit {}d)", days_elapsed, days));
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+            
+            // Get size before deletion
+            let size = if path.is_dir() {
+                get_directory_size(path.to_str().unwrap_or(""))
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            };
+            
+            ctx.record_item(path.to_str().unwrap_or(""), size);
+            candidates.push((path.to_str().unwrap_or("").to_string(), size));
+        }
+    }
+
+    // Try to remove (or simulate in dry run)
+    for ((path, size), removed) in candidates.iter().zip(ctx.remove_paths(&candidates)) {
+        if removed {
+            stats.record_removed(*size);
+            if ctx.verbose && !ctx.dry_run {
+                println!("    {} Removed: {}", "✓".green(), path);
+            }
+        }
+    }
+
+    stats
+}
+
+/// Package extensions that Finder shows as a single file. Scanners size these but never
+/// descend into them; a target that needs bundle contents matches its extension first.
+pub const BUNDLE_EXTENSIONS: &[&str] = &[
+    "app", "appex", "framework", "bundle", "plugin", "kext", "xpc", "qlgenerator", "saver", "prefPane",
+    "photoslibrary", "imovielibrary", "musiclibrary", "tvlibrary", "fcpbundle", "logicx", "band",
+    "pkg", "mpkg", "xcarchive", "xcodeproj", "xcworkspace", "playground", "rtfd", "pages", "numbers", "key",
+];
+
+pub fn is_bundle(path: &Path) -> bool {
+    extension_is(path, BUNDLE_EXTENSIONS) && path.is_dir()
+}
+
+/// Whether the volume holding `path` compares names case-insensitively (the APFS default).
+/// Probed once per device by looking the path up again with its name's case flipped.
+pub fn volume_is_case_insensitive(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    static BY_DEVICE: OnceLock<Mutex<HashMap<u64, bool>>> = OnceLock::new();
+
+    let Ok(metadata) = fs::symlink_metadata(path) else { return true };
+    let cache = BY_DEVICE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(&insensitive) = cache.lock().unwrap().get(&metadata.dev()) {
+        return insensitive;
+    }
+
+    // Probe with the nearest ancestor that has letters in its name
+    let mut probe = Some(path);
+    let insensitive = loop {
+        let Some(current) = probe else { break true };
+        let name = current.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        let flipped: String = name.chars()
+            .map(|c| if c.is_lowercase() { c.to_ascii_uppercase() } else { c.to_ascii_lowercase() })
+            .collect();
+        if flipped != name {
+            let original = fs::symlink_metadata(current).ok();
+            let other = fs::symlink_metadata(current.with_file_name(&flipped)).ok();
+            break match (original, other) {
+                (Some(original), Some(other)) => original.dev() == other.dev() && original.ino() == other.ino(),
+                _ => false,
+            };
+        }
+        probe = current.parent();
+    };
+
+    cache.lock().unwrap().insert(metadata.dev(), insensitive);
+    insensitive
+}
+
+/// Whether `name` is `expected`, or only differs in case and `case_insensitive` says the
+/// volume doesn't tell them apart. The volume is only probed for such near misses.
+pub fn same_name(name: &str, expected: &str, case_insensitive: impl FnOnce() -> bool) -> bool {
+    name == expected || (name.eq_ignore_ascii_case(expected) && case_insensitive())
+}
+
+/// Compare `path`'s file name with `expected` the way its volume would.
+pub fn name_is(path: &Path, expected: &str) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else { return false };
+    same_name(name, expected, || volume_is_case_insensitive(path))
+}
+
+pub fn extension_is(path: &Path, expected: &[&str]) -> bool {
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else { return false };
+    expected.iter().any(|candidate| same_name(extension, candidate, || volume_is_case_insensitive(path)))
+}
+
+pub fn get_directory_size(path: &str) -> u64 {
+    walk_directory_size(path, &mut Vec::new())
+}
+
+/// Like `get_directory_size`, but collects directories we weren't allowed to read.
+pub fn walk_directory_size(path: &str, denied: &mut Vec<String>) -> u64 {
+    walk_size(path, denied, 0)
+}
+
+fn walk_size(path: &str, denied: &mut Vec<String>, depth: usize) -> u64 {
+    let mut size = 0;
+    let shallow = SHALLOW_SCAN.load(Ordering::Relaxed);
+    
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    if !shallow || depth + 1 < SHALLOW_SCAN_DEPTH {
+                        size += walk_size(path.to_str().unwrap_or(""), denied, depth + 1);
+                    }
+                } else {
+                    size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                }
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => denied.push(path.to_string()),
+        Err(_) => {}
+    }
+    
+    size
+}
+
+pub static SUDO_ESTIMATES: AtomicBool = AtomicBool::new(false);
+
+/// Set while `--quick` estimates run: size walks stop `SHALLOW_SCAN_DEPTH` levels below
+/// the folder being sized, so the figures come fast but low.
+pub static SHALLOW_SCAN: AtomicBool = AtomicBool::new(false);
+pub const SHALLOW_SCAN_DEPTH: usize = 2;
+
+/// A last scan younger than this stands in for a target's `--quick` estimate.
+pub const QUICK_CACHE_MAX_AGE_HOURS: i64 = 24;
+
+/// `--quick` size of a target: the last scan's figure when it's recent, otherwise its
+/// estimate with shallow walks. Approximate either way.
+pub fn quick_estimate(task: &dyn CleanupTask, cache: Option<&ScanCache>) -> u64 {
+    let recent = cache.filter(|cache| chrono::DateTime::parse_from_rfc3339(&cache.created)
+        .is_ok_and(|created| (Local::now().fixed_offset() - created).num_hours() < QUICK_CACHE_MAX_AGE_HOURS));
+    if let Some(size) = recent.and_then(|cache| cache.targets.get(task.id())) {
+        return *size;
+    }
+    SHALLOW_SCAN.store(true, Ordering::Relaxed);
+    let size = task.estimate();
+    SHALLOW_SCAN.store(false, Ordering::Relaxed);
+    size
+}
+
+pub static RESTRICTED_PATHS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Size a target root, noting it as restricted if parts were unreadable.
+/// With --sudo-estimates, restricted roots are re-measured with `sudo du -sk`.
+pub fn estimate_path_size(path: &str) -> u64 {
+    let mut denied = Vec::new();
+    let size = walk_directory_size(path, &mut denied);
+    if denied.is_empty() {
+        return size;
+    }
+
+    RESTRICTED_PATHS.lock().unwrap().push(path.to_string());
+    if !SUDO_ESTIMATES.load(Ordering::Relaxed) {
+        return size;
+    }
+
+    let du_size = Command::new("sudo")
+        .args(["du", "-sk", path])
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .next()
+                .and_then(|kb| kb.parse::<u64>().ok())
+        })
+        .map(|kb| kb * 1024)
+        .unwrap_or(0);
+
+    size.max(du_size)
+}
+
+/// Entries `clean_directory` would remove from `path`, with their sizes, for listing them
+/// before anything is deleted.
+pub fn directory_candidates(path: &str, days_old: Option<u64>) -> Vec<(String, u64)> {
+    fs::read_dir(path).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| !entry.file_name().to_str().unwrap_or("").starts_with('.') && !is_ignored(&entry.path()))
+        .filter(|entry| days_old.is_none_or(|days| entry.metadata().and_then(|m| m.modified()).ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_none_or(|elapsed| elapsed.as_secs() / 86400 >= days)))
+        .map(|entry| {
+            let path = entry.path().to_str().unwrap_or("").to_string();
+            let size = get_paths_size(std::slice::from_ref(&path));
+            (path, size)
+        })
+        .collect()
+}
+
+pub fn get_old_files_size(path: &str, days: u64) -> u64 {
+    let mut size = 0;
+    
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            if let Ok(metadata) = entry.metadata() {
+                if let Ok(modified) = metadata.modified() {
+                    if let Ok(elapsed) = modified.elapsed() {
+                        let days_elapsed = elapsed.as_secs() / 86400;
+                        if days_elapsed >= days {
+                            if entry.path().is_dir() {
+                                size += get_directory_size(entry.path().to_str().unwrap_or(""));
+                            } else {
+                                size += metadata.len();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    size
+}
+
+/// Where a run keeps copies of everything it changes in place.
+pub fn quarantine_dir(run_id: &str) -> PathBuf {
+    state_dir().join("quarantine").join(run_id)
+}
+
+/// Run `sql` on the SQLite database `db` in place. Every target that edits a database goes
+/// through here: the database and its -wal/-shm files are copied into the quarantine area
+/// first, and if sqlite3 fails or `PRAGMA integrity_check` doesn't answer "ok" afterwards,
+/// the copies are put back.
+pub fn edit_database(ctx: &CleanupContext, db: &str, sql: &str) -> bool {
+    if ctx.dry_run {
+        println!("    {} Would run {:?} on {} (after backing it up)", "→".yellow(), sql, db);
+        return true;
+    }
+
+    let backup_dir = quarantine_dir(&ctx.run.borrow().id).join(db.trim_start_matches('/').replace('/', "_"));
+    let files: Vec<(PathBuf, PathBuf)> = ["", "-wal", "-shm"].iter()
+        .map(|suffix| format!("{}{}", db, suffix))
+        .filter(|file| Path::new(file).exists())
+        .map(|file| {
+            let name = Path::new(&file).file_name().unwrap_or_default().to_owned();
+            (PathBuf::from(&file), backup_dir.join(name))
+        })
+        .collect();
+    let backed_up = fs::create_dir_all(&backup_dir)
+        .and_then(|()| files.iter().try_for_each(|(file, backup)| fs::copy(file, backup).map(|_| ())));
+    if let Err(err) = backed_up {
+        ctx.log_error(&format!("Left {} alone: couldn't back it up: {}", db, err));
+        return false;
+    }
+
+    let sqlite = |sql: &str| Command::new("sqlite3").arg(db).arg(sql).output();
+    let edited = sqlite(sql).is_ok_and(|output| output.status.success());
+    let intact = edited && sqlite("PRAGMA integrity_check;")
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "ok");
+    if intact {
+        ctx.audit(&format!("ran {:?} on {} (backup in {})", sql, db, backup_dir.display()));
+        return true;
+    }
+
+    // Journal files the edit created would be replayed into the restored copy
+    for suffix in ["-wal", "-shm"] {
+        let file = PathBuf::from(format!("{}{}", db, suffix));
+        if !files.iter().any(|(original, _)| *original == file) {
+            let _ = fs::remove_file(file);
+        }
+    }
+    let restored = files.iter().all(|(file, backup)| fs::copy(backup, file).is_ok());
+    let problem = if edited { "failed its integrity check" } else { "failed" };
+    if restored {
+        ctx.log_error(&format!("Editing {} {}; restored it from the backup", db, problem));
+    } else {
+        ctx.log_error(&format!("Editing {} failed and it couldn't be restored; the backup is in {}", db, backup_dir.display()));
+    }
+    ctx.target_stats.record_error();
+    false
+}
+
+/// One removed path in a run's `deletions.jsonl`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeletionRecord {
+    pub path: String,
+    pub size: u64,
+    pub target: String,
+    /// Where it went if it was moved to the Trash or staged rather than deleted
+    #[serde(default)]
+    pub moved_to: Option<String>,
+}
+
+pub fn read_deletions(run_id: &str) -> Vec<DeletionRecord> {
+    fs::read_to_string(quarantine_dir(run_id).join("deletions.jsonl")).unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+pub fn retry_with_sudo(ctx: &CleanupContext, paths: &[String]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for path in paths {
+        let size = get_paths_size(std::slice::from_ref(path));
+        ctx.log_action(&format!("Removing {} with sudo", path));
+        if ctx.remove_path_with_sudo(path, size) {
+            stats.record_removed(size);
+        } else {
+            ctx.log_error(&format!("Still unable to remove {}", path));
+        }
+    }
+
+    ctx.log_success(&format!("Removed {} items with sudo, freed {}",
+        stats.files_removed(),
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Targets whose files go to the Trash unless `--no-trash` is given.
+pub const TRASHED_BY_DEFAULT: &[&str] = &["downloads"];
+
+pub fn is_in_trash(path: &Path) -> bool {
+    path.components().any(|component| component.as_os_str() == ".Trash" || component.as_os_str() == ".Trashes")
+}
+
+/// The Trash `path` can be renamed into: the home one, or the user's `.Trashes/<uid>` on the
+/// external volume holding it, as Finder does.
+pub fn trash_dir_for(path: &Path) -> PathBuf {
+    use std::os::unix::fs::MetadataExt;
+    let home = home_dir();
+    let device = |path: &Path| fs::metadata(path).map(|metadata| metadata.dev()).ok();
+    if let Some(volume) = path.strip_prefix("/Volumes").ok().and_then(|rest| rest.components().next()) {
+        let volume = Path::new("/Volumes").join(volume);
+        if device(&volume) != device(Path::new(&home)) {
+            return volume.join(".Trashes").join(target_uid().to_string());
+        }
+    }
+    PathBuf::from(home).join(".Trash")
+}
+
+/// `name`, or `name 2`, `name 3`, ... before the extension, whichever is free in `dir`.
+pub fn unique_destination(dir: &Path, name: &std::ffi::OsStr) -> PathBuf {
+    let candidate = dir.join(name);
+    if fs::symlink_metadata(&candidate).is_err() {
+        return candidate;
+    }
+    let name = Path::new(name);
+    let stem = name.file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+    let extension = name.extension().and_then(|ext| ext.to_str()).map(|ext| format!(".{}", ext)).unwrap_or_default();
+    (2..).map(|n| dir.join(format!("{} {}{}", stem, n, extension)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| dir.join(name))
+}
+
+/// Move a file or folder to the Trash, returning where it went.
+pub fn move_to_trash(path: &Path) -> io::Result<PathBuf> {
+    use std::os::unix::fs::DirBuilderExt;
+    let trash = trash_dir_for(path);
+    if !trash.exists() {
+        fs::DirBuilder::new().recursive(true).mode(0o700).create(&trash)?;
+        // Cleaning another account as root must leave its Trash usable by that account
+        if TARGET_USER.get().is_some() {
+            let uid = target_uid();
+            std::os::unix::fs::chown(&trash, Some(uid), None)?;
+        }
+    }
+
+    let name = path.file_name().ok_or_else(|| io::Error::other("nothing to move"))?;
+    let destination = unique_destination(&trash, name);
+    move_path(path, &destination)?;
+    Ok(destination)
+}
+
+/// Rename, or copy and delete the original when `destination` is on another volume.
+pub fn move_path(source: &Path, destination: &Path) -> io::Result<()> {
+    match fs::rename(source, destination) {
+        Ok(()) => Ok(()),
+        Err(err) if err.raw_os_error() == Some(libc::EXDEV) => {
+            if let Err(err) = copy_recursively(source, destination) {
+                // Don't leave half a copy behind, e.g. when the other volume fills up
+                if let Ok(metadata) = fs::symlink_metadata(destination) {
+                    let _ = if metadata.is_dir() { fs::remove_dir_all(destination) } else { fs::remove_file(destination) };
+                }
+                return Err(err);
+            }
+            if source.is_dir() {
+                fs::remove_dir_all(source)
+            } else {
+                fs::remove_file(source)
+            }
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Copy a file, folder or symlink, keeping symlinks as links.
+pub fn copy_recursively(source: &Path, destination: &Path) -> io::Result<()> {
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(io::Error::other)?;
+        let target = destination.join(entry.path().strip_prefix(source).map_err(io::Error::other)?);
+        if entry.path_is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+        } else if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn remove_with_sudo(path: &str) -> bool {
+    Command::new("sudo")
+        .args(["rm", "-rf", path])
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Ask the user to pick entries from a numbered list.
+/// Accepts "1,3,5", ranges like "2-4", or "all"; returns zero-based indices.
+pub fn select_items(prompt: &str, count: usize) -> Vec<usize> {
+    print!("  {} {} {} ", "?".cyan(), prompt, "(e.g. 1,3-5 or 'all', Enter to skip):".yellow());
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+    let input = input.trim().to_lowercase();
+
+    if input == "all" {
+        return (0..count).collect();
+    }
+
+    let mut selected = Vec::new();
+    for part in input.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim().parse::<usize>(), end.trim().parse::<usize>()),
+            None => (part.parse::<usize>(), part.parse::<usize>()),
+        };
+
+        if let (Ok(start), Ok(end)) = (start, end) {
+            for n in start..=end {
+                if n >= 1 && n <= count && !selected.contains(&(n - 1)) {
+                    selected.push(n - 1);
+                }
+            }
+        }
+    }
+
+    selected
+}
+
+/// Remove an explicit list of candidates, reporting them as `label` in the summary.
+pub fn clean_paths(ctx: &CleanupContext, paths: &[String], label: &str) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    let sized: Vec<(String, u64)> = paths.iter()
+        .map(|path| {
+            ctx.log_action(&format!("Removing {}", path));
+            (path.clone(), get_paths_size(std::slice::from_ref(path)))
+        })
+        .collect();
+    for ((_, size), removed) in sized.iter().zip(ctx.remove_paths(&sized)) {
+        if removed {
+            stats.record_removed(*size);
+        }
+    }
+
+    ctx.log_success(&format!("Removed {} {}, freed {}",
+        stats.files_removed(),
+        label,
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub fn get_paths_size(paths: &[String]) -> u64 {
+    paths.iter()
+        .map(|path| {
+            if Path::new(path).is_dir() {
+                get_directory_size(path)
+            } else {
+                fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+            }
+        })
+        .sum()
+}
+
+#[derive(Debug, Default)]
+pub struct ScanTotals {
+    pub files: u64,
+    pub dirs: u64,
+    pub bytes: u64,
+    pub errors: u64,
+}
+
+/// Walk `roots` with a shared work queue of directories drained by `threads` workers.
+pub fn scan_parallel(roots: &[String], threads: usize) -> ScanTotals {
+    // (pending directories, directories currently being read)
+    let queue = Mutex::new((roots.iter().map(PathBuf::from).collect::<Vec<_>>(), 0usize));
+    let wakeup = Condvar::new();
+    let files = AtomicU64::new(0);
+    let dirs = AtomicU64::new(0);
+    let bytes = AtomicU64::new(0);
+    let errors = AtomicU64::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let next = {
+                    let mut state = queue.lock().unwrap();
+                    loop {
+                        if let Some(dir) = state.0.pop() {
+                            state.1 += 1;
+                            break Some(dir);
+                        }
+                        if state.1 == 0 {
+                            break None;
+                        }
+                        state = wakeup.wait(state).unwrap();
+                    }
+                };
+
+                let Some(dir) = next else {
+                    wakeup.notify_all();
+                    return;
+                };
+
+                let mut subdirs = Vec::new();
+                match fs::read_dir(&dir) {
+                    Ok(entries) => {
+                        for entry in entries.flatten() {
+                            match entry.file_type() {
+                                Ok(file_type) if file_type.is_dir() => {
+                                    dirs.fetch_add(1, Ordering::Relaxed);
+                                    subdirs.push(entry.path());
+                                }
+                                Ok(file_type) if file_type.is_file() => {
+                                    files.fetch_add(1, Ordering::Relaxed);
+                                    bytes.fetch_add(entry.metadata().map(|m| m.len()).unwrap_or(0), Ordering::Relaxed);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    Err(_) => {
+                        errors.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+
+                let mut state = queue.lock().unwrap();
+                state.0.extend(subdirs);
+                state.1 -= 1;
+                wakeup.notify_all();
+            });
+        }
+    });
+
+    ScanTotals {
+        files: files.into_inner(),
+        dirs: dirs.into_inner(),
+        bytes: bytes.into_inner(),
+        errors: errors.into_inner(),
+    }
+}
+
+/// Workers deleting sibling trees in `CleanupContext::remove_paths`.
+pub const DELETE_THREADS: usize = 4;
+
+/// Files and folders under `path`, counting `path` itself.
+pub fn count_entries(path: &Path) -> u64 {
+    if !path.is_dir() {
+        return 1;
+    }
+    let totals = scan_parallel(&[path.to_str().unwrap_or("").to_string()], 1);
+    totals.files + totals.dirs + 1
+}
+
+/// Delete `trees` on `threads` workers, one tree per worker at a time. Deleting is bound by
+/// the entries to unlink rather than bytes, so the trees with the most entries start first
+/// and a huge node_modules isn't left to finish alone after the small ones.
+pub fn delete_trees_parallel(trees: &[PathBuf], threads: usize) -> Vec<io::Result<()>> {
+    let mut order: Vec<(u64, usize)> = trees.iter().enumerate().map(|(i, tree)| (count_entries(tree), i)).collect();
+    // Popped from the end, so the biggest goes last in the queue
+    order.sort();
+    let queue = Mutex::new(order);
+    let results: Mutex<Vec<Option<io::Result<()>>>> = Mutex::new(trees.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, trees.len().max(1)) {
+            scope.spawn(|| loop {
+                let Some((_, i)) = queue.lock().unwrap().pop() else { return };
+                let tree = &trees[i];
+                let result = if tree.is_dir() { fs::remove_dir_all(tree) } else { fs::remove_file(tree) };
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results.into_inner().unwrap().into_iter()
+        .map(|result| result.unwrap_or_else(|| Err(io::Error::other("not deleted"))))
+        .collect()
+}
+
+/// Patterns from `~/.maccleanupignore`, loaded once per run.
+pub fn ignore_rules() -> &'static Gitignore {
+    static RULES: OnceLock<Gitignore> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let home = home_dir();
+        let mut builder = GitignoreBuilder::new(&home);
+        let ignore_file = format!("{}/.maccleanupignore", home);
+
+        if Path::new(&ignore_file).exists() {
+            if let Some(err) = builder.add(&ignore_file) {
+                println!("  {} Problem reading {}: {}", "⚠".yellow(), ignore_file, err);
+            }
+        }
+
+        builder.build().unwrap_or_else(|_| Gitignore::empty())
+    })
+}
+
+pub fn is_ignored(path: &Path) -> bool {
+    let rules = ignore_rules();
+    if rules.is_empty() {
+        return false;
+    }
+
+    let is_dir = path.is_dir();
+    // Anchored patterns are relative to HOME; bare names like `*.psd` match anywhere
+    if path.starts_with(rules.path()) {
+        rules.matched_path_or_any_parents(path, is_dir).is_ignore()
+    } else {
+        rules.matched(path, is_dir).is_ignore()
+    }
+}
+
+pub fn write_manifest(path: &Path, manifest: &Manifest) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(manifest)?;
+    fs::write(path, json)
+}
+
+pub fn read_manifest(path: &Path) -> io::Result<Manifest> {
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Runs kept in `history.jsonl` for timing and digests.
+pub const HISTORY_MAX_RUNS: usize = 200;
+
+pub fn history_path() -> PathBuf {
+    state_dir().join("history.jsonl")
+}
+
+/// Append this run's manifest as one line, dropping the oldest runs past the limit.
+pub fn append_history(manifest: &Manifest) {
+    let Ok(line) = serde_json::to_string(manifest) else { return };
+    let existing = fs::read_to_string(history_path()).unwrap_or_default();
+    let mut lines: Vec<&str> = existing.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines.push(&line);
+    let start = lines.len().saturating_sub(HISTORY_MAX_RUNS);
+    let _ = fs::write(history_path(), lines[start..].join("\n") + "\n");
+}
+
+pub fn read_history() -> Vec<Manifest> {
+    fs::read_to_string(history_path()).unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Days a failed target is skipped when `failure_cooldown_days` isn't set in the config.
+pub const FAILURE_COOLDOWN_DAYS: u64 = 7;
+
+/// When a target last failed in a run that deleted, and why.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cooldown {
+    pub failed: String,
+    pub reason: String,
+}
+
+pub fn cooldowns_path() -> PathBuf {
+    state_dir().join("cooldowns.json")
+}
+
+pub fn read_cooldowns() -> BTreeMap<String, Cooldown> {
+    fs::read_to_string(cooldowns_path()).ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Targets that failed within the last `days` days, with the days left and the reason.
+pub fn cooling_down(days: u64) -> Vec<(String, u64, String)> {
+    read_cooldowns().into_iter()
+        .filter_map(|(id, cooldown)| {
+            let failed = chrono::DateTime::parse_from_rfc3339(&cooldown.failed).ok()?;
+            let since = (Local::now().fixed_offset() - failed).num_days().max(0) as u64;
+            (since < days).then(|| (id, days - since, cooldown.reason))
+        })
+        .collect()
+}
+
+/// Why target `id` failed in this run: a tool it needs is missing, or it hit errors and deleted
+/// nothing. A single item it couldn't handle isn't a failure. Clears the missing tool for the next target.
+pub fn target_failure(ctx: &CleanupContext, id: &str) -> Option<String> {
+    let missing = ctx.missing_tool.borrow_mut().take();
+    missing.or_else(|| {
+        let manifest = ctx.manifest.borrow();
+        let result = manifest.targets.iter().rev().find(|target| target.id == id)?.result.as_ref()?;
+        (result.errors > 0 && result.removed == 0)
+            .then(|| format!("none of {} items could be deleted", result.errors))
+    })
+}
+
+/// Put target `id` on cooldown after it failed, or take it off after it ran cleanly.
+pub fn record_target_outcome(id: &str, failure: Option<String>) {
+    let mut cooldowns = read_cooldowns();
+    let changed = match failure {
+        Some(reason) => {
+            cooldowns.insert(id.to_string(), Cooldown { failed: Local::now().to_rfc3339(), reason });
+            true
+        }
+        None => cooldowns.remove(id).is_some(),
+    };
+    if changed {
+        if let Ok(json) = serde_json::to_string_pretty(&cooldowns) {
+            let _ = fs::write(cooldowns_path(), json);
+        }
+    }
+}
+
+/// Runs that actually delete start out in safe mode until this many have finished.
+pub const SAFE_MODE_RUNS: usize = 3;
+
+/// Finished runs that deleted something, i.e. not dry runs.
+pub fn completed_runs(history: &[Manifest]) -> usize {
+    history.iter().filter(|manifest| !manifest.dry_run).count()
+}
+
+/// Whether a dry run's report has been shown, which is what unlocks --force in safe mode.
+pub fn reviewed_a_report(history: &[Manifest]) -> bool {
+    history.iter().any(|manifest| manifest.dry_run)
+}
+
+/// Average bytes reclaimed per second of scanning + cleaning, best first.
+/// Runs where a target wasn't cleaned count its estimate, so dry runs help too.
+pub fn target_efficiency(history: &[Manifest]) -> Vec<(String, f64)> {
+    let mut totals: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+    for target in history.iter().flat_map(|run| &run.targets) {
+        let (bytes, millis) = match &target.result {
+            Some(result) => (result.freed, target.scan_ms + result.duration_ms),
+            _ => (target.size, target.scan_ms),
+        };
+        let entry = totals.entry(&target.id).or_insert((0, 0));
+        entry.0 += bytes;
+        entry.1 += millis;
+    }
+
+    let mut efficiency: Vec<(String, f64)> = totals.into_iter()
+        .filter(|(_, (bytes, _))| *bytes > 0)
+        .map(|(id, (bytes, millis))| (id.to_string(), bytes as f64 / (millis.max(1) as f64 / 1000.0)))
+        .collect();
+    efficiency.sort_by(|a, b| b.1.total_cmp(&a.1));
+    efficiency
+}
+
+/// Per-user state (baselines, history) lives in `~/.maccleanup`.
+pub fn state_dir() -> PathBuf {
+    // State belongs to whoever runs the tool, even when cleaning another account with --user
+    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
+    let dir = PathBuf::from(home).join(".maccleanup");
+    let _ = fs::create_dir_all(&dir);
+    dir
+}
+
+/// Order dotted version strings numerically ("10.2" > "9.14"), falling back to text.
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |version: &str| -> Vec<u64> {
+        version.split(|c: char| !c.is_ascii_digit())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    parse(a).cmp(&parse(b)).then_with(|| a.cmp(b))
+}
+
+/// Result of the last run's estimate, read by `status` so it never has to scan.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanCache {
+    pub created: String,
+    pub reclaimable: u64,
+    pub targets: BTreeMap<String, u64>,
+}
+
+pub fn scan_cache_path() -> PathBuf {
+    state_dir().join("scan-cache.json")
+}
+
+pub fn write_scan_cache(reclaimable: u64, manifest: &Manifest) {
+    let cache = ScanCache {
+        created: Local::now().to_rfc3339(),
+        reclaimable,
+        targets: manifest.targets.iter().map(|target| (target.id.clone(), target.size)).collect(),
+    };
+    if let Ok(json) = serde_json::to_string(&cache) {
+        let _ = fs::write(scan_cache_path(), json);
+    }
+}
+
+pub fn read_scan_cache() -> Option<ScanCache> {
+    let contents = fs::read_to_string(scan_cache_path()).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub fn runs_dir() -> PathBuf {
+    let dir = state_dir().join("runs");
+    let _ = fs::create_dir_all(&dir);
+    dir
+}
+
+pub fn new_run_state() -> RunState {
+    let now = Local::now();
+    RunState {
+        id: format!("{}-{}", now.format("%Y%m%d-%H%M%S"), std::process::id()),
+        started: now.to_rfc3339(),
+        args: env::args().collect(),
+        answers: BTreeMap::new(),
+        completed: Vec::new(),
+        finished: false,
+    }
+}
+
+pub fn save_run_state(run: &RunState) {
+    if let Ok(json) = serde_json::to_string_pretty(run) {
+        let _ = fs::write(runs_dir().join(format!("{}.json", run.id)), json);
+    }
+}
+
+/// The named run, or the most recent unfinished one when `id` is `None`.
+pub fn load_run_state(id: Option<&str>) -> Option<RunState> {
+    let read = |path: &Path| -> Option<RunState> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    };
+
+    if let Some(id) = id {
+        return read(&runs_dir().join(format!("{}.json", id))).filter(|run| !run.finished);
+    }
+
+    // IDs start with a timestamp, so the lexically largest is the newest
+    let mut runs: Vec<RunState> = fs::read_dir(runs_dir()).ok()?
+        .flatten()
+        .filter_map(|entry| read(&entry.path()))
+        .filter(|run| !run.finished)
+        .collect();
+    runs.sort_by(|a, b| a.id.cmp(&b.id));
+    runs.pop()
+}
+
+/// How bad it is to lose what a pack path holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SafetyClass {
+    Cache,
+    History,
+    UserData,
+}
+
+impl std::fmt::Display for SafetyClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            SafetyClass::Cache => "cache",
+            SafetyClass::History => "history",
+            SafetyClass::UserData => "user data",
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AppPacks {
+    pub app: Vec<AppPack>,
+}
+
+/// Declarative description of one app's cleanable data, from `src/apps.toml`.
+#[derive(Debug, Deserialize)]
+pub struct AppPack {
+    pub id: String,
+    pub name: String,
+    pub min_os: Option<String>,
+    #[serde(default)]
+    pub detect: PackDetect,
+    pub paths: Vec<PackPath>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PackDetect {
+    #[serde(default)]
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub commands: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PackPath {
+    pub path: String,
+    pub safety: SafetyClass,
+}
+
+impl AppPack {
+    pub fn is_installed(&self) -> bool {
+        self.detect.paths.iter().any(|path| Path::new(&expand_home(path)).exists())
+            || self.detect.commands.iter().any(|command| {
+                let mut parts = command.split_whitespace();
+                let program = parts.next().unwrap_or("");
+                Command::new(program).args(parts).output().is_ok()
+            })
+    }
+
+    /// Whether this macOS is new enough for the pack's paths to apply.
+    pub fn supports_this_os(&self) -> bool {
+        match (&self.min_os, macos_version()) {
+            (Some(min_os), Some(current)) => compare_versions(current, min_os) != std::cmp::Ordering::Less,
+            _ => true,
+        }
+    }
+
+    /// Expanded paths that exist and aren't ignored, with their safety class.
+    pub fn existing_paths(&self) -> Vec<(String, SafetyClass)> {
+        if !self.supports_this_os() {
+            return Vec::new();
+        }
+        self.paths.iter()
+            .flat_map(|pack_path| expand_wildcards(&expand_home(&pack_path.path)).into_iter()
+                .map(|path| (path, pack_path.safety)))
+            .filter(|(path, _)| Path::new(path).exists() && !is_ignored(Path::new(path)))
+            .collect()
+    }
+}
+
+/// Paths matching `pattern`, whose components may contain `*` and `?`; a pattern
+/// without wildcards comes back as is, whether or not it exists.
+pub fn expand_wildcards(pattern: &str) -> Vec<String> {
+    let mut matches = vec![PathBuf::from("/")];
+    for component in Path::new(pattern).components().skip(1) {
+        let component = component.as_os_str().to_str().unwrap_or("");
+        if !component.contains(['*', '?']) {
+            matches.iter_mut().for_each(|path| path.push(component));
+            continue;
+        }
+        matches = matches.iter()
+            .flat_map(|dir| fs::read_dir(dir).into_iter().flat_map(|entries| entries.flatten()))
+            .filter(|entry| entry.file_name().to_str().is_some_and(|name| wildcard_match(component, name)))
+            .map(|entry| entry.path())
+            .collect();
+        matches.sort();
+    }
+    matches.into_iter().filter_map(|path| path.to_str().map(String::from)).collect()
+}
+
+pub fn app_packs() -> &'static [AppPack] {
+    static PACKS: OnceLock<Vec<AppPack>> = OnceLock::new();
+    PACKS.get_or_init(|| {
+        let packs: AppPacks = toml::from_str(include_str!("apps.toml"))
+            .unwrap_or_else(|err| panic!("src/apps.toml is invalid: {}", err));
+        if let Err(problems) = validate_app_packs(&packs.app) {
+            panic!("src/apps.toml is invalid:\n  {}", problems.join("\n  "));
+        }
+        packs.app
+    })
+}
+
+/// Look up a pack that the code depends on; a missing id is a bug in `apps.toml`.
+pub fn app_pack(id: &str) -> &'static AppPack {
+    app_packs().iter()
+        .find(|pack| pack.id == id)
+        .unwrap_or_else(|| panic!("no app pack with id {:?} in src/apps.toml", id))
+}
+
+/// Checks every pack must pass before any of them is used.
+pub fn validate_app_packs(packs: &[AppPack]) -> Result<(), Vec<String>> {
+    let mut problems = Vec::new();
+    let mut seen = Vec::new();
+
+    for pack in packs {
+        if pack.id.is_empty() || !pack.id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_') {
+            problems.push(format!("{:?}: id must be lowercase letters, digits, '-' or '_'", pack.id));
+        }
+        if seen.contains(&&pack.id) {
+            problems.push(format!("{}: duplicate id", pack.id));
+        }
+        seen.push(&pack.id);
+
+        if pack.name.trim().is_empty() {
+            problems.push(format!("{}: missing name", pack.id));
+        }
+        if let Some(min_os) = &pack.min_os {
+            if min_os.split('.').any(|part| part.parse::<u32>().is_err()) {
+                problems.push(format!("{}: min_os {:?} is not a version like 13.0", pack.id, min_os));
+            }
+        }
+        if pack.paths.is_empty() {
+            problems.push(format!("{}: no paths", pack.id));
+        }
+
+        for path in pack.paths.iter().map(|pack_path| &pack_path.path).chain(&pack.detect.paths) {
+            if !(path.starts_with("~/") || path.starts_with('/')) {
+                problems.push(format!("{}: {:?} must start with ~/ or /", pack.id, path));
+            }
+            if path.split('/').any(|part| part == "..") {
+                problems.push(format!("{}: {:?} must not contain ..", pack.id, path));
+            }
+            // Cleaning a whole top-level folder is never what a pack means
+            if path.trim_end_matches('/').matches('/').count() < 2 {
+                problems.push(format!("{}: {:?} is too broad", pack.id, path));
+            }
+        }
+    }
+
+    if problems.is_empty() { Ok(()) } else { Err(problems) }
+}
+
+/// `~/...` relative to the home being cleaned.
+pub fn expand_home(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => format!("{}/{}", home_dir(), rest),
+        None => path.to_string(),
+    }
+}
+
+pub fn macos_version() -> Option<&'static str> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        let output = Command::new("sw_vers").arg("-productVersion").output().ok()?;
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!version.is_empty()).then_some(version)
+    }).as_deref()
+}
+
+/// Point at the real space consumers when cleanup can free less than 1/N of the used space.
+pub const BIG_TICKET_RATIO: u64 = 20;
+
+pub fn show_big_ticket_items(total_potential: u64, disk: &DiskInfo) {
+    println!("{}", "🔎 Cleanup Won't Help Much".bold().yellow());
+    println!("{}", "─".repeat(40).dimmed());
+    println!("  {} Cleanup can free about {} of the {} in use. The big consumers are elsewhere:",
+        "ℹ".blue(),
+        format_size(total_potential, BINARY).yellow(),
+        format_size(disk.used, BINARY));
+
+    let home = home_dir();
+    let mut home_dirs: Vec<(String, u64)> = fs::read_dir(&home).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.path().is_dir() && !entry.file_name().to_str().unwrap_or("").starts_with('.'))
+        .map(|entry| {
+            let path = entry.path().to_str().unwrap_or("").to_string();
+            let size = get_directory_size(&path);
+            (path, size)
+        })
+        .collect();
+    home_dirs.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    println!("\n  {}", "Largest folders in your home:".bold());
+    for (path, size) in home_dirs.iter().take(5) {
+        println!("    {} {} ({})", "•".dimmed(), path, format_size(*size, BINARY).red());
+    }
+
+    let vm_disks = find_vm_disks();
+    if !vm_disks.is_empty() {
+        println!("\n  {}", "Virtual machine disks:".bold());
+        for (path, size) in &vm_disks {
+            println!("    {} {} ({})", "•".dimmed(), path, format_size(*size, BINARY).red());
+        }
+    }
+
+    let photos: Vec<tasks::media_libraries::MediaLibrary> = tasks::media_libraries::find_media_libraries().into_iter()
+        .filter(|library| library.path.ends_with(".photoslibrary"))
+        .collect();
+    if !photos.is_empty() {
+        println!("\n  {}", "Photos libraries:".bold());
+        for library in &photos {
+            println!("    {} {} ({})", "•".dimmed(), library.path, format_size(library.size, BINARY).red());
+        }
+        println!("    {}", "Turning on \"Optimize Mac Storage\" in Photos → Settings → iCloud keeps originals in iCloud".dimmed());
+    }
+
+    let snapshots = local_snapshot_count();
+    if snapshots > 0 {
+        println!("\n  {} {} local Time Machine snapshots hold deleted files until macOS thins them",
+            "•".dimmed(), snapshots.to_string().yellow());
+        println!("    {}", "Free them now with: tmutil thinlocalsnapshots / 999999999999 4".dimmed());
+    }
+    println!();
+}
+
+/// Disk images of the common desktop hypervisors, with Docker's VM disk by allocated size.
+pub fn find_vm_disks() -> Vec<(String, u64)> {
+    let home = home_dir();
+    let mut disks = Vec::new();
+
+    if let Some((path, _, allocated)) = tasks::docker::docker_vm_disk() {
+        disks.push((path, allocated));
+    }
+
+    // (folder, bundle extension or "" for every entry)
+    let vm_folders = [
+        (format!("{}/Parallels", home), "pvm"),
+        (format!("{}/Virtual Machines.localized", home), "vmwarevm"),
+        (format!("{}/Library/Containers/com.utmapp.UTM/Data/Documents", home), "utm"),
+        (format!("{}/VirtualBox VMs", home), ""),
+    ];
+    for (folder, extension) in vm_folders {
+        for entry in fs::read_dir(&folder).into_iter().flat_map(|entries| entries.flatten()) {
+            let path = entry.path();
+            if extension.is_empty() || path.extension().is_some_and(|ext| ext == extension) {
+                let path = path.to_str().unwrap_or("").to_string();
+                let size = get_paths_size(std::slice::from_ref(&path));
+                disks.push((path, size));
+            }
+        }
+    }
+
+    disks.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    disks
+}
+
+pub fn local_snapshot_count() -> usize {
+    Command::new("tmutil").args(["listlocalsnapshots", "/"]).output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).lines()
+            .filter(|line| line.contains("com.apple.TimeMachine"))
+            .count())
+        .unwrap_or(0)
+}
+
+/// Backups older than this make the run hold back Aggressive targets.
+pub const BACKUP_STALE_DAYS: i64 = 7;
+
+/// What Time Machine says about the last backup, when a destination is configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupStatus {
+    /// The latest backup finished this many days ago
+    Recent(i64),
+    Stale(i64),
+    /// A destination is set, but no finished backup could be found (disk not connected, or never run)
+    Missing,
+}
+
+impl BackupStatus {
+    pub fn is_recent(&self) -> bool {
+        matches!(self, BackupStatus::Recent(_))
+    }
+}
+
+/// Age of the latest Time Machine backup; `None` when no destination is configured.
+pub fn time_machine_status() -> Option<BackupStatus> {
+    let destinations = Command::new("tmutil").arg("destinationinfo").output().ok()?;
+    let destinations = String::from_utf8_lossy(&destinations.stdout);
+    if !destinations.lines().any(|line| line.trim_start().starts_with("Name")) {
+        return None;
+    }
+
+    // ".../Backups.backupdb/Mac/2024-05-01-101500" or ".../2024-05-01-101500.backup"
+    let latest = Command::new("tmutil").arg("latestbackup").output().ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    let name = Path::new(&latest).file_name().and_then(|name| name.to_str()).unwrap_or("");
+    let Some(finished) = name.get(..17)
+        .and_then(|stamp| chrono::NaiveDateTime::parse_from_str(stamp, "%Y-%m-%d-%H%M%S").ok())
+        .and_then(|stamp| stamp.and_local_timezone(Local).single()) else {
+        return Some(BackupStatus::Missing);
+    };
+
+    let days = (Local::now() - finished).num_days();
+    Some(if days > BACKUP_STALE_DAYS { BackupStatus::Stale(days) } else { BackupStatus::Recent(days) })
+}
+
+/// How a run asks before deleting when no mode flag is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    Interactive,
+    DryRun,
+    Force,
+}
+
+/// Defaults for every run, kept in `~/.config/maccleanup/config.toml` and partly written by
+/// the first-run wizard. Command-line flags win over anything set here.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Mode used when none of --interactive, --dry-run or --force is given
+    #[serde(default)]
+    pub mode: Option<Mode>,
+    /// Target ids offered, all of them when empty
+    #[serde(default)]
+    pub only: Vec<String>,
+    /// Target ids never offered, e.g. "cookies" or "docker"
+    #[serde(default)]
+    pub skip: Vec<String>,
+    /// Highest risk offered when `--max-risk` isn't given
+    #[serde(default)]
+    pub max_risk: Option<Risk>,
+    /// Size limits per directory, e.g. "~/Library/Developer/Xcode/DerivedData" = "20GB"
+    #[serde(default)]
+    pub budgets: BTreeMap<String, String>,
+    /// Bundle ids whose Open Recent lists are never cleared, e.g. "com.microsoft.Word"
+    #[serde(default)]
+    pub keep_recents: Vec<String>,
+    /// Newest OS versions per platform whose Xcode DeviceSupport symbols are kept (default 2)
+    #[serde(default)]
+    pub keep_device_support: Option<usize>,
+    /// Days a target that failed (permissions, missing or broken tool) is skipped (default 7, 0 never skips)
+    #[serde(default)]
+    pub failure_cooldown_days: Option<u64>,
+    /// Age in days before files count as old, per target: "caches", "logs", "downloads", "extra_paths", "instruments", "cargo_targets", "mail_attachments", "simulators"
+    #[serde(default)]
+    pub max_age_days: BTreeMap<String, u64>,
+    /// More directories whose old files are cleaned, e.g. "~/Library/Caches/MyTool"
+    #[serde(default)]
+    pub extra_paths: Vec<String>,
+    /// Shell command run before each target deletes anything, e.g. to send its candidates
+    /// to restic or rclone; if it fails, nothing more is deleted
+    #[serde(default)]
+    pub backup_command: Option<String>,
+}
+
+impl Config {
+    /// Budgets with `~/` expanded and sizes parsed; bad entries are reported and left out.
+    pub fn budgets(&self) -> Vec<(String, u64)> {
+        self.budgets.iter()
+            .filter_map(|(path, size)| match parse_size_arg(size) {
+                Ok(size) => Some((expand_home(path), size)),
+                Err(err) => {
+                    println!("{} Ignoring budget for {} in {}: {}", "⚠".yellow(), path, config_path().display(), err);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Days before files of target `id` count as old, or `default` when not configured.
+    pub fn max_age(&self, id: &str, default: u64) -> u64 {
+        self.max_age_days.get(id).copied().unwrap_or(default)
+    }
+
+    /// Extra paths with `~/` expanded; the home folder itself and relative paths are left out.
+    pub fn extra_paths(&self) -> Vec<String> {
+        self.extra_paths.iter()
+            .filter_map(|path| {
+                let expanded = expand_home(path);
+                let trimmed = expanded.trim_end_matches('/');
+                if !expanded.starts_with('/') || trimmed.is_empty() || trimmed == home_dir().trim_end_matches('/') {
+                    println!("{} Ignoring extra path {} in {}: not a folder that can be cleaned",
+                        "⚠".yellow(), path, config_path().display());
+                    return None;
+                }
+                Some(expanded)
+            })
+            .collect()
+    }
+}
+
+/// Set by `--config` to read and write another file than the default.
+pub static CONFIG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// `--config` if given, else `~/.config/maccleanup/config.toml`. A config the wizard saved
+/// to `~/.maccleanup/config.toml` by older versions is still used when it's the only one.
+pub fn config_path() -> PathBuf {
+    if let Some(path) = CONFIG_FILE.get() {
+        return path.clone();
+    }
+    // Like the rest of our state, the config belongs to whoever runs the tool
+    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
+    let path = PathBuf::from(home).join(".config/maccleanup/config.toml");
+    let legacy = state_dir().join("config.toml");
+    if !path.exists() && legacy.exists() {
+        return legacy;
+    }
+    path
+}
+
+/// The saved config, or `None` before the wizard has run.
+pub fn load_config() -> Option<Config> {
+    let contents = fs::read_to_string(config_path()).ok()?;
+    match toml::from_str(&contents) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            println!("{} Ignoring {}: {}", "⚠".yellow(), config_path().display(), err);
+            Some(Config::default())
+        }
+    }
+}
+
+pub fn save_config(config: &Config) -> io::Result<()> {
+    let contents = toml::to_string(config).map_err(io::Error::other)?;
+    let header = "# Written by `maccleanup-rust setup`; edit freely or run setup again.\n";
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{}{}", header, contents))
+}
+
+/// Targets the socket server can scan and clean: the ones that need no prompts or selection.
+pub const SERVICE_TARGETS: &[&str] = &[
+    "caches", "logs", "downloads", "extra_paths", "trash", "xcode", "homebrew", "podman", "safari", "chrome", "python",
+    "cookies",
+];
+
+impl CleanupContext {
+    /// Context for prompt-free cleaning on behalf of a socket client.
+    pub fn unattended(dry_run: bool, max_risk: Risk, max_delete: Option<u64>) -> CleanupContext {
+        let config = load_config().unwrap_or_default();
+        CleanupContext {
+            interactive: false,
+            dry_run,
+            force: true,
+            verbose: false,
+            manifest: RefCell::new(Manifest {
+                created: Local::now().to_rfc3339(),
+                dry_run,
+                targets: Vec::new(),
+            }),
+            max_delete,
+            deleted_bytes: Cell::new(0),
+            left_by_cap: RefCell::new(Vec::new()),
+            permission_denied: RefCell::new(Vec::new()),
+            syslog: false,
+            run: RefCell::new(new_run_state()),
+            pending_action: RefCell::new(None),
+            target_stats: CleanupStats::new(),
+            scan_mark: Cell::new(Instant::now()),
+            budgets: config.budgets(),
+            extra_paths: config.extra_paths(),
+            only: config.only,
+            skip: config.skip,
+            opted_in: Vec::new(),
+            keep_recents: config.keep_recents,
+            keep_device_support: config.keep_device_support.unwrap_or(tasks::device_support::DEVICE_SUPPORT_KEEP),
+            max_risk,
+            current_risk: Cell::new(None),
+            removed_paths: RefCell::new(Vec::new()),
+            trimming: Cell::new(false),
+            max_age_days: config.max_age_days,
+            to_trash: None,
+            trashed_bytes: Cell::new(0),
+            recoverable: false,
+            staged_bytes: Cell::new(0),
+            backup_command: config.backup_command,
+            backup_failed: Cell::new(false),
+            backed_up: RefCell::new(std::collections::HashSet::new()),
+            missing_tool: RefCell::new(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Held by every test that reads or writes under HOME, so none of them sees another's
+    /// temporary home while the rest of the tests run in parallel.
+    static HOME_LOCK: Mutex<()> = Mutex::new(());
+
+    /// HOME pointed at a fresh temporary folder until dropped; then the previous HOME is put
+    /// back and the folder removed.
+    struct TempHome {
+        dir: PathBuf,
+        previous: Option<std::ffi::OsString>,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl TempHome {
+        fn new(name: &str) -> TempHome {
+            let lock = HOME_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let dir = env::temp_dir().join(format!("maccleanup-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            let previous = env::var_os("HOME");
+            env::set_var("HOME", &dir);
+            TempHome { dir, previous, _lock: lock }
+        }
+    }
+
+    impl Drop for TempHome {
+        fn drop(&mut self) {
+            match &self.previous {
+                Some(home) => env::set_var("HOME", home),
+                None => env::remove_var("HOME"),
+            }
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn embedded_app_packs_are_valid() {
+        let packs: AppPacks = toml::from_str(include_str!("apps.toml")).expect("src/apps.toml parses");
+        if let Err(problems) = validate_app_packs(&packs.app) {
+            panic!("src/apps.toml is invalid:\n  {}", problems.join("\n  "));
+        }
+    }
+
+    #[test]
+    fn every_pack_the_code_names_exists() {
+        let packs: AppPacks = toml::from_str(include_str!("apps.toml")).expect("src/apps.toml parses");
+        let named = TARGETS.iter().filter_map(|target| target.app)
+            .chain(CLIPBOARD_MANAGERS.iter().copied())
+            .chain(tasks::sync_caches::SYNC_CLIENTS.iter().map(|(id, _)| *id))
+            .chain(["safari", "chrome"]);
+        for id in named {
+            assert!(packs.app.iter().any(|pack| pack.id == id), "no app pack with id {:?} in src/apps.toml", id);
+        }
+    }
+
+    #[test]
+    fn every_task_is_registered_and_unregistered_ones_dont_panic() {
+        for task in tasks::TASKS {
+            assert!(task.info().is_some(), "no TARGETS entry for task {:?}", task.id());
+        }
+
+        struct Unregistered;
+        impl CleanupTask for Unregistered {
+            fn id(&self) -> &'static str {
+                "unregistered"
+            }
+            fn estimate(&self) -> u64 {
+                0
+            }
+            fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+                CleanupStats::new()
+            }
+            fn run(&self, _ctx: &CleanupContext) -> CleanupStats {
+                CleanupStats::new()
+            }
+        }
+        assert_eq!(Unregistered.name(), "unregistered");
+        assert_eq!(Unregistered.risk(), Risk::Aggressive);
+        assert!(!CleanupContext::unattended(true, Risk::Moderate, None).wants("unregistered"));
+    }
+
+    #[test]
+    fn remove_paths_counts_each_sibling_toward_the_delete_cap() {
+        let home = TempHome::new("delete-cap");
+        let paths: Vec<(String, u64)> = (0..5)
+            .map(|i| {
+                let tree = home.dir.join(format!("project{}/node_modules", i));
+                fs::create_dir_all(&tree).unwrap();
+                fs::write(tree.join("index.js"), b"x").unwrap();
+                (tree.to_str().unwrap().to_string(), 5 << 30)
+            })
+            .collect();
+
+        // Five 5 GiB trees against a 10 GiB cap: only the first two may go
+        let ctx = CleanupContext::unattended(false, Risk::Safe, Some(10 << 30));
+        let removed = ctx.remove_paths(&paths);
+        assert_eq!(removed, vec![true, true, false, false, false]);
+        assert_eq!(ctx.deleted_bytes.get(), 10 << 30);
+        assert_eq!(ctx.left_by_cap.borrow().len(), 3);
+        for (i, (path, _)) in paths.iter().enumerate() {
+            assert_eq!(Path::new(path).exists(), i >= 2);
+        }
+    }
+
+    #[test]
+    fn backup_command_sees_every_path_before_it_goes() {
+        let home = TempHome::new("backup-hook");
+        let handed = home.dir.join("handed");
+        let cache = home.dir.join("Library/Caches/com.example");
+        fs::create_dir_all(&cache).unwrap();
+        let files: Vec<PathBuf> = (0..3).map(|i| cache.join(format!("blob{}", i))).collect();
+        for file in &files {
+            fs::write(file, b"x").unwrap();
+        }
+
+        // Nothing was recorded up front, as with targets that only find files while cleaning
+        let mut ctx = CleanupContext::unattended(false, Risk::Safe, None);
+        ctx.backup_command = Some(format!("cat >> '{}'", handed.display()));
+        assert!(ctx.run_backup());
+        assert!(!handed.exists());
+
+        assert!(ctx.remove_path(&files[0], 1));
+        let batch: Vec<(String, u64)> = files[1..].iter().map(|file| (file.to_str().unwrap().to_string(), 1)).collect();
+        assert_eq!(ctx.remove_paths(&batch), vec![true, true]);
+        let listed = fs::read_to_string(&handed).unwrap();
+        for file in &files {
+            assert!(listed.lines().any(|line| line == file.to_str().unwrap()), "{} not handed over", file.display());
+            assert!(!file.exists());
+        }
+
+        // A folder that was handed over covers what's inside it
+        ctx.backed_up.borrow_mut().insert(cache.to_str().unwrap().to_string());
+        fs::write(&files[0], b"x").unwrap();
+        ctx.backup_command = Some("exit 1".to_string());
+        assert!(ctx.remove_path(&files[0], 1));
+
+        // Anything the command fails on is kept, as is everything after it
+        let other = home.dir.join("Library/Logs/old.log");
+        fs::create_dir_all(other.parent().unwrap()).unwrap();
+        fs::write(&other, b"x").unwrap();
+        assert!(!ctx.remove_path(&other, 1));
+        assert!(other.exists());
+        assert!(ctx.backup_failed.get());
+        assert!(!ctx.run_backup());
+    }
+
+    #[test]
+    fn only_missing_tools_and_targets_that_deleted_nothing_cool_down() {
+        let home = TempHome::new("cooldowns");
+        let ctx = CleanupContext::unattended(false, Risk::Safe, None);
+        let file = home.dir.join("old.log");
+
+        // One item it couldn't handle, next to one it deleted, isn't a failure
+        fs::write(&file, b"x").unwrap();
+        ctx.record_target("logs", 2);
+        ctx.run_target(|| {
+            let stats = CleanupStats::new();
+            ctx.log_error("Failed to remove locked.log");
+            ctx.target_stats.record_error();
+            if ctx.remove_path(&file, 1) {
+                stats.record_removed(1);
+            }
+            stats
+        });
+        assert_eq!(target_failure(&ctx, "logs"), None);
+
+        ctx.record_target("docker", 0);
+        ctx.run_target(|| {
+            ctx.log_missing_tool("docker");
+            CleanupStats::new()
+        });
+        assert_eq!(target_failure(&ctx, "docker").as_deref(), Some("docker is not installed"));
+
+        ctx.record_target("caches", 1);
+        ctx.run_target(|| {
+            ctx.target_stats.record_error();
+            CleanupStats::new()
+        });
+        let failure = target_failure(&ctx, "caches");
+        assert_eq!(failure.as_deref(), Some("none of 1 items could be deleted"));
+
+        record_target_outcome("caches", failure);
+        record_target_outcome("logs", None);
+        let cooling: Vec<String> = cooling_down(FAILURE_COOLDOWN_DAYS).into_iter().map(|(id, _, _)| id).collect();
+        assert_eq!(cooling, ["caches"]);
+        record_target_outcome("caches", None);
+        assert!(cooling_down(FAILURE_COOLDOWN_DAYS).is_empty());
+    }
+
+    #[test]
+    fn a_failed_move_across_volumes_leaves_no_partial_copy() {
+        // Needs a second filesystem to rename across; Linux CI has one in /dev/shm
+        let other_volume = Path::new("/dev/shm");
+        if !other_volume.is_dir() {
+            return;
+        }
+        let source = env::temp_dir().join(format!("maccleanup-move-{}", std::process::id()));
+        let destination = other_volume.join(format!("maccleanup-move-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&source);
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("a.txt"), b"kept").unwrap();
+        // A socket can't be copied, so the copy fails partway through
+        let _listener = std::os::unix::net::UnixListener::bind(source.join("z.sock")).unwrap();
+
+        assert!(move_path(&source, &destination).is_err());
+        assert!(!destination.exists());
+        assert_eq!(fs::read(source.join("a.txt")).unwrap(), b"kept");
+        let _ = fs::remove_dir_all(&source);
+    }
+
+    #[test]
+    fn history_cut_point_lands_on_an_entry_start() {
+        use tasks::shell_history::{history_cut_point, HISTORY_KEEP_LINES};
+        let raw = 10;
+
+        // Plain zsh history has no entry markers, so the raw cut stands instead of running off the end
+        let plain = vec![&b"ls -la"[..]; HISTORY_KEEP_LINES + raw];
+        assert_eq!(history_cut_point("/Users/me/.zsh_history", &plain), raw);
+
+        // Extended history skips the continuation lines of a multi-line entry
+        let mut extended = vec![&b": 1700000000:0;echo one \\"[..]; HISTORY_KEEP_LINES + raw];
+        extended[raw] = b": not an entry";
+        extended[raw + 1] = b"continued";
+        assert_eq!(history_cut_point("/Users/me/.zsh_history", &extended), raw + 2);
+
+        let mut fish = vec![&b"  when: 1700000000"[..]; HISTORY_KEEP_LINES + raw];
+        fish[raw + 3] = b"- cmd: git status";
+        assert_eq!(history_cut_point("/Users/me/.local/share/fish/fish_history", &fish), raw + 3);
+        fish[raw + 3] = b"  paths:";
+        assert_eq!(history_cut_point("/Users/me/.local/share/fish/fish_history", &fish), raw);
+
+        assert_eq!(history_cut_point("/Users/me/.bash_history", &extended), raw);
+    }
+
+    #[test]
+    fn compacting_a_history_keeps_its_mode_and_sets_the_original_aside() {
+        use std::os::unix::fs::PermissionsExt;
+        use tasks::shell_history::{compact_histories, HISTORY_KEEP_LINES};
+
+        let home = TempHome::new("compact-history");
+        let history = home.dir.join(".zsh_history");
+        let contents: String = (0..HISTORY_KEEP_LINES + 100).map(|i| format!("echo {}\n", i)).collect();
+        fs::write(&history, &contents).unwrap();
+        fs::set_permissions(&history, fs::Permissions::from_mode(0o600)).unwrap();
+        let path = history.to_str().unwrap().to_string();
+
+        let ctx = CleanupContext::unattended(false, Risk::Safe, None);
+        let stats = compact_histories(&ctx, &[(path.clone(), contents.len() as u64)]);
+
+        let compacted = fs::read_to_string(&history).unwrap();
+        assert!(compacted.starts_with("echo 101\n"), "kept from {:?}", compacted.lines().next());
+        assert_eq!(fs::metadata(&history).unwrap().permissions().mode() & 0o777, 0o600);
+        assert!(stats.space_freed() > 0);
+
+        let staged = quarantine_dir(&ctx.run.borrow().id).join("files").join(path.trim_start_matches('/'));
+        assert_eq!(fs::read_to_string(staged).unwrap(), contents);
+        assert!(!Path::new(&format!("{}.maccleanup-tmp", path)).exists());
+    }
+
+    #[test]
+    fn same_name_ignores_case_only_on_case_insensitive_volumes() {
+        assert!(same_name("node_modules", "node_modules", || false));
+        assert!(same_name("node_modules", "node_modules", || true));
+        assert!(same_name("Node_Modules", "node_modules", || true));
+        assert!(!same_name("Node_Modules", "node_modules", || false));
+        assert!(same_name("LIBRARY", "Library", || true));
+        assert!(!same_name("LIBRARY", "Library", || false));
+        assert!(!same_name("node_module", "node_modules", || true));
+    }
+
+    #[test]
+    fn same_name_only_probes_the_volume_for_near_misses() {
+        let probed = Cell::new(false);
+        assert!(same_name("Library", "Library", || { probed.set(true); true }));
+        assert!(!same_name("Caches", "Library", || { probed.set(true); true }));
+        assert!(!probed.get());
+    }
+
+    #[test]
+    fn name_and_extension_matching_follow_the_volume() {
+        let dir = env::temp_dir().join(format!("maccleanup-case-{}", std::process::id()));
+        let modules = dir.join("Node_Modules");
+        let bytecode = dir.join("cache.PYC");
+        fs::create_dir_all(&modules).unwrap();
+        fs::write(&bytecode, b"").unwrap();
+
+        let insensitive = volume_is_case_insensitive(&modules);
+        // Looking the folder up with different case finds it only on a case-insensitive volume
+        assert_eq!(insensitive, dir.join("node_modules").exists());
+        assert!(name_is(&modules, "Node_Modules"));
+        assert_eq!(name_is(&modules, "node_modules"), insensitive);
+        assert!(extension_is(&bytecode, &["PYC"]));
+        assert_eq!(extension_is(&bytecode, &["pyc"]), insensitive);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn matching_is_exact_on_a_case_sensitive_apfs_image() {
+        let dir = env::temp_dir().join(format!("maccleanup-case-sensitive-{}", std::process::id()));
+        let image = dir.join("volume.dmg");
+        let mount = dir.join("mount");
+        fs::create_dir_all(&mount).unwrap();
+        let hdiutil = |args: &[&std::ffi::OsStr]| Command::new("hdiutil").args(args).output()
+            .is_ok_and(|output| output.status.success());
+        assert!(hdiutil(&["create".as_ref(), "-size".as_ref(), "20m".as_ref(), "-fs".as_ref(),
+            "Case-sensitive APFS".as_ref(), "-volname".as_ref(), "maccleanup-test".as_ref(), image.as_os_str()]));
+        assert!(hdiutil(&["attach".as_ref(), "-nobrowse".as_ref(), "-mountpoint".as_ref(), mount.as_os_str(), image.as_os_str()]));
+
+        for name in ["node_modules", "Node_Modules", "Library", "library"] {
+            fs::create_dir(mount.join(name)).unwrap();
+        }
+        fs::write(mount.join("cache.PYC"), b"").unwrap();
+
+        assert!(!volume_is_case_insensitive(&mount.join("node_modules")));
+        assert!(name_is(&mount.join("node_modules"), "node_modules"));
+        assert!(!name_is(&mount.join("Node_Modules"), "node_modules"));
+        assert!(name_is(&mount.join("Library"), "Library"));
+        assert!(!name_is(&mount.join("library"), "Library"));
+        assert!(!extension_is(&mount.join("cache.PYC"), &["pyc"]));
+
+        hdiutil(&["detach".as_ref(), mount.as_os_str()]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn edit_database_restores_the_backup_when_the_edit_corrupts_it() {
+        if Command::new("sqlite3").arg("-version").output().is_err() {
+            return;
+        }
+        // The backup goes under ~/.maccleanup, so keep it out of the real home folder
+        let home = TempHome::new("edit-database");
+
+        let db = home.dir.join("test.db");
+        let db = db.to_str().unwrap();
+        let sqlite = |sql: &str| Command::new("sqlite3").arg(db).arg(sql).output().unwrap();
+        sqlite("CREATE TABLE t(x); INSERT INTO t VALUES (1), (2), (3);");
+
+        let ctx = CleanupContext::unattended(false, Risk::Safe, None);
+        // The edit itself succeeds but leaves a file that isn't a database any more
+        let corrupt = format!("DELETE FROM t WHERE x = 1; SELECT writefile('{}', randomblob(8192));", db);
+        assert!(!edit_database(&ctx, db, &corrupt));
+        assert_eq!(ctx.target_stats.errors(), 1);
+
+        let rows = sqlite("SELECT count(*) FROM t;");
+        assert_eq!(String::from_utf8_lossy(&rows.stdout).trim(), "3");
+        let backup = quarantine_dir(&ctx.run.borrow().id).join(db.trim_start_matches('/').replace('/', "_")).join("test.db");
+        assert!(backup.exists());
+    }
+}
@@ -1,20 +1,21 @@
-use std::fs;
-use std::path::Path;
-use std::process::Command;
-use std::io::{self, Write};
-use std::env;
-use std::thread;
-use std::time::Duration;
+use std::cell::{Cell, RefCell};
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::Instant;
 use colored::*;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use chrono::Local;
 use humansize::{format_size, BINARY};
+use maccleanup::*;
+use maccleanup::tasks::{self, TASKS};
 
 #[derive(Parser)]
 #[command(name = "maccleanup-rust")]
-#[command(about = "🧹 Mac Cleanup Tool (Rust Edition) By Gappa", long_about = None)]
+#[command(about = "🧹 Mac Cleanup Tool (Rust Edition) By Gappa", long_about = None, version)]
 struct Cli {
-    /// Run in interactive mode (ask before each action)
-    #[arg(short, long, default_value_t = true)]
+    /// Run in interactive mode (ask before each action; the default unless the config sets a mode)
+    #[arg(short, long, default_value_t = false)]
     interactive: bool,
 
     /// Dry run - only show what would be deleted
@@ -29,115 +30,632 @@ struct Cli {
     #[arg(short, long, default_value_t = false)]
     verbose: bool,
 
-    /// Clean RAM only
+    /// Clean RAM only (same as `memory purge`)
     #[arg(short = 'r', long, default_value_t = false)]
     ram_only: bool,
-}
 
-#[derive(Debug)]
-struct CleanupStats {
-    files_removed: usize,
-    space_freed: u64,
+    /// Stop deleting once this much has been removed (e.g. 20GB, 500M)
+    #[arg(long, value_name = "SIZE", value_parser = parse_size_arg)]
+    max_delete: Option<u64>,
+
+    /// Skip the remaining targets once this much space is free (e.g. 50GB, 1.5TiB)
+    #[arg(long, value_name = "SIZE", value_parser = parse_size_arg)]
+    free_target: Option<u64>,
+
+    /// Also trim giant shell histories, old zsh sessions, zcompdump duplicates and iTerm2 saved state
+    #[arg(long, default_value_t = false)]
+    shell_history: bool,
+
+    /// Only clean project build artifacts and caches inside this folder, e.g. an external project drive
+    #[arg(long, value_name = "DIR")]
+    path: Option<PathBuf>,
+
+    /// Also delete .DS_Store files under the home folder (or --path)
+    #[arg(long, default_value_t = false)]
+    ds_store: bool,
+
+    /// Also offer to remove Cargo target/ directories not built into for 30 days
+    #[arg(long, default_value_t = false)]
+    cargo_targets: bool,
+
+    /// Record every deletion in the system log (visible via `log show` / Console)
+    #[arg(long, default_value_t = false)]
+    syslog: bool,
+
+    /// Estimate sizes in seconds from the last scan and shallow walks; figures are approximate
+    #[arg(long, global = true, default_value_t = false)]
+    quick: bool,
+
+    /// Unattended runs (--force, watch budget checks) wait until there's been no input for this many minutes
+    #[arg(long, global = true, value_name = "MINUTES")]
+    when_idle: Option<u64>,
+
+    /// Unattended runs only start on AC power
+    #[arg(long, global = true, default_value_t = false)]
+    on_ac_power: bool,
+
+    /// Unattended runs only start while the screen is locked
+    #[arg(long, global = true, default_value_t = false)]
+    when_locked: bool,
+
+    /// Measure directories we can't read with `sudo du` instead of under-reporting them
+    #[arg(long, default_value_t = false)]
+    sudo_estimates: bool,
+
+    /// Allow running as root or with HOME set to `/`
+    #[arg(long, default_value_t = false)]
+    allow_root: bool,
+
+    /// Clean another account's per-user data instead of your own (requires sudo)
+    #[arg(long, value_name = "NAME")]
+    user: Option<String>,
+
+    /// Only offer targets up to this risk level (defaults to moderate with --force)
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    max_risk: Option<Risk>,
+
+    /// Offer to clear the Gatekeeper quarantine flag from downloads you keep
+    #[arg(long, default_value_t = false)]
+    unquarantine: bool,
+
+    /// If another run is already cleaning, wait for it to finish instead of exiting
+    #[arg(long, default_value_t = false)]
+    wait: bool,
+
+    /// Only offer these targets or groups, e.g. xcode,docker,trash (see `explain`)
+    #[arg(long, value_name = "IDS", value_delimiter = ',')]
+    only: Vec<String>,
+
+    /// Never offer these targets or groups, e.g. cookies,containers
+    #[arg(long, value_name = "IDS", value_delimiter = ',')]
+    skip: Vec<String>,
+
+    /// Move files to the Trash instead of deleting them, so they can be put back
+    #[arg(long, default_value_t = false, conflicts_with = "no_trash")]
+    to_trash: bool,
+
+    /// Delete old downloads outright instead of moving them to the Trash
+    #[arg(long, default_value_t = false)]
+    no_trash: bool,
+
+    /// Keep deleted items in ~/.maccleanup/quarantine so `restore` can put them back
+    #[arg(long, default_value_t = false)]
+    recoverable: bool,
+
+    /// Offer Aggressive targets even when Time Machine hasn't backed up in the last week
+    #[arg(long, default_value_t = false)]
+    skip_backup_check: bool,
+
+    /// Run targets that failed recently instead of skipping them until their cooldown ends
+    #[arg(long, default_value_t = false)]
+    retry_failed: bool,
+
+    /// Skip the safe mode of the first few runs and allow --force without a reviewed dry run
+    #[arg(long = "i-know-what-im-doing", default_value_t = false)]
+    i_know_what_im_doing: bool,
+
+    /// Review every target on one full-screen list with checkboxes, then clean the ticked ones
+    #[arg(long, default_value_t = false, conflicts_with = "force")]
+    tui: bool,
+
+    /// Read settings from this file instead of ~/.config/maccleanup/config.toml
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Write what each target found to a JSON manifest (compare runs with `diff`)
+    #[arg(long, value_name = "PATH")]
+    manifest: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Time the scanning engine over the given roots with 1 and N threads
+    Bench {
+        /// Directories to scan (defaults to ~/Library/Caches)
+        roots: Vec<String>,
+
+        /// Worker threads for the parallel pass
+        #[arg(short, long, default_value_t = 4)]
+        threads: usize,
+
+        /// Time deleting instead: build sibling trees holding this many files in the temp folder and delete them
+        #[arg(long, value_name = "FILES")]
+        delete: Option<u64>,
+    },
+
+    /// Show which directories grew most since a recorded baseline
+    Growth {
+        /// Record a fresh baseline instead of comparing against the old one
+        #[arg(long)]
+        reset: bool,
+
+        /// Number of directories to show
+        #[arg(short = 'n', long, default_value_t = 15)]
+        top: usize,
+    },
+
+    /// Show the reclaimable space found by the last run, without scanning (with --quick, a fast approximate scan)
+    Status {
+        /// Print a single line for shell prompts and status bars
+        #[arg(long)]
+        short: bool,
+    },
+
+    /// Continue an interrupted run without re-asking answered prompts
+    Resume {
+        /// Run ID printed at the start of the run (defaults to the latest unfinished one)
+        id: Option<String>,
+    },
+
+    /// List pip packages across every Python interpreter, largest first
+    PythonPackages {
+        /// Only show packages at least this large (e.g. 50MB)
+        #[arg(long, value_name = "SIZE", value_parser = parse_size_arg, default_value = "10MB")]
+        min_size: u64,
+    },
+
+    /// Compare two manifests and show which junk appeared or grew
+    Diff {
+        /// Older manifest
+        before: PathBuf,
+
+        /// Newer manifest
+        after: PathBuf,
+    },
+
+    /// RAM tools: purge inactive memory, show usage and swap, or find the hogs
+    Memory {
+        #[command(subcommand)]
+        action: MemoryAction,
+    },
+
+    /// Summarize recent runs into a text or HTML digest
+    Digest {
+        /// How many days of history to include
+        #[arg(long, default_value_t = 7)]
+        days: i64,
+
+        /// Render HTML instead of plain text
+        #[arg(long)]
+        html: bool,
+
+        /// Write the digest to a file instead of printing it
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Email the digest to this address via sendmail
+        #[arg(long, value_name = "ADDRESS")]
+        mail: Option<String>,
+    },
+
+    /// Forecast how fast each target's junk grows, from the run history
+    Forecast {
+        /// Number of targets to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// Break macOS' "System Data" down into caches, logs, snapshots, VMs and the rest, with what's reclaimable
+    SystemData,
+
+    /// Put back what a run moved to the Trash or kept with --recoverable
+    Restore {
+        /// Run ID printed at the start of the run (lists runs when omitted)
+        id: Option<String>,
+
+        /// Restore the most recent run
+        #[arg(long, conflicts_with = "id")]
+        last: bool,
+
+        /// Delete everything kept by --recoverable instead, freeing its space
+        #[arg(long, conflicts_with_all = ["id", "last"])]
+        purge: bool,
+    },
+
+    /// Browse a local Time Machine snapshot from before a run, to diff or copy back what it deleted
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// Show the version, build target, features and the paths this build reads and writes
+    Info {
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Re-run the first-time setup wizard and rewrite the config
+    Setup,
+
+    /// Serve scan/clean requests as JSON-RPC on a Unix socket, for GUI front ends
+    Serve {
+        /// Socket to listen on
+        #[arg(long, value_name = "PATH", default_value = "~/.maccleanup/serve.sock")]
+        socket: String,
+    },
+
+    /// Stay running and alert when free space runs low or a directory outgrows its budget
+    Watch {
+        /// Seconds between checks
+        #[arg(long, default_value_t = 300)]
+        interval: u64,
+
+        /// Alert when free space drops below this (e.g. 10GB)
+        #[arg(long, value_name = "SIZE", value_parser = parse_size_arg, default_value = "10GB")]
+        min_free: u64,
+
+        /// Alert when a directory grows past a size, e.g. ~/Library/Developer/Xcode/DerivedData=20GB
+        /// (repeatable; defaults to the budgets in the config)
+        #[arg(long, value_name = "PATH=SIZE", value_parser = parse_budget_arg)]
+        budget: Vec<(String, u64)>,
+    },
+
+    /// Clear browsing history, cookies, recent items, QuickLook thumbnails and clipboard history
+    Privacy,
+
+    /// Clear one app's caches, container caches, logs and saved state, e.g. com.spotify.*
+    CleanApp {
+        /// Bundle id, folder or app name; `*` and `?` match any characters
+        app: String,
+    },
+
+    /// Show exactly what a target deletes, its risk level and which macOS versions it applies to
+    Explain {
+        /// Target id, e.g. xcode (lists all targets when omitted)
+        target: Option<String>,
+
+        /// Print a JSON description instead of text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotAction {
+    /// Mount the newest local snapshot taken before a run read-only and copy back chosen items
+    Browse {
+        /// Run ID printed at the start of the run (defaults to the most recent run that deleted something)
+        id: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum MemoryAction {
+    /// Purge inactive memory (requires sudo)
+    Purge,
+
+    /// Show RAM usage, swap usage and memory pressure
+    Status,
+
+    /// List the apps and processes using the most memory
+    Top {
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+
+        /// Offer to quit selected apps afterwards
+        #[arg(long)]
+        quit: bool,
+    },
 }
 
-impl CleanupStats {
-    fn new() -> Self {
-        CleanupStats {
-            files_removed: 0,
-            space_freed: 0,
+fn main() {
+    let mut cli = Cli::parse();
+
+    // Prompt/status-bar and JSON output must not start with the banner
+    if let Some(Commands::Status { short: true }) = &cli.command {
+        print_status_line();
+        return;
+    }
+    if let Some(Commands::Explain { target, json: true }) = &cli.command {
+        run_explain(target.as_deref(), true);
+        return;
+    }
+    // Support needs this even where a run would refuse to start, e.g. as root
+    if let Some(Commands::Info { json }) = &cli.command {
+        if let Some(path) = &cli.config {
+            let _ = CONFIG_FILE.set(path.clone());
         }
+        run_info(*json);
+        return;
     }
+    
+    println!("{}", "🧹 Mac Cleanup Tool (Rust Edition) By Gappa".bold().blue());
+    println!("{}", "===============================================\n".blue());
 
-    fn add(&mut self, other: &CleanupStats) {
-        self.files_removed += other.files_removed;
-        self.space_freed += other.space_freed;
+    if let Err(reason) = validate_environment(cli.allow_root || cli.user.is_some()) {
+        println!("{} {}", "✗".red(), reason);
+        std::process::exit(1);
     }
-}
 
-#[derive(Debug)]
-struct DiskInfo {
-    total: u64,
-    available: u64,
-    used: u64,
-    percent_used: f32,
-}
+    // Resuming replays the original flags with the saved answers
+    let mut resumed_run = None;
+    if let Some(Commands::Resume { id }) = &cli.command {
+        match load_run_state(id.as_deref()) {
+            Some(run) => {
+                println!("{} Resuming run {} (started {})\n", "↻".cyan(), run.id.bold(), run.started.dimmed());
+                cli = Cli::parse_from(&run.args);
+                resumed_run = Some(run);
+            }
+            None => {
+                println!("{} No interrupted run found to resume", "ℹ".blue());
+                return;
+            }
+        }
+    }
 
-struct CleanupContext {
-    interactive: bool,
-    dry_run: bool,
-    force: bool,
-    verbose: bool,
-}
+    SUDO_ESTIMATES.store(cli.sudo_estimates, Ordering::Relaxed);
+    let run_conditions = RunConditions {
+        idle_minutes: cli.when_idle,
+        on_ac_power: cli.on_ac_power,
+        when_locked: cli.when_locked,
+    };
 
-impl CleanupContext {
-    fn should_proceed(&self, action: &str, details: Option<String>) -> bool {
-        if self.dry_run {
-            println!("  {} [DRY RUN] Would {}", "→".yellow(), action);
-            if let Some(detail) = details {
-                println!("    {}", detail.dimmed());
-            }
-            return false;
+    let (only, skip) = match (resolve_target_ids(&cli.only), resolve_target_ids(&cli.skip)) {
+        (Ok(only), Ok(skip)) => (only, skip),
+        (Err(message), _) | (_, Err(message)) => {
+            println!("{} {}", "✗".red(), message);
+            std::process::exit(1);
         }
+    };
 
-        if self.force {
-            return true;
+    // --path narrows the run to the project targets, searching that folder alone
+    let only = match &cli.path {
+        Some(path) => {
+            let dir = std::fs::canonicalize(path).ok().filter(|dir| dir.is_dir() && dir != Path::new("/"));
+            let Some(dir) = dir else {
+                println!("{} --path needs an existing folder other than /: {}", "✗".red(), path.display());
+                std::process::exit(1);
+            };
+            let _ = SCOPE_PATH.set(dir.to_str().unwrap_or("").to_string());
+            let scoped: Vec<String> = if only.is_empty() {
+                PROJECT_TARGETS.iter().map(|id| id.to_string()).collect()
+            } else {
+                only.into_iter().filter(|id| PROJECT_TARGETS.contains(&id.as_str())).collect()
+            };
+            if scoped.is_empty() {
+                println!("{} --only names none of the targets --path applies to: {}", "✗".red(), PROJECT_TARGETS.join(", "));
+                std::process::exit(1);
+            }
+            println!("{} Cleaning inside {} only\n", "📂".cyan(), dir.display());
+            scoped
         }
+        None => only,
+    };
 
-        if self.interactive {
-            print!("  {} {} {} ", "?".cyan(), action, "Proceed? (y/N):".yellow());
-            io::stdout().flush().unwrap();
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-            
-            return input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes";
+    if let Some(path) = &cli.config {
+        if !path.exists() {
+            println!("{} Config file {} not found", "✗".red(), path.display());
+            std::process::exit(1);
         }
+        let _ = CONFIG_FILE.set(path.clone());
+    }
 
-        true
+    if let Some(name) = &cli.user {
+        // SAFETY: geteuid has no preconditions and cannot fail
+        if unsafe { libc::geteuid() } != 0 {
+            println!("{} --user needs root to read and delete another account's files; run it with sudo", "✗".red());
+            std::process::exit(1);
+        }
+        let Some(user) = lookup_user(name) else {
+            println!("{} No account named {}", "✗".red(), name);
+            std::process::exit(1);
+        };
+        println!("{} Cleaning per-user data of {} ({})\n", "👤".cyan(), user.name.bold(), user.home.dimmed());
+        let _ = TARGET_USER.set(user);
     }
+    let other_user = TARGET_USER.get().is_some();
 
-    fn log_action(&self, message: &str) {
-        if self.verbose {
-            println!("  {} {}", "→".green(), message);
+    // `memory purge` and `privacy` need a cleanup context, so they run once it exists below
+    let purge_only = matches!(cli.command, Some(Commands::Memory { action: MemoryAction::Purge }));
+    let privacy_only = matches!(cli.command, Some(Commands::Privacy));
+    let clean_app = match &cli.command {
+        Some(Commands::CleanApp { app }) => Some(app.clone()),
+        _ => None,
+    };
+    if let Some(command) = cli.command.as_ref().filter(|_| !purge_only && !privacy_only && clean_app.is_none()) {
+        match command {
+            Commands::Bench { roots, threads, delete: None } => run_bench(roots, *threads),
+            Commands::Bench { threads, delete: Some(files), .. } => run_delete_bench(*files, *threads),
+            Commands::Diff { before, after } => run_diff(before, after),
+            Commands::Growth { reset, top } => run_growth(*reset, *top),
+            Commands::Status { .. } => run_status(cli.quick),
+            Commands::PythonPackages { min_size } => run_python_packages(*min_size),
+            Commands::Memory { action: MemoryAction::Status } => run_memory_status(),
+            Commands::Digest { days, html, output, mail } => run_digest(*days, *html, output.as_deref(), mail.as_deref()),
+            Commands::Memory { action: MemoryAction::Top { count, quit } } => run_memory_top(*count, *quit),
+            Commands::Forecast { top } => run_forecast(*top),
+            Commands::SystemData => run_system_data(),
+            Commands::Restore { id, last, purge } => run_restore(id.as_deref(), *last, *purge),
+            Commands::Snapshot { action: SnapshotAction::Browse { id } } => run_snapshot_browse(id.as_deref()),
+            Commands::Setup => { run_setup(); }
+            Commands::Explain { target, json } => run_explain(target.as_deref(), *json),
+            Commands::Serve { socket } => run_serve(socket),
+            Commands::Watch { interval, min_free, budget } => {
+                let budgets = if budget.is_empty() { load_config().unwrap_or_default().budgets() } else { budget.clone() };
+                run_watch(*interval, *min_free, &budgets, &run_conditions)
+            }
+            Commands::Memory { action: MemoryAction::Purge } => unreachable!("purge runs with the cleanup context"),
+            Commands::Privacy => unreachable!("privacy runs with the cleanup context"),
+            Commands::CleanApp { .. } => unreachable!("clean-app runs with the cleanup context"),
+            Commands::Info { .. } => unreachable!("info runs before the banner"),
+            Commands::Resume { .. } => unreachable!("resume is rewritten into the original command"),
         }
+        return;
     }
 
-    fn log_error(&self, message: &str) {
-        println!("  {} {}", "✗".red(), message);
+    // New users get the wizard before any prompt about deleting their cookies or containers
+    let config = match load_config() {
+        Some(config) => config,
+        None if !cli.force && !cli.ram_only && !purge_only && !privacy_only && clean_app.is_none()
+            && io::stdin().is_terminal() => run_setup(),
+        None => Config::default(),
+    };
+
+    // The config's mode only applies when no mode flag was given
+    if !cli.interactive && !cli.dry_run && !cli.force {
+        match config.mode {
+            Some(Mode::DryRun) => cli.dry_run = true,
+            Some(Mode::Force) => cli.force = true,
+            Some(Mode::Interactive) | None => {}
+        }
     }
 
-    fn log_success(&self, message: &str) {
-        println!("  {} {}", "✓".green(), message);
+    // The first few runs only touch what rebuilds itself and keep everything for `restore`
+    let history = read_history();
+    let safe_mode = !cli.i_know_what_im_doing && !cli.dry_run
+        && completed_runs(&history) < SAFE_MODE_RUNS;
+    if safe_mode && cli.force && !reviewed_a_report(&history) {
+        println!("{} --force deletes without asking, so review a report first: run with --dry-run, \
+            or pass --i-know-what-im-doing", "✗".red());
+        std::process::exit(1);
     }
 
-    fn log_info(&self, message: &str) {
-        println!("  {} {}", "ℹ".blue(), message);
+    // Scheduled runs wait for a better moment instead of deleting while someone's at the Mac
+    if cli.force && !cli.dry_run {
+        if let Some(reason) = run_conditions.unmet() {
+            println!("{} Skipping this run: {}", "⏸".yellow(), reason);
+            print_skipped_result_line(&reason);
+            return;
+        }
     }
-}
 
-fn main() {
-    let cli = Cli::parse();
-    
-    println!("{}", "🧹 Mac Cleanup Tool (Rust Edition) By Gappa".bold().blue());
-    println!("{}", "===============================================\n".blue());
+    // Aggressive targets delete what only a backup could bring back
+    let backup_status = if cli.dry_run || cli.skip_backup_check { None } else { time_machine_status() };
+    let backup_risk_cap = backup_status.filter(|status| !status.is_recent()).map(|_| Risk::Moderate);
+
+    // Held until exit so a scheduled run and a manual one never delete side by side
+    let _run_lock = if cli.dry_run {
+        None
+    } else {
+        match acquire_run_lock(cli.wait) {
+            Ok(lock) => Some(lock),
+            Err(message) => {
+                println!("{} {}", "✗".red(), message);
+                std::process::exit(1);
+            }
+        }
+    };
+
+    // Targets that failed recently (say, Docker is broken) sit out a few runs instead of failing in each
+    let cooling: Vec<(String, u64, String)> = if cli.retry_failed {
+        Vec::new()
+    } else {
+        cooling_down(config.failure_cooldown_days.unwrap_or(FAILURE_COOLDOWN_DAYS)).into_iter()
+            .filter(|(id, _, _)| !only.contains(id))
+            .collect()
+    };
 
     let ctx = CleanupContext {
-        interactive: cli.interactive && !cli.force,
+        interactive: !cli.force,
         dry_run: cli.dry_run,
         force: cli.force,
         verbose: cli.verbose,
+        manifest: RefCell::new(Manifest {
+            created: Local::now().to_rfc3339(),
+            dry_run: cli.dry_run,
+            targets: Vec::new(),
+        }),
+        max_delete: cli.max_delete,
+        deleted_bytes: Cell::new(0),
+        left_by_cap: RefCell::new(Vec::new()),
+        permission_denied: RefCell::new(Vec::new()),
+        syslog: cli.syslog,
+        run: RefCell::new(resumed_run.unwrap_or_else(new_run_state)),
+        pending_action: RefCell::new(None),
+        target_stats: CleanupStats::new(),
+        scan_mark: Cell::new(Instant::now()),
+        budgets: config.budgets(),
+        extra_paths: config.extra_paths(),
+        // --only replaces the config's selection outright; --skip adds to its skip list
+        skip: if only.is_empty() { config.skip.into_iter().chain(skip).collect() } else { skip },
+        only: if only.is_empty() { config.only } else { only.clone() },
+        // Naming an opt-in target in --only asks for it as much as its own flag does
+        opted_in: [("unquarantine", cli.unquarantine), ("shell_history", cli.shell_history),
+                ("cargo_targets", cli.cargo_targets), ("ds_store", cli.ds_store)].into_iter()
+            .filter_map(|(id, flag)| (flag || only.iter().any(|only| only == id)).then_some(id))
+            .collect(),
+        keep_recents: config.keep_recents,
+        keep_device_support: config.keep_device_support.unwrap_or(tasks::device_support::DEVICE_SUPPORT_KEEP),
+        // Unattended runs stay away from data that can't be brought back unless asked to
+        max_risk: cli.max_risk
+            .or(config.max_risk)
+            .unwrap_or(if safe_mode { Risk::Safe } else if cli.force { Risk::Moderate } else { Risk::Aggressive })
+            .min(backup_risk_cap.unwrap_or(Risk::Aggressive)),
+        current_risk: Cell::new(None),
+        removed_paths: RefCell::new(Vec::new()),
+        trimming: Cell::new(false),
+        max_age_days: config.max_age_days,
+        to_trash: if cli.to_trash { Some(true) } else if cli.no_trash { Some(false) } else { None },
+        trashed_bytes: Cell::new(0),
+        recoverable: cli.recoverable || safe_mode,
+        staged_bytes: Cell::new(0),
+        backup_command: config.backup_command,
+        backup_failed: Cell::new(false),
+        backed_up: RefCell::new(std::collections::HashSet::new()),
+        missing_tool: RefCell::new(None),
     };
 
+    if ctx.syslog {
+        // SAFETY: the identifier is a static C string, as openlog requires
+        unsafe { libc::openlog(c"maccleanup-rust".as_ptr(), libc::LOG_PID, libc::LOG_USER) };
+    }
+
     // If RAM only mode, just clean RAM and exit
-    if cli.ram_only {
+    if cli.ram_only || purge_only {
         println!("{}", "🧠 RAM Cleanup Mode".bold());
         println!("{}", "─".repeat(40).dimmed());
-        clean_ram(&ctx);
+        tasks::ram::clean_ram(&ctx);
+        return;
+    }
+
+    if privacy_only {
+        run_privacy(&ctx);
+        return;
+    }
+
+    if let Some(pattern) = &clean_app {
+        run_clean_app(&ctx, pattern);
+        return;
+    }
+
+    // Tool-driven targets act on the invoking account, so they're skipped with --user
+    let offered: Vec<&'static dyn CleanupTask> = TASKS.iter().copied()
+        .filter(|task| ctx.wants(task.id())
+            && !(task.uses_invoking_account() && other_user)
+            && (!task.opt_in() || ctx.opted_in.contains(&task.id()))
+            && !cooling.iter().any(|(id, _, _)| id == task.id()))
+        .collect();
+
+    if cli.tui {
+        if !io::stdin().is_terminal() {
+            println!("{} --tui needs a terminal", "✗".red());
+            std::process::exit(1);
+        }
+        let disk_before = get_disk_info_with_purgeable();
+        match tui::run_tui(&ctx, &offered) {
+            Ok(total_stats) => {
+                ctx.finish_run();
+                println!("{}", if ctx.dry_run { "📊 Dry Run Summary:" } else { "📊 Cleanup Statistics:" }.bold().cyan());
+                println!("  {} {}", "Items removed:".bold(), total_stats.files_removed().to_string().yellow());
+                println!("  {} {}", "Reported freed:".bold(), format_size(total_stats.space_freed(), BINARY).green());
+                show_target_results(&ctx, &total_stats);
+                append_history(&ctx.manifest.borrow());
+                let status = if ctx.backup_failed.get() { "backup-failed" } else { "completed" };
+                let disk_freed = (!ctx.dry_run).then(|| get_disk_info_with_purgeable().freed_since(&disk_before));
+                print_result_line(&ctx, &total_stats, status, disk_freed);
+            }
+            Err(err) => println!("{} The full-screen mode failed: {}", "✗".red(), err),
+        }
         return;
     }
 
     // Get initial disk info
-    let initial_disk = get_disk_info();
+    let initial_disk = get_disk_info_with_purgeable();
     show_disk_status(&initial_disk, "Current Disk Status");
 
     if ctx.dry_run {
@@ -147,179 +665,136 @@ fn main() {
     } else if ctx.interactive {
         println!("\n{}", "💬 Running in INTERACTIVE mode - will ask before actions\n".green());
     }
+    match backup_status {
+        Some(BackupStatus::Stale(days)) => println!("{}",
+            format!("⚠️  Time Machine's last backup is {} days old; Aggressive targets are held back until it runs \
+                (or pass --skip-backup-check)\n", days).red()),
+        Some(BackupStatus::Missing) => println!("{}",
+            "⚠️  No finished Time Machine backup found (is the backup disk connected?); Aggressive targets are held back \
+                (or pass --skip-backup-check)\n".red()),
+        Some(BackupStatus::Recent(_)) | None => {}
+    }
+    if safe_mode {
+        println!("{} Safe mode (run {} of {}): removed items are kept in {} so `restore --last` can \
+            put them back; pass --i-know-what-im-doing to skip\n",
+            "🛟".green(), completed_runs(&history) + 1, SAFE_MODE_RUNS, state_dir().join("quarantine").display());
+    }
+    if ctx.max_risk < Risk::Aggressive {
+        let excluded: Vec<&str> = TARGETS.iter()
+            .filter(|target| target.risk > ctx.max_risk)
+            .map(|target| target.id)
+            .collect();
+        println!("{} Only offering targets up to {} risk; not offered: {}\n",
+            "ℹ".blue(), ctx.max_risk.label(), excluded.join(", ").dimmed());
+    }
+    let source = if cli.path.is_some() {
+        "--path".to_string()
+    } else if cli.only.is_empty() && cli.skip.is_empty() {
+        format!("set in {}", config_path().display())
+    } else {
+        "--only/--skip".to_string()
+    };
+    if !ctx.only.is_empty() {
+        println!("{} {}\n", "ℹ".blue(), format!("Only offering {} ({})", ctx.only.join(", "), source).dimmed());
+    }
+    if !ctx.skip.is_empty() {
+        println!("{} {}\n", "ℹ".blue(), format!("Skipping {} ({})", ctx.skip.join(", "), source).dimmed());
+    }
+    for (id, days_left, reason) in cooling.iter().filter(|(id, _, _)| ctx.wants(id)) {
+        println!("{} {}\n", "ℹ".blue(), format!("Skipping {} for {} more days after it failed: {} (--retry-failed to try now)",
+            id, days_left, reason).dimmed());
+    }
 
-    let mut total_stats = CleanupStats::new();
+    let total_stats = CleanupStats::new();
 
-    // Show menu first in interactive mode
-    if ctx.interactive && !ctx.dry_run {
-        if !show_menu() {
+    let is_resumed = !ctx.run.borrow().answers.is_empty();
+    if !ctx.dry_run && !is_resumed {
+        let run_id = ctx.run.borrow().id.clone();
+        println!("{} {} {}", "🆔 Run".dimmed(), run_id,
+            format!("(if interrupted: maccleanup-rust resume {})", run_id).dimmed());
+    }
+
+    // Catch targets that would fail halfway before anything is deleted
+    let access_problems = probe_targets(&offered);
+
+    // Show menu first in interactive mode, unless a few targets were picked already
+    if ctx.interactive && !ctx.dry_run && !is_resumed && ctx.only.is_empty() {
+        if !show_menu(&read_history(), &offered, &access_problems) {
             println!("\n{}", "Cleanup cancelled.".yellow());
+            print_result_line(&ctx, &total_stats, "cancelled", Some(0));
             return;
         }
+    } else {
+        show_access_problems(&access_problems);
     }
 
     // Calculate total potential cleanup size
     println!("\n{}", "📊 Calculating cleanup potential...".bold().cyan());
-    let total_potential = calculate_total_cleanup_size();
-    println!("{}", format!("  Total potential cleanup: {}", 
-        format_size(total_potential, BINARY).bold().yellow()));
-    println!();
-
-    // System Caches
-    println!("{}", "📁 System & User Caches".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let cache_size = estimate_cache_size();
-    ctx.log_info(&format!("Estimated size: {}", format_size(cache_size, BINARY).red()));
-    show_space_preview(cache_size);
-    
-    if ctx.should_proceed("Clean system and user caches?", 
-        Some(format!("This will free approximately {}", format_size(cache_size, BINARY)))) {
-        total_stats.add(&clean_caches(&ctx));
-    }
-
-    // Logs
-    println!("\n{}", "📝 System Logs".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let log_size = estimate_logs_size();
-    ctx.log_info(&format!("Estimated size: {}", format_size(log_size, BINARY).red()));
-    show_space_preview(log_size);
-    
-    if ctx.should_proceed("Clean system logs older than 7 days?",
-        Some(format!("This will free approximately {}", format_size(log_size, BINARY)))) {
-        total_stats.add(&clean_logs(&ctx));
-    }
-
-    // Downloads folder
-    println!("\n{}", "📥 Downloads Folder".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let downloads_size = estimate_old_downloads_size();
-    ctx.log_info(&format!("Old files (30+ days): {}", format_size(downloads_size, BINARY).red()));
-    show_space_preview(downloads_size);
-    
-    if downloads_size > 0 && ctx.should_proceed("Clean files older than 30 days in Downloads?",
-        Some(format!("This will free approximately {}", format_size(downloads_size, BINARY)))) {
-        total_stats.add(&clean_old_downloads(&ctx));
-    }
-
-    // Trash
-    println!("\n{}", "🗑️  Trash".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let trash_size = estimate_trash_size();
-    ctx.log_info(&format!("Current size: {}", format_size(trash_size, BINARY).red()));
-    show_space_preview(trash_size);
-    
-    if trash_size > 0 && ctx.should_proceed("Empty trash?",
-        Some(format!("This will permanently delete {} of files", format_size(trash_size, BINARY)))) {
-        total_stats.add(&empty_trash(&ctx));
+    let total_potential = calculate_total_cleanup_size(&ctx, cli.quick);
+    if cli.quick {
+        println!("  Total potential cleanup: ≈ {} {}",
+            format_size(total_potential, BINARY).bold().yellow(), "(approximate, --quick)".dimmed());
+    } else {
+        println!("  Total potential cleanup: {}", 
+            format_size(total_potential, BINARY).bold().yellow());
     }
+    println!();
 
-    // Xcode derived data
-    if check_xcode_installed() {
-        println!("\n{}", "🛠️  Xcode".bold());
-        println!("{}", "─".repeat(40).dimmed());
-        let xcode_size = estimate_xcode_size();
-        ctx.log_info(&format!("Derived Data & Archives: {}", format_size(xcode_size, BINARY).red()));
-        show_space_preview(xcode_size);
-        
-        if xcode_size > 0 && ctx.should_proceed("Clean Xcode derived data and archives?",
-            Some(format!("This will free approximately {}", format_size(xcode_size, BINARY)))) {
-            total_stats.add(&clean_xcode(&ctx));
+    // Don't let a few GB of caches look like the fix for a disk full of something else
+    if ctx.only.is_empty() && total_potential < initial_disk.used / BIG_TICKET_RATIO {
+        show_big_ticket_items(total_potential, &initial_disk);
+    }
+    ctx.scan_mark.set(Instant::now());
+
+    // Every target in registry order; each shows what it found and asks before cleaning
+    for task in &offered {
+        // Enough space is free already; the rest can wait for another run
+        if let Some(target) = cli.free_target.filter(|_| !ctx.dry_run) {
+            let free = available_space(&home_dir());
+            if free >= target {
+                println!("\n{} {} free, reaching --free-target {}; skipping the remaining targets",
+                    "✓".green(), format_size(free, BINARY), format_size(target, BINARY));
+                break;
+            }
         }
-    }
-
-    // Homebrew cache
-    if check_homebrew_installed() {
-        println!("\n{}", "🍺 Homebrew".bold());
-        println!("{}", "─".repeat(40).dimmed());
-        let brew_size = estimate_homebrew_size();
-        ctx.log_info(&format!("Cache size: {}", format_size(brew_size, BINARY).red()));
-        show_space_preview(brew_size);
-        
-        if ctx.should_proceed("Clean Homebrew cache and outdated formulae?", None) {
-            total_stats.add(&clean_homebrew(&ctx));
+        ctx.missing_tool.take();
+        total_stats.add(&task.run(&ctx));
+        if ctx.backup_failed.get() {
+            break;
+        }
+        if !ctx.dry_run {
+            record_target_outcome(task.id(), target_failure(&ctx, task.id()));
         }
     }
 
-    // Node modules
-    println!("\n{}", "📦 Node Modules".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    find_and_clean_node_modules(&ctx, &mut total_stats);
-
-    // Docker
-    if check_docker_installed() {
-        println!("\n{}", "🐳 Docker".bold());
+    // Items that failed with permission errors get one escalated retry
+    ctx.current_risk.set(None);
+    let denied = ctx.permission_denied.take();
+    let denied: Vec<String> = denied.into_iter().filter(|path| Path::new(path).exists()).collect();
+    if !denied.is_empty() && !ctx.backup_failed.get() {
+        println!("\n{}", "🔐 Permission Denied".bold());
         println!("{}", "─".repeat(40).dimmed());
-        let docker_size = estimate_docker_size();
-        if docker_size > 0 {
-            ctx.log_info(&format!("Estimated unused: {}", format_size(docker_size, BINARY).red()));
-            show_space_preview(docker_size);
+        let denied_size = get_paths_size(&denied);
+        ctx.log_info(&format!("{} items could not be deleted ({})",
+            denied.len(),
+            format_size(denied_size, BINARY).red()));
+        for path in denied.iter().take(5) {
+            println!("    {} {}", "•".dimmed(), path.dimmed());
         }
-        
-        if ctx.should_proceed("Clean Docker unused containers, images and volumes?", None) {
-            clean_docker(&ctx);
+        if denied.len() > 5 {
+            println!("    {} ... and {} more", "•".dimmed(), denied.len() - 5);
         }
-    }
-
-    // Safari
-    println!("\n{}", "🌐 Safari".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let safari_size = estimate_safari_size();
-    ctx.log_info(&format!("Cache & History: {}", format_size(safari_size, BINARY).red()));
-    show_space_preview(safari_size);
-    
-    if safari_size > 0 && ctx.should_proceed("Clean Safari cache and history?",
-        Some(format!("This will free approximately {}", format_size(safari_size, BINARY)))) {
-        total_stats.add(&clean_safari(&ctx));
-    }
 
-    // Chrome Cache
-    println!("\n{}", "🌐 Chrome Cache".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let chrome_size = estimate_chrome_cache_size();
-    ctx.log_info(&format!("Browser cache: {}", format_size(chrome_size, BINARY).red()));
-    show_space_preview(chrome_size);
-    
-    if chrome_size > 0 && ctx.should_proceed("Clean Chrome cache?",
-        Some(format!("This will free approximately {}", format_size(chrome_size, BINARY)))) {
-        total_stats.add(&clean_chrome_cache(&ctx));
-    }
-
-    // Python Cache
-    println!("\n{}", "🐍 Python Cache".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let python_size = estimate_python_cache_size();
-    ctx.log_info(&format!("__pycache__ & .pyc files: {}", format_size(python_size, BINARY).red()));
-    show_space_preview(python_size);
-    
-    if python_size > 0 && ctx.should_proceed("Clean Python cache files?",
-        Some(format!("This will free approximately {}", format_size(python_size, BINARY)))) {
-        total_stats.add(&clean_python_cache(&ctx));
-    }
-
-    // Browser Cookies & Web Data
-    println!("\n{}", "🍪 Browser Cookies & Web Data".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    let cookies_size = estimate_cookies_size();
-    ctx.log_info(&format!("Cookies & web data: {}", format_size(cookies_size, BINARY).red()));
-    show_space_preview(cookies_size);
-    
-    if cookies_size > 0 && ctx.should_proceed("Clean browser cookies and web data?",
-        Some(format!("This will free approximately {}", format_size(cookies_size, BINARY)))) {
-        total_stats.add(&clean_cookies(&ctx));
+        if ctx.should_proceed(&format!("Retry {} items with sudo?", denied.len()),
+            Some(format!("This will free approximately {}", format_size(denied_size, BINARY)))) {
+            total_stats.add(&retry_with_sudo(&ctx, &denied));
+        }
     }
 
-    // RAM Cleanup
-    println!("\n{}", "🧠 RAM Memory".bold());
-    println!("{}", "─".repeat(40).dimmed());
-    show_ram_status();
-    
-    if ctx.should_proceed("Clean RAM memory (purge inactive memory)?", 
-        Some("This will free up inactive RAM".to_string())) {
-        clean_ram(&ctx);
-    }
+    ctx.finish_run();
 
     // Get final disk info
-    let final_disk = get_disk_info();
+    let final_disk = get_disk_info_with_purgeable();
     
     // Final report
     println!("\n{}", "=".repeat(60).green());
@@ -335,20 +810,24 @@ fn main() {
             format!("{} available", format_size(final_disk.available, BINARY)).green()
         );
         
-        let actual_freed = if final_disk.available > initial_disk.available {
-            final_disk.available - initial_disk.available
-        } else {
-            0
-        };
+        let actual_freed = final_disk.freed_since(&initial_disk);
         
         println!("  {} {}", 
             "Actual space freed:".bold(),
             format_size(actual_freed, BINARY).bold().green()
         );
+        if initial_disk.purgeable != final_disk.purgeable {
+            println!("  {} {} → {}",
+                "Purgeable:".bold(),
+                format_size(initial_disk.purgeable, BINARY),
+                format_size(final_disk.purgeable, BINARY));
+        }
         
         println!("\n{}", "📊 Cleanup Statistics:".bold().cyan());
-        println!("  {} {}", "Files removed:".bold(), total_stats.files_removed.to_string().yellow());
-        println!("  {} {}", "Reported freed:".bold(), format_size(total_stats.space_freed, BINARY).green());
+        println!("  {} {}", "Files removed:".bold(), total_stats.files_removed().to_string().yellow());
+        println!("  {} {}", "Reported freed:".bold(), format_size(total_stats.space_freed(), BINARY).green());
+        show_target_results(&ctx, &total_stats);
+        show_left_by_cap(&ctx);
         
         // Show final disk status
         show_disk_status(&final_disk, "\n📱 Final Disk Status");
@@ -365,1063 +844,31 @@ fn main() {
                 percent_improvement);
         }
     } else {
-        println!("{}", "No files were actually deleted (dry run mode)".dimmed());
+        println!("\n{}", "📊 Dry Run Summary:".bold().cyan());
+        println!("  {} {}", "Items that would be removed:".bold(), total_stats.files_removed().to_string().yellow());
+        println!("  {} {}", "Would free:".bold(), format_size(total_stats.space_freed(), BINARY).green());
+        show_target_results(&ctx, &total_stats);
+        show_left_by_cap(&ctx);
+        println!("\n{}", "No files were actually deleted (dry run mode)".dimmed());
     }
-}
 
-fn get_disk_info() -> DiskInfo {
-    let output = Command::new("df")
-        .args(&["-H", "/"])
-        .output()
-        .expect("Failed to get disk info");
-    
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = output_str.lines().collect();
-    
-    if lines.len() >= 2 {
-        let parts: Vec<&str> = lines[1].split_whitespace().collect();
-        if parts.len() >= 5 {
-            let total = parse_size(parts[1]);
-            let used = parse_size(parts[2]);
-            let available = parse_size(parts[3]);
-            let percent_str = parts[4].trim_end_matches('%');
-            let percent_used = percent_str.parse::<f32>().unwrap_or(0.0);
-            
-            return DiskInfo {
-                total,
-                available,
-                used,
-                percent_used,
-            };
-        }
-    }
-    
-    DiskInfo {
-        total: 0,
-        available: 0,
-        used: 0,
-        percent_used: 0.0,
-    }
-}
-
-fn parse_size(size_str: &str) -> u64 {
-    let size_str = size_str.to_uppercase();
-    let number: f64;
-    let multiplier: u64;
-    
-    if size_str.ends_with("T") {
-        number = size_str.trim_end_matches('T').parse().unwrap_or(0.0);
-        multiplier = 1_099_511_627_776;
-    } else if size_str.ends_with("G") {
-        number = size_str.trim_end_matches('G').parse().unwrap_or(0.0);
-        multiplier = 1_073_741_824;
-    } else if size_str.ends_with("M") {
-        number = size_str.trim_end_matches('M').parse().unwrap_or(0.0);
-        multiplier = 1_048_576;
-    } else if size_str.ends_with("K") {
-        number = size_str.trim_end_matches('K').parse().unwrap_or(0.0);
-        multiplier = 1024;
+    // Cache the estimate for `status`, minus whatever this run actually freed
+    let remaining = if ctx.dry_run {
+        total_potential
     } else {
-        number = size_str.parse().unwrap_or(0.0);
-        multiplier = 1;
-    }
-    
-    (number * multiplier as f64) as u64
-}
-
-fn show_disk_status(disk: &DiskInfo, title: &str) {
-    println!("{}", title.bold().cyan());
-    
-    let used_bar_length = (disk.percent_used / 100.0 * 30.0) as usize;
-    let free_bar_length = 30 - used_bar_length;
-    
-    let bar = format!("{}{}",
-        "█".repeat(used_bar_length).red(),
-        "░".repeat(free_bar_length).dimmed()
-    );
-    
-    println!("  {} [{}] {:.1}%", 
-        "Disk Usage:".bold(),
-        bar,
-        disk.percent_used
-    );
-    
-    println!("  {} {} / {} ({})", 
-        "Space:".bold(),
-        format_size(disk.used, BINARY).red(),
-        format_size(disk.total, BINARY),
-        format!("{} free", format_size(disk.available, BINARY)).green()
-    );
-}
-
-fn show_space_preview(size: u64) {
-    if size > 0 {
-        let disk = get_disk_info();
-        let new_available = disk.available + size;
-        let new_percent_used = if disk.used > size {
-            ((disk.used - size) as f32 / disk.total as f32) * 100.0
-        } else {
-            0.0
-        };
-        
-        println!("  {} {} → {} ({:.1}% → {:.1}%)",
-            "Preview:".dimmed(),
-            format_size(disk.available, BINARY).dimmed(),
-            format_size(new_available, BINARY).green(),
-            disk.percent_used,
-            new_percent_used
-        );
-    }
-}
-
-fn show_ram_status() {
-    let output = Command::new("vm_stat")
-        .output()
-        .expect("Failed to get RAM info");
-    
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let mut free_pages = 0u64;
-    let mut inactive_pages = 0u64;
-    let mut active_pages = 0u64;
-    let mut wired_pages = 0u64;
-    let mut compressed_pages = 0u64;
-    
-    for line in output_str.lines() {
-        if line.contains("Pages free:") {
-            free_pages = extract_number_from_line(line);
-        } else if line.contains("Pages inactive:") {
-            inactive_pages = extract_number_from_line(line);
-        } else if line.contains("Pages active:") {
-            active_pages = extract_number_from_line(line);
-        } else if line.contains("Pages wired down:") {
-            wired_pages = extract_number_from_line(line);
-        } else if line.contains("Pages occupied by compressor:") {
-            compressed_pages = extract_number_from_line(line);
-        }
-    }
-    
-    let page_size = 4096u64; // 4KB per page on macOS
-    let free_mb = (free_pages * page_size) / 1_048_576;
-    let inactive_mb = (inactive_pages * page_size) / 1_048_576;
-    let active_mb = (active_pages * page_size) / 1_048_576;
-    let wired_mb = (wired_pages * page_size) / 1_048_576;
-    let compressed_mb = (compressed_pages * page_size) / 1_048_576;
-    
-    let total_ram = get_total_ram();
-    let used_mb = active_mb + wired_mb + compressed_mb;
-    let available_mb = free_mb + inactive_mb;
-    
-    println!("  {} {} / {} MB", 
-        "RAM Usage:".bold(),
-        format!("{} MB", used_mb).red(),
-        total_ram
-    );
-    
-    println!("  {} {} MB ({} MB inactive can be freed)",
-        "Available:".bold(),
-        format!("{}", available_mb).green(),
-        inactive_mb
-    );
-}
-
-fn get_total_ram() -> u64 {
-    let output = Command::new("sysctl")
-        .args(&["hw.memsize"])
-        .output()
-        .expect("Failed to get total RAM");
-    
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let parts: Vec<&str> = output_str.trim().split(": ").collect();
-    
-    if parts.len() == 2 {
-        let bytes = parts[1].parse::<u64>().unwrap_or(0);
-        return bytes / 1_048_576; // Convert to MB
-    }
-    
-    8192 // Default to 8GB if can't determine
-}
-
-fn extract_number_from_line(line: &str) -> u64 {
-    line.split_whitespace()
-        .last()
-        .and_then(|s| s.trim_end_matches('.').parse().ok())
-        .unwrap_or(0)
-}
-
-fn clean_ram(ctx: &CleanupContext) {
-    ctx.log_action("Purging inactive memory...");
-    
-    if !ctx.dry_run {
-        println!("  {} This requires sudo password", "ℹ".blue());
-        
-        // Show before state
-        let before_output = Command::new("vm_stat")
-            .output()
-            .expect("Failed to get RAM info");
-        let before_str = String::from_utf8_lossy(&before_output.stdout);
-        let before_inactive = extract_inactive_pages(&before_str);
-        
-        // Run purge command
-        let output = Command::new("sudo")
-            .args(&["purge"])
-            .output();
-        
-        match output {
-            Ok(result) => {
-                if result.status.success() {
-                    // Wait a moment for the purge to complete
-                    thread::sleep(Duration::from_secs(2));
-                    
-                    // Show after state
-                    let after_output = Command::new("vm_stat")
-                        .output()
-                        .expect("Failed to get RAM info");
-                    let after_str = String::from_utf8_lossy(&after_output.stdout);
-                    let after_inactive = extract_inactive_pages(&after_str);
-                    
-                    let freed_pages = if before_inactive > after_inactive {
-                        before_inactive - after_inactive
-                    } else {
-                        before_inactive // Assume all inactive was freed
-                    };
-                    
-                    let freed_mb = (freed_pages * 4096) / 1_048_576;
-                    
-                    ctx.log_success(&format!("RAM purged successfully! Freed approximately {} MB", freed_mb));
-                    
-                    // Show updated RAM status
-                    println!("\n  {} Updated RAM status:", "ℹ".blue());
-                    show_ram_status();
-                } else {
-                    ctx.log_error("Failed to purge RAM - may need sudo privileges");
-                }
-            },
-            Err(_) => {
-                ctx.log_error("Failed to run purge command - sudo may not be available");
-            }
-        }
-    } else {
-        ctx.log_info("Would purge inactive RAM memory");
-    }
-}
-
-fn extract_inactive_pages(vm_stat_output: &str) -> u64 {
-    for line in vm_stat_output.lines() {
-        if line.contains("Pages inactive:") {
-            return extract_number_from_line(line);
-        }
-    }
-    0
-}
-
-fn calculate_total_cleanup_size() -> u64 {
-    let mut total = 0u64;
-    
-    total += estimate_cache_size();
-    total += estimate_logs_size();
-    total += estimate_old_downloads_size();
-    total += estimate_trash_size();
-    
-    if check_xcode_installed() {
-        total += estimate_xcode_size();
-    }
-    
-    if check_homebrew_installed() {
-        total += estimate_homebrew_size();
-    }
-    
-    if check_docker_installed() {
-        total += estimate_docker_size();
-    }
-    
-    total += estimate_safari_size();
-    total += estimate_python_cache_size();
-    total += estimate_chrome_cache_size();
-    
-    total
-}
-
-fn estimate_homebrew_size() -> u64 {
-    let brew_cache = "/Library/Caches/Homebrew";
-    let user_brew_cache = format!("{}/Library/Caches/Homebrew", 
-        env::var("HOME").unwrap_or_else(|_| String::from("/")));
-    
-    let mut size = 0;
-    if Path::new(brew_cache).exists() {
-        size += get_directory_size(brew_cache);
-    }
-    if Path::new(&user_brew_cache).exists() {
-        size += get_directory_size(&user_brew_cache);
-    }
-    
-    size
-}
-
-fn estimate_docker_size() -> u64 {
-    // This is an estimate - actual size can be determined by docker system df
-    if let Ok(output) = Command::new("docker")
-        .args(&["system", "df"])
-        .output() {
-        if output.status.success() {
-            // Parse docker system df output
-            // This is simplified - you might want to parse more accurately
-            return 1_073_741_824; // Return 1GB as estimate
-        }
-    }
-    0
-}
-
-fn estimate_safari_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let safari_paths = vec![
-        format!("{}/Library/Caches/com.apple.Safari", home),
-        format!("{}/Library/Safari/History.db", home),
-        format!("{}/Library/Safari/TopSites.plist", home),
-        format!("{}/Library/Caches/com.apple.WebKit.PluginProcess", home),
-    ];
-    
-    let mut total = 0;
-    for path in safari_paths {
-        if Path::new(&path).exists() {
-            if Path::new(&path).is_dir() {
-                total += get_directory_size(&path);
-            } else if let Ok(metadata) = fs::metadata(&path) {
-                total += metadata.len();
-            }
-        }
-    }
-    total
-}
-
-fn estimate_chrome_cache_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let chrome_paths = vec![
-        format!("{}/Library/Caches/Google/Chrome", home),
-        format!("{}/Library/Caches/com.google.Chrome", home),
-    ];
-    
-    let mut total = 0;
-    for path in chrome_paths {
-        if Path::new(&path).exists() {
-            total += get_directory_size(&path);
-        }
-    }
-    total
-}
-
-fn estimate_python_cache_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let search_paths = vec![
-        format!("{}/Desktop", home),
-        format!("{}/Documents", home),
-        format!("{}/Developer", home),
-        format!("{}/Projects", home),
-    ];
-    
-    let mut total = 0;
-    for search_path in search_paths {
-        if Path::new(&search_path).exists() {
-            total += find_python_cache_size(&search_path, 0, 4);
-        }
-    }
-    total
-}
-
-fn estimate_cookies_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_default();
-    let paths = vec![
-        format!("{}/Library/Cookies", home),
-        format!("{}/Library/HTTPStorages", home),
-        format!("{}/Library/WebKit", home),
-        format!("{}/Library/Safari/LocalStorage", home),
-        format!("{}/Library/Safari/Databases", home),
-        format!("{}/Library/Application Support/Google/Chrome/Default/Cookies", home),
-        format!("{}/Library/Application Support/Google/Chrome/Default/Local Storage", home),
-    ];
-    
-    let mut total_size = 0u64;
-    for path in paths {
-        if Path::new(&path).exists() {
-            total_size += get_directory_size(&path);
-        }
-    }
-    total_size
-}
-
-fn show_menu() -> bool {
-    println!("\n{}", "This tool will clean the following:".bold());
-    println!("  • System and user caches");
-    println!("  • Old system logs (7+ days)");
-    println!("  • Old downloads (30+ days)");
-    println!("  • Trash bin");
-    println!("  • Xcode derived data (if installed)");
-    println!("  • Homebrew cache (if installed)");
-    println!("  • Unused node_modules");
-    println!("  • Docker unused data (if installed)");
-    println!("  • Safari cache and history");
-    println!("  • Chrome browser cache");
-    println!("  • Python cache files (__pycache__, .pyc)");
-    println!("  • Browser cookies and web data");
-    println!("  • RAM inactive memory");
-    
-    print!("\n{} {} ", "?".cyan(), "Continue with cleanup? (y/N):".yellow().bold());
-    io::stdout().flush().unwrap();
-    
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    
-    input.trim().to_lowercase() == "y" || input.trim().to_lowercase() == "yes"
-}
-
-fn estimate_cache_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let cache_paths = vec![
-        format!("{}/Library/Caches", home),
-        format!("{}/.cache", home),
-        "/Library/Caches".to_string(),
-        "/System/Library/Caches".to_string(),
-    ];
-    
-    let mut total = 0;
-    for path in cache_paths {
-        if Path::new(&path).exists() {
-            total += get_directory_size(&path);
-        }
-    }
-    total
-}
-
-fn estimate_logs_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let log_paths = vec![
-        format!("{}/Library/Logs", home),
-        "/Library/Logs".to_string(),
-        "/var/log".to_string(),
-    ];
-    
-    let mut total = 0;
-    for path in log_paths {
-        if Path::new(&path).exists() {
-            total += get_old_files_size(&path, 7);
-        }
-    }
-    total
-}
-
-fn estimate_old_downloads_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let downloads_path = format!("{}/Downloads", home);
-    
-    if Path::new(&downloads_path).exists() {
-        return get_old_files_size(&downloads_path, 30);
-    }
-    0
-}
-
-fn estimate_trash_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let trash_path = format!("{}/.Trash", home);
-    
-    if Path::new(&trash_path).exists() {
-        return get_directory_size(&trash_path);
-    }
-    0
-}
-
-fn estimate_xcode_size() -> u64 {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let xcode_paths = vec![
-        format!("{}/Library/Developer/Xcode/DerivedData", home),
-        format!("{}/Library/Developer/Xcode/Archives", home),
-    ];
-    
-    let mut total = 0;
-    for path in xcode_paths {
-        if Path::new(&path).exists() {
-            total += get_directory_size(&path);
-        }
-    }
-    total
-}
-
-fn check_xcode_installed() -> bool {
-    Path::new("/Applications/Xcode.app").exists() || 
-    Command::new("xcode-select").arg("-p").output().is_ok()
-}
-
-fn check_homebrew_installed() -> bool {
-    Command::new("brew").arg("--version").output().is_ok()
-}
-
-fn check_docker_installed() -> bool {
-    Command::new("docker").arg("--version").output().is_ok()
-}
-
-fn clean_caches(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    
-    let cache_paths = vec![
-        format!("{}/Library/Caches", home),
-        format!("{}/.cache", home),
-        "/Library/Caches".to_string(),
-        "/System/Library/Caches".to_string(),
-    ];
-
-    for path in cache_paths {
-        if Path::new(&path).exists() {
-            ctx.log_action(&format!("Cleaning {}", path));
-            // Use longer retention for system caches for safety
-            let retention_days = if path.starts_with("/System") || path.starts_with("/Library") { 7 } else { 1 };
-            stats.add(&clean_directory(&path, Some(retention_days), ctx));
-        }
-    }
-
-    ctx.log_success(&format!("Cleaned {} files, freed {}", 
-        stats.files_removed, 
-        format_size(stats.space_freed, BINARY)));
-    stats
-}
-
-fn clean_logs(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    
-    let log_paths = vec![
-        format!("{}/Library/Logs", home),
-        format!("{}/.npm/_logs", home),
-        "/Library/Logs".to_string(),
-        "/var/log".to_string(),
-    ];
-
-    for path in log_paths {
-        if Path::new(&path).exists() {
-            ctx.log_action(&format!("Cleaning {}", path));
-            stats.add(&clean_directory(&path, Some(7), ctx));
-        }
-    }
-
-    ctx.log_success(&format!("Cleaned {} log files, freed {}", 
-        stats.files_removed, 
-        format_size(stats.space_freed, BINARY)));
-    stats
-}
-
-fn clean_old_downloads(ctx: &CleanupContext) -> CleanupStats {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let downloads_path = format!("{}/Downloads", home);
-    
-    if Path::new(&downloads_path).exists() {
-        ctx.log_action("Cleaning old files in Downloads folder");
-        let stats = clean_directory(&downloads_path, Some(30), ctx);
-        ctx.log_success(&format!("Cleaned {} old files, freed {}", 
-            stats.files_removed, 
-            format_size(stats.space_freed, BINARY)));
-        return stats;
-    }
-    
-    CleanupStats::new()
-}
-
-fn empty_trash(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let trash_path = format!("{}/.Trash", home);
-    
-    if Path::new(&trash_path).exists() {
-        ctx.log_action("Emptying trash");
-        stats = clean_directory(&trash_path, None, ctx);
-        ctx.log_success(&format!("Emptied trash, freed {}", 
-            format_size(stats.space_freed, BINARY)));
-    }
-
-    stats
-}
-
-fn clean_xcode(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    
-    let xcode_paths = vec![
-        format!("{}/Library/Developer/Xcode/DerivedData", home),
-        format!("{}/Library/Developer/Xcode/Archives", home),
-        format!("{}/Library/Developer/CoreSimulator/Caches", home),
-    ];
-
-    for path in xcode_paths {
-        if Path::new(&path).exists() {
-            ctx.log_action(&format!("Cleaning {}", path));
-            stats.add(&clean_directory(&path, None, ctx));
-        }
-    }
-
-    ctx.log_success(&format!("Cleaned Xcode data, freed {}", 
-        format_size(stats.space_freed, BINARY)));
-    stats
-}
-
-fn clean_homebrew(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    
-    ctx.log_action("Running brew cleanup");
-    
-    if !ctx.dry_run {
-        // Get size before cleanup
-        let before_size = estimate_homebrew_size();
-        
-        if let Ok(output) = Command::new("brew")
-            .args(&["cleanup", "-s"])
-            .output() {
-            if output.status.success() {
-                // Estimate freed space
-                let after_size = estimate_homebrew_size();
-                stats.space_freed = if before_size > after_size {
-                    before_size - after_size
-                } else {
-                    before_size / 2 // Estimate half was cleaned
-                };
-                
-                ctx.log_success(&format!("Homebrew cleanup completed, freed approximately {}", 
-                    format_size(stats.space_freed, BINARY)));
-            }
-        }
-    }
-
-    stats
-}
-
-fn find_and_clean_node_modules(ctx: &CleanupContext, total_stats: &mut CleanupStats) {
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let search_paths = vec![
-        format!("{}/Desktop", home),
-        format!("{}/Documents", home),
-        format!("{}/Developer", home),
-        format!("{}/Projects", home),
-    ];
-
-    ctx.log_action("Searching for node_modules directories...");
-    let mut found_dirs = Vec::new();
-
-    for search_path in search_paths {
-        if Path::new(&search_path).exists() {
-            find_node_modules_recursive(&search_path, &mut found_dirs, 0, 3);
-        }
-    }
-
-    if !found_dirs.is_empty() {
-        let total_size: u64 = found_dirs.iter()
-            .map(|dir| get_directory_size(dir))
-            .sum();
-        
-        println!("\n  {} Found {} node_modules directories ({})", 
-            "ℹ".blue(), 
-            found_dirs.len().to_string().yellow(),
-            format_size(total_size, BINARY).red());
-        
-        show_space_preview(total_size);
-        
-        // Show first 5 directories
-        for (i, dir) in found_dirs.iter().enumerate() {
-            if i < 5 {
-                let size = get_directory_size(dir);
-                println!("    {} {} ({})", 
-                    "•".dimmed(),
-                    dir.dimmed(), 
-                    format_size(size, BINARY).red());
-            }
-        }
-        if found_dirs.len() > 5 {
-            println!("    {} ... and {} more", "•".dimmed(), found_dirs.len() - 5);
-        }
-        
-        if ctx.should_proceed("Remove all node_modules directories?", 
-            Some(format!("This will free approximately {}", format_size(total_size, BINARY)))) {
-            
-            if !ctx.dry_run {
-                for dir in found_dirs {
-                    if let Ok(_) = fs::remove_dir_all(&dir) {
-                        total_stats.files_removed += 1;
-                    }
-                }
-                total_stats.space_freed += total_size;
-                ctx.log_success(&format!("Removed all node_modules directories, freed {}", 
-                    format_size(total_size, BINARY)));
-            }
-        }
-    } else {
-        ctx.log_info("No node_modules directories found");
-    }
-}
-
-fn find_node_modules_recursive(path: &str, found: &mut Vec<String>, depth: usize, max_depth: usize) {
-    if depth > max_depth {
-        return;
-    }
-
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
-                    
-                    if dir_name == "node_modules" {
-                        found.push(path.to_str().unwrap_or("").to_string());
-                    } else if !dir_name.starts_with('.') && dir_name != "Library" {
-                        find_node_modules_recursive(
-                            path.to_str().unwrap_or(""),
-                            found,
-                            depth + 1,
-                            max_depth
-                        );
-                    }
-                }
-            }
-        }
-    }
-}
-
-fn clean_docker(ctx: &CleanupContext) {
-    ctx.log_action("Running Docker system prune");
-    
-    if !ctx.dry_run {
-        if let Ok(output) = Command::new("docker")
-            .args(&["system", "prune", "-a", "-f", "--volumes"])
-            .output() {
-            if output.status.success() {
-                ctx.log_success("Docker cleanup completed");
-            }
-        }
-    }
-}
-
-fn clean_directory(path: &str, days_old: Option<u64>, ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                
-                // Skip important system files
-                let name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
-                if name == ".DS_Store" || name.starts_with(".") {
-                    continue;
-                }
-                
-                // Check age if days_old is specified
-                if let Some(days) = days_old {
-                    if let Ok(metadata) = entry.metadata() {
-                        if let Ok(modified) = metadata.modified() {
-                            if let Ok(elapsed) = modified.elapsed() {
-                                let days_elapsed = elapsed.as_secs() / 86400;
-                                if days_elapsed < days {
-                                    continue;
-                                }
-                            }
-                        }
-                    }
-                }
-                
-                // Get size before deletion
-                let size = if path.is_dir() {
-                    get_directory_size(path.to_str().unwrap_or(""))
-                } else {
-                    entry.metadata().map(|m| m.len()).unwrap_or(0)
-                };
-                
-                // Try to remove (or simulate in dry run)
-                if !ctx.dry_run {
-                    let removed = if path.is_dir() {
-                        fs::remove_dir_all(&path).is_ok()
-                    } else {
-                        fs::remove_file(&path).is_ok()
-                    };
-                    
-                    if removed {
-                        stats.files_removed += 1;
-                        stats.space_freed += size;
-                        if ctx.verbose {
-                            println!("    {} Removed: {}", "✓".green(), path.display());
-                        }
-                    }
-                } else {
-                    stats.files_removed += 1;
-                    stats.space_freed += size;
-                }
-            }
-        }
-    }
-    
-    stats
-}
-
-fn get_directory_size(path: &str) -> u64 {
-    let mut size = 0;
-    
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    size += get_directory_size(path.to_str().unwrap_or(""));
-                } else {
-                    size += entry.metadata().map(|m| m.len()).unwrap_or(0);
-                }
-            }
-        }
-    }
-    
-    size
-}
-
-fn get_old_files_size(path: &str, days: u64) -> u64 {
-    let mut size = 0;
-    
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                if let Ok(metadata) = entry.metadata() {
-                    if let Ok(modified) = metadata.modified() {
-                        if let Ok(elapsed) = modified.elapsed() {
-                            let days_elapsed = elapsed.as_secs() / 86400;
-                            if days_elapsed >= days {
-                                if entry.path().is_dir() {
-                                    size += get_directory_size(entry.path().to_str().unwrap_or(""));
-                                } else {
-                                    size += metadata.len();
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    size
-}
-
-fn clean_safari(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    
-    let safari_paths = vec![
-        format!("{}/Library/Caches/com.apple.Safari", home),
-        format!("{}/Library/Safari/History.db", home),
-        format!("{}/Library/Safari/TopSites.plist", home),
-        format!("{}/Library/Caches/com.apple.WebKit.PluginProcess", home),
-    ];
-
-    for path in safari_paths {
-        if Path::new(&path).exists() {
-            ctx.log_action(&format!("Cleaning {}", path));
-            
-            let size = if Path::new(&path).is_dir() {
-                get_directory_size(&path)
-            } else if let Ok(metadata) = fs::metadata(&path) {
-                metadata.len()
-            } else {
-                0
-            };
-            
-            if !ctx.dry_run {
-                let removed = if Path::new(&path).is_dir() {
-                    fs::remove_dir_all(&path).is_ok()
-                } else {
-                    fs::remove_file(&path).is_ok()
-                };
-                
-                if removed {
-                    stats.files_removed += 1;
-                    stats.space_freed += size;
-                }
-            } else {
-                stats.files_removed += 1;
-                stats.space_freed += size;
-            }
-        }
-    }
-
-    ctx.log_success(&format!("Cleaned Safari data, freed {}", 
-        format_size(stats.space_freed, BINARY)));
-    stats
-}
-
-fn clean_chrome_cache(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    
-    let chrome_paths = vec![
-        format!("{}/Library/Caches/Google/Chrome", home),
-        format!("{}/Library/Caches/com.google.Chrome", home),
-    ];
-
-    for path in chrome_paths {
-        if Path::new(&path).exists() {
-            ctx.log_action(&format!("Cleaning {}", path));
-            
-            let size = get_directory_size(&path);
-            
-            if !ctx.dry_run {
-                let removed = fs::remove_dir_all(&path).is_ok();
-                if removed {
-                    stats.files_removed += 1;
-                    stats.space_freed += size;
-                }
-            } else {
-                stats.files_removed += 1;
-                stats.space_freed += size;
-            }
-        }
-    }
-
-    ctx.log_success(&format!("Cleaned Chrome cache, freed {}", 
-        format_size(stats.space_freed, BINARY)));
-    stats
-}
-
-fn clean_python_cache(ctx: &CleanupContext) -> CleanupStats {
-    let mut stats = CleanupStats::new();
-    let home = env::var("HOME").unwrap_or_else(|_| String::from("/"));
-    let search_paths = vec![
-        format!("{}/Desktop", home),
-        format!("{}/Documents", home),
-        format!("{}/Developer", home),
-        format!("{}/Projects", home),
-    ];
-
-    ctx.log_action("Searching for Python cache files...");
-    let mut found_files = Vec::new();
-
-    for search_path in search_paths {
-        if Path::new(&search_path).exists() {
-            find_python_cache_files(&search_path, &mut found_files, 0, 4);
-        }
-    }
-
-    if !found_files.is_empty() {
-        let total_size: u64 = found_files.iter()
-            .map(|file| {
-                if let Ok(metadata) = fs::metadata(file) {
-                    metadata.len()
-                } else {
-                    0
-                }
-            })
-            .sum();
-
-        if !ctx.dry_run {
-            for file in found_files {
-                if fs::remove_file(&file).is_ok() || fs::remove_dir_all(&file).is_ok() {
-                    stats.files_removed += 1;
-                }
-            }
-            stats.space_freed = total_size;
-        } else {
-            stats.files_removed = found_files.len();
-            stats.space_freed = total_size;
-        }
-    }
-
-    ctx.log_success(&format!("Cleaned {} Python cache files, freed {}", 
-        stats.files_removed, 
-        format_size(stats.space_freed, BINARY)));
-    stats
-}
+        total_potential.saturating_sub(total_stats.space_freed())
+    };
+    write_scan_cache(remaining, &ctx.manifest.borrow());
+    append_history(&ctx.manifest.borrow());
 
-fn clean_cookies(ctx: &CleanupContext) -> CleanupStats {
-    let home = env::var("HOME").unwrap_or_default();
-    let paths = vec![
-        format!("{}/Library/Cookies", home),
-        format!("{}/Library/HTTPStorages", home),
-        format!("{}/Library/WebKit", home),
-        format!("{}/Library/Safari/LocalStorage", home),
-        format!("{}/Library/Safari/Databases", home),
-        format!("{}/Library/Application Support/Google/Chrome/Default/Cookies", home),
-        format!("{}/Library/Application Support/Google/Chrome/Default/Local Storage", home),
-    ];
-    
-    ctx.log_action("Cleaning browser cookies and web data...");
-    let mut total_stats = CleanupStats::new();
-    
-    for path in paths {
-        if Path::new(&path).exists() {
-            let stats = clean_directory(&path, Some(0), ctx); // Clean all cookies/web data
-            total_stats.add(&stats);
+    if let Some(path) = &cli.manifest {
+        match write_manifest(path, &ctx.manifest.borrow()) {
+            Ok(()) => ctx.log_success(&format!("Manifest written to {}", path.display())),
+            Err(err) => ctx.log_error(&format!("Failed to write manifest {}: {}", path.display(), err)),
         }
     }
-    
-    ctx.log_success(&format!("Cleaned {} cookie/web data files, freed {}", 
-        total_stats.files_removed, 
-        format_size(total_stats.space_freed, BINARY)));
-    
-    total_stats
-}
 
-fn find_python_cache_size(path: &str, depth: usize, max_depth: usize) -> u64 {
-    if depth > max_depth {
-        return 0;
-    }
-
-    let mut size = 0;
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
-                    
-                    if dir_name == "__pycache__" {
-                        size += get_directory_size(path.to_str().unwrap_or(""));
-                    } else if !dir_name.starts_with('.') && dir_name != "Library" {
-                        size += find_python_cache_size(
-                            path.to_str().unwrap_or(""),
-                            depth + 1,
-                            max_depth
-                        );
-                    }
-                } else if let Some(extension) = path.extension() {
-                    if extension == "pyc" || extension == "pyo" {
-                        if let Ok(metadata) = entry.metadata() {
-                            size += metadata.len();
-                        }
-                    }
-                }
-            }
-        }
-    }
-    size
+    let status = if ctx.backup_failed.get() { "backup-failed" } else { "completed" };
+    let disk_freed = (!ctx.dry_run).then(|| final_disk.freed_since(&initial_disk));
+    print_result_line(&ctx, &total_stats, status, disk_freed);
 }
-
-fn find_python_cache_files(path: &str, found: &mut Vec<String>, depth: usize, max_depth: usize) {
-    if depth > max_depth {
-        return;
-    }
-
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
-                    
-                    if dir_name == "__pycache__" {
-                        found.push(path.to_str().unwrap_or("").to_string());
-                    } else if !dir_name.starts_with('.') && dir_name != "Library" {
-                        find_python_cache_files(
-                            path.to_str().unwrap_or(""),
-                            found,
-                            depth + 1,
-                            max_depth
-                        );
-                    }
-                } else if let Some(extension) = path.extension() {
-                    if extension == "pyc" || extension == "pyo" {
-                        found.push(path.to_str().unwrap_or("").to_string());
-                    }
-                }
-            }
-        }
-    }
-}
\ No newline at end of file
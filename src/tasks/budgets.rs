@@ -0,0 +1,93 @@
+use crate::*;
+
+pub struct OverBudget {
+    pub dir: String,
+    pub budget: u64,
+    pub size: u64,
+    /// Oldest entries directly inside `dir` whose removal brings it down to the budget
+    pub excess: Vec<(String, u64)>,
+}
+
+pub fn find_over_budget(dir: &str, budget: u64) -> Option<OverBudget> {
+    let mut entries: Vec<(String, u64, std::time::SystemTime)> = fs::read_dir(dir).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| !entry.file_name().to_str().unwrap_or("").starts_with('.') && !is_ignored(&entry.path()))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            let path = entry.path().to_str().unwrap_or("").to_string();
+            let size = get_paths_size(std::slice::from_ref(&path));
+            Some((path, size, modified))
+        })
+        .collect();
+    entries.sort_by_key(|(_, _, modified)| *modified);
+
+    let total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    let mut remaining = total;
+    let mut excess = Vec::new();
+    for (path, size, _) in entries {
+        if remaining <= budget {
+            break;
+        }
+        remaining -= size;
+        excess.push((path, size));
+    }
+    (!excess.is_empty()).then(|| OverBudget { dir: dir.to_string(), budget, size: total, excess })
+}
+
+fn over_budget(budgets: &[(String, u64)]) -> Vec<OverBudget> {
+    budgets.iter().filter_map(|(dir, budget)| find_over_budget(dir, *budget)).collect()
+}
+
+/// Trim the oldest contents of budgeted directories instead of wiping them.
+pub struct Budgets;
+
+impl CleanupTask for Budgets {
+    fn id(&self) -> &'static str {
+        "budgets"
+    }
+
+    fn estimate(&self) -> u64 {
+        over_budget(&load_config().unwrap_or_default().budgets()).iter()
+            .flat_map(|over| &over.excess)
+            .map(|(_, size)| size)
+            .sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        ctx.trimming.set(true);
+        for over in over_budget(&ctx.budgets) {
+            let paths: Vec<String> = over.excess.into_iter().map(|(path, _)| path).collect();
+            stats.add(&clean_paths(ctx, &paths, "items"));
+        }
+        ctx.trimming.set(false);
+        stats
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        let over_budget = over_budget(&ctx.budgets);
+        if over_budget.is_empty() {
+            return stats;
+        }
+
+        println!("{}", "📏 Size Budgets".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let excess_size: u64 = over_budget.iter().flat_map(|over| &over.excess).map(|(_, size)| size).sum();
+        ctx.record_target("budgets", excess_size);
+
+        for over in &over_budget {
+            let trim_size: u64 = over.excess.iter().map(|(_, size)| size).sum();
+            ctx.log_info(&format!("{}: {} of {} budget", over.dir, format_size(over.size, BINARY).red(), format_size(over.budget, BINARY)));
+            if ctx.should_proceed(&format!("Trim {} to its budget by removing the {} oldest items?", over.dir, over.excess.len()),
+                Some(format!("This will free approximately {}", format_size(trim_size, BINARY)))) {
+                let paths: Vec<String> = over.excess.iter().map(|(path, _)| path.clone()).collect();
+                ctx.trimming.set(true);
+                stats.add(&ctx.run_target(|| clean_paths(ctx, &paths, "items")));
+                ctx.trimming.set(false);
+            }
+        }
+        println!();
+        stats
+    }
+}
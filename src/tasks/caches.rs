@@ -0,0 +1,90 @@
+use crate::*;
+
+pub fn estimate_cache_size() -> u64 {
+    let home = home_dir();
+    let cache_paths = vec![
+        format!("{}/Library/Caches", home),
+        format!("{}/.cache", home),
+        "/Library/Caches".to_string(),
+        "/System/Library/Caches".to_string(),
+    ];
+    
+    let mut total = 0;
+    for path in cache_paths {
+        if Path::new(&path).exists() {
+            let size = estimate_path_size(&path);
+            claim_path("caches", &path, size);
+            total += size;
+        }
+    }
+    total
+}
+
+pub fn clean_caches(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let home = home_dir();
+    
+    let cache_paths = vec![
+        format!("{}/Library/Caches", home),
+        format!("{}/.cache", home),
+        "/Library/Caches".to_string(),
+        "/System/Library/Caches".to_string(),
+    ];
+
+    for path in cache_paths {
+        if Path::new(&path).exists() {
+            ctx.log_action(&format!("Cleaning {}", path));
+            // Use longer retention for system caches for safety
+            let retention_days = if path.starts_with("/System") || path.starts_with("/Library") { 7 } else { 1 };
+            let retention_days = ctx.max_age("caches", retention_days);
+            stats.add(&clean_directory(&path, Some(retention_days), ctx));
+        }
+    }
+
+    ctx.log_success(&format!("Cleaned {} files, freed {}", 
+        stats.files_removed(), 
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct Caches;
+
+impl CleanupTask for Caches {
+    fn id(&self) -> &'static str {
+        "caches"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("/Library/Caches")
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_cache_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_caches(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("{}", "📁 System & User Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let wanted: Vec<&str> = tasks::TASKS.iter().map(|task| task.id()).filter(|id| ctx.wants(id)).collect();
+        let cache_size = estimate_cache_size();
+        let elsewhere = claimed_by_others("caches", &wanted);
+        let cache_size = cache_size.saturating_sub(elsewhere);
+        ctx.record_target("caches", cache_size);
+        ctx.log_info(&format!("Estimated size: {}", format_size(cache_size, BINARY).red()));
+        if elsewhere > 0 {
+            ctx.log_info(&format!("Another {} in these folders is counted under narrower targets such as Chrome or Homebrew",
+                format_size(elsewhere, BINARY)));
+        }
+        show_space_preview(cache_size);
+
+        if ctx.should_proceed("Clean system and user caches?",
+            Some(format!("This will free approximately {}", format_size(cache_size, BINARY)))) {
+            return ctx.run_target(|| clean_caches(ctx));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::*;
+
+/// Existing Cargo download caches as (path, size).
+pub fn find_cargo_caches() -> Vec<(String, u64)> {
+    app_pack("cargo").existing_paths().into_iter()
+        .map(|(path, _)| {
+            let size = get_directory_size(&path);
+            (path, size)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect()
+}
+
+pub fn clean_cargo_caches(ctx: &CleanupContext, caches: &[(String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (path, _) in caches {
+        ctx.log_action(&format!("Cleaning {}", path));
+        stats.add(&clean_directory(path, None, ctx));
+    }
+
+    ctx.log_success(&format!("Cleaned Cargo caches, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Cargo's registry and git download caches.
+pub struct Cargo;
+
+impl CleanupTask for Cargo {
+    fn id(&self) -> &'static str {
+        "cargo"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_cargo_caches().iter().map(|(_, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_cargo_caches(ctx, &find_cargo_caches())
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let caches = find_cargo_caches();
+        if caches.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🦀 Cargo Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let caches_size: u64 = caches.iter().map(|(_, size)| size).sum();
+        ctx.record_target("cargo", caches_size);
+        for (path, size) in &caches {
+            ctx.record_item(path, *size);
+            println!("    {} {} ({})", "•".dimmed(), path, format_size(*size, BINARY).red());
+        }
+        ctx.log_info(&format!("Registry and git caches: {}", format_size(caches_size, BINARY).red()));
+        show_space_preview(caches_size);
+
+        if ctx.should_proceed("Clean Cargo's download caches?",
+            Some("Crates are downloaded again by the next build that needs them".to_string())) {
+            return ctx.run_target(|| clean_cargo_caches(ctx, &caches));
+        }
+        CleanupStats::new()
+    }
+}
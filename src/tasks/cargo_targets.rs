@@ -0,0 +1,132 @@
+use std::time::SystemTime;
+
+use crate::*;
+
+pub struct CargoTarget {
+    pub path: String,
+    pub size: u64,
+    pub last_build: Option<SystemTime>,
+}
+
+/// When a `target/` directory was last built into: the newest of its entries, since every
+/// build rewrites `.rustc_info.json` and the profile folder it used.
+pub fn last_build(target: &Path) -> Option<SystemTime> {
+    fs::read_dir(target).ok()?
+        .flatten()
+        .filter_map(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .max()
+}
+
+/// `target/` directories next to a Cargo.toml, up to `max_depth` levels below `path`.
+pub fn find_cargo_targets_recursive(path: &str, found: &mut Vec<String>, depth: usize, max_depth: usize) {
+    if depth > max_depth {
+        return;
+    }
+
+    let is_crate = Path::new(path).join("Cargo.toml").is_file();
+    for entry in fs::read_dir(path).into_iter().flat_map(|entries| entries.flatten()) {
+        let path = entry.path();
+        if !path.is_dir() || is_ignored(&path) {
+            continue;
+        }
+        let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+
+        if is_crate && name_is(&path, "target") {
+            found.push(path.to_str().unwrap_or("").to_string());
+        } else if !dir_name.starts_with('.') && !name_is(&path, "Library") && !name_is(&path, "node_modules")
+            && !is_bundle(&path) {
+            find_cargo_targets_recursive(path.to_str().unwrap_or(""), found, depth + 1, max_depth);
+        }
+    }
+}
+
+/// Build directories under the project roots not built into for `days`, largest first.
+pub fn find_stale_cargo_targets(days: u64) -> Vec<CargoTarget> {
+    let mut found = Vec::new();
+    for root in project_roots() {
+        if Path::new(&root).exists() {
+            find_cargo_targets_recursive(&root, &mut found, 0, 3);
+        }
+    }
+
+    let mut targets: Vec<CargoTarget> = found.into_iter()
+        .map(|path| (last_build(Path::new(&path)), path))
+        .filter(|(last_build, _)| last_build
+            .and_then(|time| time.elapsed().ok())
+            .is_none_or(|elapsed| elapsed.as_secs() / 86400 >= days))
+        .map(|(last_build, path)| CargoTarget { size: get_directory_size(&path), path, last_build })
+        .filter(|target| target.size > 0)
+        .collect();
+    targets.sort_by_key(|target| std::cmp::Reverse(target.size));
+    targets
+}
+
+/// Build output of Rust projects nobody has built lately; opt-in.
+pub struct CargoTargets;
+
+impl CleanupTask for CargoTargets {
+    fn id(&self) -> &'static str {
+        "cargo_targets"
+    }
+
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        let days = load_config().unwrap_or_default().max_age("cargo_targets", 30);
+        find_stale_cargo_targets(days).iter().map(|target| target.size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let paths: Vec<String> = find_stale_cargo_targets(ctx.max_age("cargo_targets", 30)).into_iter()
+            .map(|target| target.path)
+            .collect();
+        clean_paths(ctx, &paths, "target directories")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "🦀 Stale Cargo Target Directories".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let days = ctx.max_age("cargo_targets", 30);
+        ctx.log_action("Searching for Cargo target directories...");
+        let targets = find_stale_cargo_targets(days);
+        if targets.is_empty() {
+            ctx.log_info(&format!("No target directories unbuilt for {}+ days", days));
+            return CleanupStats::new();
+        }
+
+        let targets_size: u64 = targets.iter().map(|target| target.size).sum();
+        ctx.record_target("cargo_targets", targets_size);
+        for (i, target) in targets.iter().enumerate() {
+            ctx.record_item(&target.path, target.size);
+            let built = target.last_build
+                .map(|time| chrono::DateTime::<Local>::from(time).format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| String::from("unknown"));
+            println!("    {} {} ({}, last built {})",
+                format!("{:>2}.", i + 1).dimmed(),
+                target.path,
+                format_size(target.size, BINARY).red(),
+                built);
+        }
+        ctx.log_info(&format!("Target directories unbuilt for {}+ days: {}", days, format_size(targets_size, BINARY).red()));
+
+        let chosen: Vec<usize> = if ctx.interactive && !ctx.dry_run {
+            select_items("Select target directories to remove", targets.len())
+        } else {
+            (0..targets.len()).collect()
+        };
+        if chosen.is_empty() {
+            return CleanupStats::new();
+        }
+        let chosen_paths: Vec<String> = chosen.iter().map(|&i| targets[i].path.clone()).collect();
+        let chosen_size: u64 = chosen.iter().map(|&i| targets[i].size).sum();
+        show_space_preview(chosen_size);
+
+        if ctx.should_proceed(&format!("Remove {} target directories?", chosen_paths.len()),
+            Some("The next `cargo build` in those projects starts from scratch".to_string())) {
+            return ctx.run_target(|| clean_paths(ctx, &chosen_paths, "target directories"));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,83 @@
+use crate::*;
+
+/// Chromium-based browser packs in `apps.toml` whose caches this target clears, each asked about on its own.
+pub const CHROMIUM_BROWSERS: &[&str] = &["chrome", "brave", "edge", "arc", "opera", "vivaldi"];
+
+/// Existing caches per browser as (browser name, cache paths, size).
+pub fn find_browser_caches() -> Vec<(&'static str, Vec<String>, u64)> {
+    CHROMIUM_BROWSERS.iter()
+        .map(|id| app_pack(id))
+        .filter_map(|pack| {
+            let paths: Vec<String> = pack.existing_paths().into_iter()
+                .filter(|(_, safety)| *safety == SafetyClass::Cache)
+                .map(|(path, _)| path)
+                .collect();
+            let size = claim_paths_size("chrome", &paths);
+            (size > 0).then_some((pack.name.as_str(), paths, size))
+        })
+        .collect()
+}
+
+pub fn clean_browser_cache(ctx: &CleanupContext, name: &str, paths: &[String]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    // History is left to the privacy sweep
+    for path in paths {
+        ctx.log_action(&format!("Cleaning {} ({} cache)", path, name));
+        let size = get_directory_size(path);
+        if ctx.remove_path(Path::new(path), size) {
+            stats.record_removed(size);
+        }
+    }
+
+    ctx.log_success(&format!("Cleaned {} cache, freed {}", name,
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Caches of Chrome and the other Chromium-based browsers.
+pub struct Chrome;
+
+impl CleanupTask for Chrome {
+    fn id(&self) -> &'static str {
+        "chrome"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_browser_caches().iter().map(|(_, _, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        for (name, paths, _) in find_browser_caches() {
+            stats.add(&clean_browser_cache(ctx, name, &paths));
+        }
+        stats
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let caches = find_browser_caches();
+        if caches.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🌐 Chrome & Chromium Browser Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let caches_size: u64 = caches.iter().map(|(_, _, size)| size).sum();
+        ctx.record_target("chrome", caches_size);
+
+        let stats = CleanupStats::new();
+        for (name, paths, size) in &caches {
+            for path in paths {
+                ctx.record_item(path, get_directory_size(path));
+            }
+            ctx.log_info(&format!("{} cache: {}", name, format_size(*size, BINARY).red()));
+            show_space_preview(*size);
+            if ctx.should_proceed(&format!("Clean the {} cache?", name),
+                Some(format!("This will free approximately {}", format_size(*size, BINARY)))) {
+                stats.add(&ctx.run_target(|| clean_browser_cache(ctx, name, paths)));
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,203 @@
+use crate::*;
+
+/// Folders Chromium uses for regenerable caches, in every profile and Electron app.
+/// Service workers keep their registrations next to CacheStorage, so only that subfolder goes.
+pub const CHROMIUM_CACHE_DIRS: &[&str] = &["Cache", "Code Cache", "GPUCache", "Service Worker/CacheStorage"];
+
+/// Electron apps whose Application Support folder counts as theirs even when Chromium's
+/// marker files aren't next to the caches.
+pub const KNOWN_ELECTRON_APPS: &[&str] = &[
+    "Slack", "discord", "Microsoft Teams", "Notion", "Code", "Figma", "Signal", "WhatsApp", "Obsidian", "Postman",
+];
+
+pub struct ChromiumCache {
+    pub path: String,
+    /// Owning app, or the Application Support folder name when no installed app matches
+    pub app: String,
+    pub size: u64,
+}
+
+/// Chromium-style cache folders under ~/Library/Application Support, matched to their apps.
+pub fn find_chromium_caches() -> Vec<ChromiumCache> {
+    let support = format!("{}/Library/Application Support", home_dir());
+    let apps = installed_app_names();
+    let mut caches = Vec::new();
+
+    let mut walker = WalkDir::new(&support).min_depth(1).max_depth(4).into_iter();
+    while let Some(Ok(entry)) = walker.next() {
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_str().unwrap_or("");
+        if name.starts_with('.') || is_bundle(entry.path()) || is_ignored(entry.path()) {
+            walker.skip_current_dir();
+            continue;
+        }
+        let cache_dir = CHROMIUM_CACHE_DIRS.iter()
+            .find(|cache| name_is(entry.path(), cache.split('/').next().unwrap_or(cache)));
+        let Some(cache_dir) = cache_dir.filter(|_| entry.depth() > 1) else { continue };
+        walker.skip_current_dir();
+
+        // "Slack", or "Google/Chrome" for apps filed under their vendor
+        let parent = entry.path().parent().unwrap_or(Path::new("/"));
+        let relative = parent.strip_prefix(&support).unwrap_or(parent);
+        let folders: Vec<&str> = relative.components().filter_map(|component| component.as_os_str().to_str()).collect();
+
+        // Every Chromium profile and Electron app keeps one of these next to its caches
+        let known = folders.first().is_some_and(|first| KNOWN_ELECTRON_APPS.iter().any(|app| app.eq_ignore_ascii_case(first)));
+        if !known && !parent.join("Local State").exists() && !parent.join("Preferences").exists() {
+            continue;
+        }
+
+        let path = parent.join(cache_dir);
+        if !path.is_dir() {
+            continue;
+        }
+        let path = path.to_str().unwrap_or("").to_string();
+        let size = get_directory_size(&path);
+        if size == 0 {
+            continue;
+        }
+        let candidates = [folders.first().map(|first| first.to_string()), folders.get(..2).map(|pair| pair.join(" "))];
+        let app = apps.iter()
+            .find(|(names, _)| names.iter().any(|name| candidates.iter().flatten().any(|candidate| name.eq_ignore_ascii_case(candidate))))
+            .map(|(_, display)| display.clone())
+            .unwrap_or_else(|| format!("{} (no matching app installed)", relative.display()));
+        caches.push(ChromiumCache { path, app, size });
+    }
+
+    caches.sort_by_key(|cache| std::cmp::Reverse(cache.size));
+    caches
+}
+
+/// Installed apps as (names their data folders may use, display name).
+pub fn installed_app_names() -> Vec<(Vec<String>, String)> {
+    let user_apps = format!("{}/Applications", home_dir());
+    let mut bundles: Vec<PathBuf> = ["/Applications", user_apps.as_str()].iter()
+        .flat_map(|dir| fs::read_dir(dir).into_iter().flat_map(|entries| entries.flatten()))
+        .map(|entry| entry.path())
+        .filter(|path| extension_is(path, &["app"]))
+        .collect();
+
+    // App Store apps can sit outside the Applications folders; ones Spotlight can't place
+    // are still known by name, so their data isn't taken for a leftover
+    let mut unplaced = Vec::new();
+    for (adam_id, name) in mas_apps() {
+        match mas_app_path(&adam_id) {
+            Some(path) if !bundles.contains(&path) => bundles.push(path),
+            Some(_) => {}
+            None => unplaced.push((vec![name.clone()], name)),
+        }
+    }
+
+    bundles.iter()
+        .map(|bundle| {
+            let stem = bundle.file_stem().and_then(|stem| stem.to_str()).unwrap_or("").to_string();
+            let mut names = vec![stem.clone()];
+            // Folders are usually named after CFBundleName ("Code" for Visual Studio Code),
+            // sometimes after the bundle id
+            let plist = bundle.join("Contents/Info.plist");
+            for key in ["CFBundleName", "CFBundleIdentifier"] {
+                if let Ok(output) = Command::new("plutil").args(["-extract", key, "raw", "-o", "-"]).arg(&plist).output() {
+                    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                    if output.status.success() && !value.is_empty() {
+                        names.push(value);
+                    }
+                }
+            }
+            (names, stem)
+        })
+        .chain(unplaced)
+        .collect()
+}
+
+/// App Store apps from `mas list` as (App Store id, name); empty when mas isn't installed.
+pub fn mas_apps() -> Vec<(String, String)> {
+    let Ok(output) = Command::new("mas").arg("list").output() else { return Vec::new() };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    // "497799835  Xcode  (15.0)"
+    String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| {
+            let (adam_id, rest) = line.trim().split_once(char::is_whitespace)?;
+            let name = rest.rsplit_once('(').map_or(rest, |(name, _)| name).trim();
+            (adam_id.chars().all(|c| c.is_ascii_digit()) && !name.is_empty())
+                .then(|| (adam_id.to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+/// Where an App Store app is installed, found by its App Store id through Spotlight.
+pub fn mas_app_path(adam_id: &str) -> Option<PathBuf> {
+    let output = Command::new("mdfind").arg(format!("kMDItemAppStoreAdamID == {}", adam_id)).output().ok()?;
+    String::from_utf8_lossy(&output.stdout).lines()
+        .map(PathBuf::from)
+        .find(|path| extension_is(path, &["app"]))
+}
+
+/// Electron/Chromium app caches, found by layout plus a short list of well-known apps.
+pub struct ChromiumCaches;
+
+impl CleanupTask for ChromiumCaches {
+    fn id(&self) -> &'static str {
+        "chromium_caches"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_chromium_caches().iter().map(|cache| cache.size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let paths: Vec<String> = find_chromium_caches().into_iter().map(|cache| cache.path).collect();
+        clean_paths(ctx, &paths, "app cache folders")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let chromium_caches = find_chromium_caches();
+        if chromium_caches.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "⚛️  Electron & Chromium App Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let caches_size: u64 = chromium_caches.iter().map(|cache| cache.size).sum();
+        ctx.record_target("chromium_caches", caches_size);
+
+        let mut by_app: BTreeMap<&str, u64> = BTreeMap::new();
+        for cache in &chromium_caches {
+            ctx.record_item(&cache.path, cache.size);
+            *by_app.entry(&cache.app).or_default() += cache.size;
+        }
+        let mut by_app: Vec<(&str, u64)> = by_app.into_iter().collect();
+        by_app.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        for (i, (app, size)) in by_app.iter().enumerate() {
+            println!("    {} {} ({})", format!("{:>2}.", i + 1).dimmed(), app, format_size(*size, BINARY).red());
+        }
+        ctx.log_info(&format!("{} cache folders in {} apps: {}",
+            chromium_caches.len(), by_app.len(), format_size(caches_size, BINARY).red()));
+
+        // An app that's busy (a call in Teams, a download in Slack) is kept by leaving it out
+        let chosen: Vec<usize> = if ctx.interactive && !ctx.dry_run {
+            select_items("Select apps whose caches to clear", by_app.len())
+        } else {
+            (0..by_app.len()).collect()
+        };
+        if chosen.is_empty() {
+            return CleanupStats::new();
+        }
+        let chosen_apps: Vec<&str> = chosen.iter().map(|&i| by_app[i].0).collect();
+        let chosen_size: u64 = chosen.iter().map(|&i| by_app[i].1).sum();
+        let paths: Vec<String> = chromium_caches.iter()
+            .filter(|cache| chosen_apps.contains(&cache.app.as_str()))
+            .map(|cache| cache.path.clone())
+            .collect();
+        show_space_preview(chosen_size);
+
+        if ctx.should_proceed(&format!("Clean the caches of {} apps?", chosen_apps.len()),
+            Some("Apps rebuild them on next launch; quit running apps first".to_string())) {
+            return ctx.run_target(|| clean_paths(ctx, &paths, "app cache folders"));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::*;
+
+/// Entries of Apple Configurator's firmware cache with their sizes, largest first.
+/// Kept even after the app is removed, so the cache is checked whether or not it's installed.
+pub fn find_configurator_firmware() -> Vec<(String, u64)> {
+    let mut firmware: Vec<(String, u64)> = app_pack("configurator").existing_paths().iter()
+        .flat_map(|(dir, _)| fs::read_dir(dir).into_iter().flat_map(|entries| entries.flatten()))
+        .filter(|entry| !entry.file_name().to_str().unwrap_or("").starts_with('.') && !is_ignored(&entry.path()))
+        .map(|entry| {
+            let path = entry.path().to_str().unwrap_or("").to_string();
+            let size = get_paths_size(std::slice::from_ref(&path));
+            (path, size)
+        })
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    firmware.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    firmware
+}
+
+/// IPSWs left behind by every restore.
+pub struct Configurator;
+
+impl CleanupTask for Configurator {
+    fn id(&self) -> &'static str {
+        "configurator"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_configurator_firmware().iter().map(|(_, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let paths: Vec<String> = find_configurator_firmware().into_iter().map(|(path, _)| path).collect();
+        clean_paths(ctx, &paths, "firmware files")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let firmware = find_configurator_firmware();
+        if firmware.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "📲 Apple Configurator Firmware".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let firmware_size: u64 = firmware.iter().map(|(_, size)| size).sum();
+        ctx.record_target("configurator", firmware_size);
+        for (path, size) in &firmware {
+            ctx.record_item(path, *size);
+            println!("    {} {} ({})",
+                "•".dimmed(),
+                Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path),
+                format_size(*size, BINARY).red());
+        }
+        ctx.log_info(&format!("Cached restore images: {} (downloaded again when needed)", format_size(firmware_size, BINARY).red()));
+        show_space_preview(firmware_size);
+
+        let paths: Vec<String> = firmware.into_iter().map(|(path, _)| path).collect();
+        if ctx.should_proceed("Remove cached Apple Configurator firmware?",
+            Some(format!("This will free approximately {}", format_size(firmware_size, BINARY)))) {
+            return ctx.run_target(|| clean_paths(ctx, &paths, "firmware files"));
+        }
+        CleanupStats::new()
+    }
+}
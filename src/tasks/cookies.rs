@@ -0,0 +1,117 @@
+use crate::*;
+
+pub fn estimate_cookies_size() -> u64 {
+    let home = home_dir();
+    let paths = vec![
+        format!("{}/Library/Cookies", home),
+        format!("{}/Library/HTTPStorages", home),
+        format!("{}/Library/Safari/LocalStorage", home),
+        format!("{}/Library/Safari/Databases", home),
+        format!("{}/Library/Application Support/Google/Chrome/Default/Cookies", home),
+        format!("{}/Library/Application Support/Google/Chrome/Default/Local Storage", home),
+    ];
+    let paths = paths.into_iter().chain(find_webkit_cache_records());
+    
+    let mut total_size = 0u64;
+    for path in paths {
+        if Path::new(&path).exists() {
+            total_size += estimate_path_size(&path);
+        }
+    }
+    total_size
+}
+
+pub fn clean_cookies(ctx: &CleanupContext) -> CleanupStats {
+    let home = home_dir();
+    let paths = vec![
+        format!("{}/Library/Cookies", home),
+        format!("{}/Library/HTTPStorages", home),
+        format!("{}/Library/Safari/LocalStorage", home),
+        format!("{}/Library/Safari/Databases", home),
+        format!("{}/Library/Application Support/Google/Chrome/Default/Cookies", home),
+        format!("{}/Library/Application Support/Google/Chrome/Default/Local Storage", home),
+    ];
+    // Other apps' WebKit stores only lose their caches, never their cookies or storage
+    let paths = paths.into_iter().chain(find_webkit_cache_records());
+    
+    ctx.log_action("Cleaning browser cookies and web data...");
+    let total_stats = CleanupStats::new();
+    
+    for path in paths {
+        if Path::new(&path).exists() {
+            let stats = clean_directory(&path, Some(0), ctx); // Clean all cookies/web data
+            total_stats.add(&stats);
+        }
+    }
+    
+    ctx.log_success(&format!("Cleaned {} cookie/web data files, freed {}", 
+        total_stats.files_removed(), 
+        format_size(total_stats.space_freed(), BINARY)));
+    
+    total_stats
+}
+
+/// Record types WKWebsiteDataStore treats as caches (disk, fetch and offline app caches).
+/// Cookies, LocalStorage, IndexedDB and service workers hold app state and are left alone.
+pub const WEBKIT_CACHE_RECORDS: &[&str] = &["NetworkCache", "CacheStorage", "OfflineWebApplicationCache", "ApplicationCache"];
+
+/// Cache-type records inside each app's `~/Library/WebKit/<bundle id>` data store.
+pub fn find_webkit_cache_records() -> Vec<String> {
+    let home = home_dir();
+    let mut records = Vec::new();
+
+    for store in fs::read_dir(format!("{}/Library/WebKit", home)).into_iter().flat_map(|entries| entries.flatten()) {
+        if !store.path().is_dir() || is_ignored(&store.path()) {
+            continue;
+        }
+
+        // Newer WebKit partitions records per origin under WebsiteData/Default/<hash>/<hash>
+        let mut walker = WalkDir::new(store.path()).max_depth(6).into_iter();
+        while let Some(Ok(entry)) = walker.next() {
+            let name = entry.file_name().to_str().unwrap_or("");
+            if entry.file_type().is_dir() && WEBKIT_CACHE_RECORDS.contains(&name) {
+                walker.skip_current_dir();
+                if !is_ignored(entry.path()) {
+                    records.push(entry.path().to_str().unwrap_or("").to_string());
+                }
+            }
+        }
+    }
+
+    records
+}
+
+pub struct Cookies;
+
+impl CleanupTask for Cookies {
+    fn id(&self) -> &'static str {
+        "cookies"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Library/Cookies")
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_cookies_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_cookies(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "🍪 Browser Cookies & Web Data".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let cookies_size = estimate_cookies_size();
+        ctx.record_target("cookies", cookies_size);
+        ctx.log_info(&format!("Cookies & web data: {}", format_size(cookies_size, BINARY).red()));
+        show_space_preview(cookies_size);
+
+        if cookies_size > 0 && ctx.should_proceed("Clean browser cookies and web data?",
+            Some(format!("This will free approximately {}", format_size(cookies_size, BINARY)))) {
+            return ctx.run_target(|| clean_cookies(ctx));
+        }
+        CleanupStats::new()
+    }
+}
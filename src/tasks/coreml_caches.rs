@@ -0,0 +1,83 @@
+use crate::*;
+
+/// Compiled `.mlmodelc` bundles in cache folders, as (path, owner, size).
+/// Models shipped inside apps or kept in Application Support are never included.
+pub fn find_coreml_caches() -> Vec<(String, String, u64)> {
+    let home = home_dir();
+    let mut roots = vec![(format!("{}/Library/Caches/com.apple.dt.Xcode", home), String::from("Xcode"))];
+    for container in fs::read_dir(format!("{}/Library/Containers", home)).into_iter().flat_map(|entries| entries.flatten()) {
+        let owner = container.file_name().to_str().unwrap_or("").to_string();
+        roots.push((container.path().join("Data/Library/Caches").to_str().unwrap_or("").to_string(), owner));
+    }
+
+    let mut models = Vec::new();
+    for (root, owner) in roots {
+        let mut walker = WalkDir::new(&root).max_depth(6).into_iter();
+        while let Some(Ok(entry)) = walker.next() {
+            if !entry.file_type().is_dir() || !extension_is(entry.path(), &["mlmodelc"]) {
+                continue;
+            }
+            walker.skip_current_dir();
+            if is_ignored(entry.path()) {
+                continue;
+            }
+            let path = entry.path().to_str().unwrap_or("").to_string();
+            let size = get_directory_size(&path);
+            if size > 0 {
+                claim_path("coreml_caches", &path, size);
+                models.push((path, owner.clone(), size));
+            }
+        }
+    }
+    models
+}
+
+/// CoreML models compiled on device; apps and Xcode compile them again when needed.
+pub struct CoremlCaches;
+
+impl CleanupTask for CoremlCaches {
+    fn id(&self) -> &'static str {
+        "coreml_caches"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_coreml_caches().iter().map(|(_, _, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let paths: Vec<String> = find_coreml_caches().into_iter().map(|(path, _, _)| path).collect();
+        clean_paths(ctx, &paths, "compiled models")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let coreml_caches = find_coreml_caches();
+        if coreml_caches.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🤖 CoreML Model Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let coreml_size: u64 = coreml_caches.iter().map(|(_, _, size)| size).sum();
+        ctx.record_target("coreml_caches", coreml_size);
+
+        let mut by_owner: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
+        for (path, owner, size) in &coreml_caches {
+            ctx.record_item(path, *size);
+            let entry = by_owner.entry(owner).or_default();
+            entry.0 += 1;
+            entry.1 += size;
+        }
+        for (owner, (count, size)) in &by_owner {
+            println!("    {} {} {} ({})", "•".dimmed(), owner, format!("{} models", count).dimmed(), format_size(*size, BINARY).red());
+        }
+        ctx.log_info(&format!("Compiled models: {}", format_size(coreml_size, BINARY).red()));
+        show_space_preview(coreml_size);
+
+        let paths: Vec<String> = coreml_caches.into_iter().map(|(path, _, _)| path).collect();
+        if ctx.should_proceed("Remove cached compiled CoreML models?",
+            Some(format!("This will free approximately {}", format_size(coreml_size, BINARY)))) {
+            return ctx.run_target(|| clean_paths(ctx, &paths, "compiled models"));
+        }
+        CleanupStats::new()
+    }
+}
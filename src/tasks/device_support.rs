@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Symbol folders Xcode copies from each device OS version it meets, per platform.
+pub const DEVICE_SUPPORT_DIRS: &[(&str, &str)] = &[
+    ("iOS", "~/Library/Developer/Xcode/iOS DeviceSupport"),
+    ("watchOS", "~/Library/Developer/Xcode/watchOS DeviceSupport"),
+    ("tvOS", "~/Library/Developer/Xcode/tvOS DeviceSupport"),
+    ("visionOS", "~/Library/Developer/Xcode/visionOS DeviceSupport"),
+];
+
+/// OS versions kept per platform when `keep_device_support` isn't set in the config.
+pub const DEVICE_SUPPORT_KEEP: usize = 2;
+
+pub struct DeviceSupport {
+    pub platform: &'static str,
+    pub path: String,
+    /// e.g. "17.4 (21E219)"
+    pub version: String,
+    pub size: u64,
+}
+
+/// The OS version in a DeviceSupport folder name: "17.4 (21E219)", or
+/// "iPhone15,2 17.4 (21E219)" since Xcode 15, where the model comes first.
+fn os_version(name: &str) -> &str {
+    match name.split_once(' ') {
+        Some((model, rest)) if model.contains(',') => rest,
+        _ => name,
+    }
+}
+
+/// DeviceSupport folders beyond the newest `keep` OS versions of each platform, oldest first.
+/// Folders of one version for several models go together.
+pub fn find_old_device_support(keep: usize) -> Vec<DeviceSupport> {
+    let mut old = Vec::new();
+
+    for (platform, dir) in DEVICE_SUPPORT_DIRS {
+        let mut folders: Vec<(String, String)> = fs::read_dir(expand_home(dir)).into_iter()
+            .flat_map(|entries| entries.flatten())
+            .filter(|entry| entry.path().is_dir() && !is_ignored(&entry.path()))
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                Some((os_version(&name).to_string(), entry.path().to_str()?.to_string()))
+            })
+            .collect();
+        folders.sort_by(|a, b| compare_versions(&b.0, &a.0));
+
+        let mut versions: Vec<&str> = folders.iter().map(|(version, _)| version.as_str()).collect();
+        versions.dedup();
+        let kept = &versions[..keep.min(versions.len())];
+        let mut platform_old: Vec<DeviceSupport> = folders.iter()
+            .filter(|(version, _)| !kept.contains(&version.as_str()))
+            .map(|(version, path)| DeviceSupport {
+                platform,
+                path: path.clone(),
+                version: version.clone(),
+                size: get_directory_size(path),
+            })
+            .collect();
+        platform_old.reverse();
+        old.extend(platform_old);
+    }
+    old
+}
+
+pub fn clean_device_support(ctx: &CleanupContext, folders: &[&DeviceSupport]) -> CleanupStats {
+    let paths: Vec<String> = folders.iter().map(|folder| folder.path.clone()).collect();
+    clean_paths(ctx, &paths, "DeviceSupport folders")
+}
+
+/// Device symbols for OS versions older than the newest few; Xcode copies them again from a
+/// device running one of those versions the next time it's connected.
+pub struct DeviceSupportSymbols;
+
+impl CleanupTask for DeviceSupportSymbols {
+    fn id(&self) -> &'static str {
+        "device_support"
+    }
+
+    fn estimate(&self) -> u64 {
+        let keep = load_config().unwrap_or_default().keep_device_support.unwrap_or(DEVICE_SUPPORT_KEEP);
+        find_old_device_support(keep).iter().map(|folder| folder.size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let folders = find_old_device_support(ctx.keep_device_support);
+        clean_device_support(ctx, &folders.iter().collect::<Vec<_>>())
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let folders = find_old_device_support(ctx.keep_device_support);
+        if folders.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "📲 Device Support Symbols".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let folders_size: u64 = folders.iter().map(|folder| folder.size).sum();
+        ctx.record_target("device_support", folders_size);
+        for (i, folder) in folders.iter().enumerate() {
+            ctx.record_item(&folder.path, folder.size);
+            println!("    {} {} {} ({})",
+                format!("{:>2}.", i + 1).dimmed(),
+                folder.platform,
+                folder.version,
+                format_size(folder.size, BINARY).red());
+        }
+        ctx.log_info(&format!("Symbols for OS versions older than the newest {} per platform: {}",
+            ctx.keep_device_support, format_size(folders_size, BINARY).red()));
+        show_space_preview(folders_size);
+
+        if ctx.should_proceed(&format!("Remove {} old DeviceSupport folders?", folders.len()),
+            Some("Xcode copies symbols again from a device running that version when it's connected".to_string())) {
+            return ctx.run_target(|| clean_device_support(ctx, &folders.iter().collect::<Vec<_>>()));
+        } else if ctx.interactive && !ctx.dry_run {
+            let chosen: Vec<&DeviceSupport> = select_items("Select folders to remove instead", folders.len())
+                .into_iter()
+                .map(|i| &folders[i])
+                .collect();
+            if !chosen.is_empty() {
+                return ctx.run_target(|| clean_device_support(ctx, &chosen));
+            }
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,171 @@
+use crate::*;
+
+/// Sparse images smaller than this aren't worth listing.
+pub const SPARSE_IMAGE_MIN_SIZE: u64 = 1_073_741_824;
+
+pub struct SparseImage {
+    pub path: String,
+    pub size: u64,
+    pub last_mounted: String,
+}
+
+/// Image paths of currently attached disk images, from `hdiutil info`.
+pub fn attached_disk_images() -> Vec<String> {
+    let Ok(output) = Command::new("hdiutil").arg("info").output() else { return Vec::new() };
+    String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| line.strip_prefix("image-path"))
+        .filter_map(|rest| rest.split_once(':'))
+        .map(|(_, path)| path.trim().to_string())
+        .collect()
+}
+
+/// Large `.sparseimage` and `.sparsebundle` files under the home folder.
+pub fn find_sparse_images() -> Vec<SparseImage> {
+    let home = home_dir();
+    let mut images = Vec::new();
+
+    // Library and dot-folders belong to apps; Time Machine bundles live on other volumes
+    let mut walker = WalkDir::new(&home).max_depth(4).into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_str().unwrap_or("");
+            entry.depth() == 0 || !(name.starts_with('.') || (entry.depth() == 1 && name_is(entry.path(), "Library")))
+        });
+    while let Some(Ok(entry)) = walker.next() {
+        let path = entry.path();
+        if !extension_is(path, &["sparseimage", "sparsebundle"]) {
+            if is_bundle(path) {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if path.is_dir() {
+            walker.skip_current_dir();
+        }
+        if is_ignored(path) {
+            continue;
+        }
+
+        let path_str = path.to_str().unwrap_or("").to_string();
+        let size = get_paths_size(std::slice::from_ref(&path_str));
+        if size < SPARSE_IMAGE_MIN_SIZE {
+            continue;
+        }
+
+        // Attaching reads Info.plist (bundles) or the image header, bumping its access time
+        let probe = if extension_is(path, &["sparsebundle"]) { path.join("Info.plist") } else { path.to_path_buf() };
+        let last_mounted = fs::metadata(&probe).and_then(|m| m.accessed()).ok()
+            .map(|time| chrono::DateTime::<Local>::from(time).format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| String::from("unknown"));
+        images.push(SparseImage { path: path_str, size, last_mounted });
+    }
+
+    images.sort_by_key(|image| std::cmp::Reverse(image.size));
+    images
+}
+
+/// Reclaim free space inside sparse images; they never give it back by themselves.
+pub fn compact_sparse_images(ctx: &CleanupContext, paths: &[String]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for path in paths {
+        ctx.log_action(&format!("Compacting {}", path));
+        if ctx.dry_run {
+            continue;
+        }
+
+        let size_before = get_paths_size(std::slice::from_ref(path));
+        let compacted = Command::new("hdiutil")
+            .args(["compact", path, "-batteryallowed"])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if !compacted {
+            ctx.log_error(&format!("Failed to compact {}", path));
+            continue;
+        }
+        ctx.audit(&format!("compacted {}", path));
+
+        let freed = size_before.saturating_sub(get_paths_size(std::slice::from_ref(path)));
+        stats.add_freed(freed);
+        ctx.log_success(&format!("Compacted {}, freed {}", path, format_size(freed, BINARY)));
+    }
+
+    stats
+}
+
+/// Attached DMGs and sparse images that never shrink on their own.
+pub struct DiskImages;
+
+impl CleanupTask for DiskImages {
+    fn id(&self) -> &'static str {
+        "disk_images"
+    }
+
+    fn estimate(&self) -> u64 {
+        0
+    }
+
+    /// Images hold user data and encrypted ones ask for a password, so nothing runs unattended
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        let attached_images = attached_disk_images();
+        let sparse_images = find_sparse_images();
+        if attached_images.is_empty() && sparse_images.is_empty() {
+            return stats;
+        }
+
+        println!("\n{}", "💿 Disk Images".bold());
+        println!("{}", "─".repeat(40).dimmed());
+
+        if !attached_images.is_empty() {
+            ctx.log_info(&format!("{} attached disk images:", attached_images.len()));
+            for image in &attached_images {
+                println!("    {} {}", "•".dimmed(), image.dimmed());
+            }
+        }
+
+        // Attached images can't be compacted or deleted
+        let sparse_images: Vec<SparseImage> = sparse_images.into_iter()
+            .filter(|image| !attached_images.contains(&image.path))
+            .collect();
+        if !sparse_images.is_empty() {
+            let sparse_size: u64 = sparse_images.iter().map(|image| image.size).sum();
+            ctx.record_target("disk_images", sparse_size);
+            for (i, image) in sparse_images.iter().enumerate() {
+                println!("    {} {} ({}, last mounted {})",
+                    format!("{:>2}.", i + 1).dimmed(),
+                    image.path,
+                    format_size(image.size, BINARY).red(),
+                    image.last_mounted);
+            }
+            ctx.log_info(&format!("Sparse images over {}: {}",
+                format_size(SPARSE_IMAGE_MIN_SIZE, BINARY),
+                format_size(sparse_size, BINARY).red()));
+
+            let paths: Vec<String> = sparse_images.iter().map(|image| image.path.clone()).collect();
+            if ctx.should_proceed("Compact sparse images (hdiutil compact)?",
+                Some("Encrypted images will ask for their password".to_string())) {
+                stats.add(&ctx.run_target(|| compact_sparse_images(ctx, &paths)));
+            }
+
+            // Images hold user data, so deletion is only ever an explicit choice
+            if ctx.interactive && !ctx.dry_run {
+                let chosen = select_items("Select disk images to delete", sparse_images.len());
+                if !chosen.is_empty() {
+                    let chosen_paths: Vec<String> = chosen.iter().map(|&i| sparse_images[i].path.clone()).collect();
+                    let chosen_size: u64 = chosen.iter().map(|&i| sparse_images[i].size).sum();
+                    show_space_preview(chosen_size);
+                    if ctx.should_proceed(&format!("Delete {} disk images?", chosen_paths.len()),
+                        Some(format!("This will free approximately {}", format_size(chosen_size, BINARY)))) {
+                        stats.add(&ctx.run_target(|| clean_paths(ctx, &chosen_paths, "disk images")));
+                    }
+                }
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,180 @@
+use crate::*;
+
+pub fn estimate_docker_size() -> u64 {
+    system_df_reclaimable("docker").iter().map(|(_, size)| size).sum()
+}
+
+pub fn check_docker_installed() -> bool {
+    Command::new("docker").arg("--version").output().is_ok()
+}
+
+/// List what `docker system prune -a --volumes` would remove, per category.
+pub fn preview_docker_cleanup(ctx: &CleanupContext) {
+    for (kind, size) in system_df_reclaimable("docker") {
+        if size > 0 {
+            ctx.log_info(&format!("{} reclaimable: {}", kind, format_size(size, BINARY).red()));
+        }
+    }
+
+    let list = |args: &[&str]| -> Vec<String> {
+        Command::new("docker").args(args).output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).lines().map(|l| l.to_string()).collect())
+            .unwrap_or_default()
+    };
+
+    let stopped = list(&["ps", "-a", "--filter", "status=exited", "--filter", "status=created", "--format", "{{.Names}} ({{.Image}})"]);
+    // prune -a keeps only images some container (running or not) still uses
+    let used_images = list(&["ps", "-a", "--format", "{{.ImageID}}"]);
+    let unused_images: Vec<String> = list(&["image", "ls", "--no-trunc", "--format", "{{.ID}} {{.Repository}}:{{.Tag}} ({{.Size}})"])
+        .into_iter()
+        .filter(|line| {
+            let id = line.split_whitespace().next().unwrap_or("");
+            !used_images.iter().any(|used| id.ends_with(used.trim_start_matches("sha256:")))
+        })
+        .map(|line| line.split_once(' ').map(|(_, rest)| rest.to_string()).unwrap_or(line))
+        .collect();
+    let volumes = list(&["volume", "ls", "-q", "--filter", "dangling=true"]);
+
+    for (label, items) in [("Stopped containers", &stopped), ("Unused images", &unused_images), ("Unused volumes", &volumes)] {
+        if items.is_empty() {
+            continue;
+        }
+        println!("    {} {}:", "•".dimmed(), label.bold());
+        for item in items.iter().take(10) {
+            println!("      {}", item.dimmed());
+        }
+        if items.len() > 10 {
+            println!("      ... and {} more", items.len() - 10);
+        }
+    }
+}
+
+pub fn clean_docker(ctx: &CleanupContext) {
+    ctx.log_action("Running Docker system prune");
+    
+    if ctx.delete_cap_reached() {
+        ctx.log_info("Skipping Docker prune: --max-delete cap reached");
+        return;
+    }
+
+    if !ctx.dry_run {
+        if let Ok(output) = Command::new("docker")
+            .args(["system", "prune", "-a", "-f", "--volumes"])
+            .output() {
+            if output.status.success() {
+                ctx.audit("ran docker system prune -a -f --volumes");
+                ctx.log_success("Docker cleanup completed");
+            }
+        }
+    }
+}
+
+/// Docker Desktop's VM disk as (path, logical size, bytes actually allocated).
+pub fn docker_vm_disk() -> Option<(String, u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let home = home_dir();
+    let disk_path = format!("{}/Library/Containers/com.docker.docker/Data/vms/0/data/Docker.raw", home);
+    let metadata = fs::metadata(&disk_path).ok()?;
+
+    // Docker.raw is sparse: Finder shows the logical size, blocks show real usage
+    Some((disk_path, metadata.len(), metadata.blocks() * 512))
+}
+
+pub fn compact_docker_vm(ctx: &CleanupContext, disk_path: &str) -> CleanupStats {
+    let stats = CleanupStats::new();
+    ctx.log_action("Trimming Docker VM disk");
+
+    if ctx.dry_run {
+        return stats;
+    }
+
+    let allocated_before = docker_vm_disk().map(|(_, _, allocated)| allocated).unwrap_or(0);
+    let trimmed = match Command::new("docker")
+        .args(["run", "--rm", "--privileged", "--pid=host", "docker/desktop-reclaim-space"])
+        .status() {
+        Ok(status) => status.success(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            ctx.log_missing_tool("docker");
+            return stats;
+        }
+        Err(_) => false,
+    };
+
+    if !trimmed {
+        ctx.log_error("Failed to trim the VM disk - is Docker Desktop running?");
+        return stats;
+    }
+    ctx.audit(&format!("trimmed {}", disk_path));
+
+    // The host sees freed blocks a few seconds after the trim completes
+    thread::sleep(Duration::from_secs(5));
+    let allocated_after = docker_vm_disk().map(|(_, _, allocated)| allocated).unwrap_or(allocated_before);
+    stats.add_freed(allocated_before.saturating_sub(allocated_after));
+
+    ctx.log_success(&format!("Compacted {}, freed {}",
+        disk_path,
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct Docker;
+
+impl CleanupTask for Docker {
+    fn id(&self) -> &'static str {
+        "docker"
+    }
+
+    fn uses_invoking_account(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        if check_docker_installed() { estimate_docker_size() } else { 0 }
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        if check_docker_installed() {
+            clean_docker(ctx);
+        }
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        if !check_docker_installed() {
+            return stats;
+        }
+
+        println!("\n{}", "🐳 Docker".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let docker_size = estimate_docker_size();
+        ctx.record_target("docker", docker_size);
+        if docker_size > 0 {
+            ctx.log_info(&format!("Estimated unused: {}", format_size(docker_size, BINARY).red()));
+            show_space_preview(docker_size);
+        }
+        
+        if ctx.dry_run {
+            preview_docker_cleanup(ctx);
+        }
+        
+        if ctx.should_proceed("Clean Docker unused containers, images and volumes?", None) {
+            clean_docker(ctx);
+        }
+
+        // Pruning frees space inside the VM, but Docker.raw only shrinks after a trim
+        if let Some((disk_path, logical, allocated)) = docker_vm_disk() {
+            ctx.log_info(&format!("VM disk {}: {} allocated on disk (max {})",
+                disk_path.dimmed(),
+                format_size(allocated, BINARY).red(),
+                format_size(logical, BINARY)));
+
+            if ctx.should_proceed("Reclaim unused space inside the Docker VM disk (runs docker/desktop-reclaim-space)?",
+                Some("Docker Desktop must be running; you can also lower the disk limit in Settings → Resources".to_string())) {
+                stats.add(&ctx.run_target(|| compact_docker_vm(ctx, &disk_path)));
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,72 @@
+use crate::*;
+
+pub fn estimate_old_downloads_size() -> u64 {
+    let home = home_dir();
+    let downloads_path = format!("{}/Downloads", home);
+    
+    if Path::new(&downloads_path).exists() {
+        return get_old_files_size(&downloads_path, load_config().unwrap_or_default().max_age("downloads", 30));
+    }
+    0
+}
+
+pub fn clean_old_downloads(ctx: &CleanupContext) -> CleanupStats {
+    let home = home_dir();
+    let downloads_path = format!("{}/Downloads", home);
+    
+    if Path::new(&downloads_path).exists() {
+        ctx.log_action("Cleaning old files in Downloads folder");
+        let stats = clean_directory(&downloads_path, Some(ctx.max_age("downloads", 30)), ctx);
+        ctx.log_success(&format!("Cleaned {} old files, freed {}", 
+            stats.files_removed(), 
+            format_size(stats.space_freed(), BINARY)));
+        return stats;
+    }
+    
+    CleanupStats::new()
+}
+
+pub struct Downloads;
+
+impl CleanupTask for Downloads {
+    fn id(&self) -> &'static str {
+        "downloads"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Downloads")
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_old_downloads_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_old_downloads(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "📥 Downloads Folder".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let days = ctx.max_age("downloads", 30);
+        let downloads_size = estimate_old_downloads_size();
+        ctx.record_target("downloads", downloads_size);
+        ctx.log_info(&format!("Old files ({}+ days): {}", days, format_size(downloads_size, BINARY).red()));
+        show_space_preview(downloads_size);
+        if ctx.moves_to_trash() {
+            ctx.log_info("They go to the Trash, so anything still needed can be put back (--no-trash deletes them)");
+        }
+
+        if ctx.backup_command.is_some() {
+            for (path, size) in directory_candidates(&format!("{}/Downloads", home_dir()), Some(days)) {
+                ctx.record_item(&path, size);
+            }
+        }
+
+        if downloads_size > 0 && ctx.should_proceed(&format!("Clean files older than {} days in Downloads?", days),
+            Some(format!("This will free approximately {}", format_size(downloads_size, BINARY)))) {
+            return ctx.run_target(|| clean_old_downloads(ctx));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,69 @@
+use crate::*;
+
+/// `.DS_Store` files under the home folder, or the `--path` folder, with their sizes.
+/// ~/Library and app bundles are left out; hidden folders aren't entered.
+pub fn find_ds_store_files() -> Vec<(String, u64)> {
+    let root = SCOPE_PATH.get().cloned().unwrap_or_else(home_dir);
+    let library = format!("{}/Library", home_dir());
+
+    WalkDir::new(&root).into_iter()
+        .filter_entry(|entry| {
+            let path = entry.path();
+            entry.depth() == 0 || !entry.file_type().is_dir()
+                || !(path == Path::new(&library) || is_bundle(path) || is_ignored(path)
+                    || entry.file_name().to_str().is_some_and(|name| name.starts_with('.')))
+        })
+        .flatten()
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == ".DS_Store" && !is_ignored(entry.path()))
+        .map(|entry| {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            (entry.path().to_str().unwrap_or("").to_string(), size)
+        })
+        .collect()
+}
+
+/// Finder's `.DS_Store` view settings files (opt-in), which `clean_directory` never touches.
+pub struct DsStore;
+
+impl CleanupTask for DsStore {
+    fn id(&self) -> &'static str {
+        "ds_store"
+    }
+
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        find_ds_store_files().iter().map(|(_, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let paths: Vec<String> = find_ds_store_files().into_iter().map(|(path, _)| path).collect();
+        clean_paths(ctx, &paths, ".DS_Store files")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let files = find_ds_store_files();
+        if files.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🗂️  .DS_Store Files".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let files_size: u64 = files.iter().map(|(_, size)| size).sum();
+        ctx.record_target("ds_store", files_size);
+        for (path, size) in &files {
+            ctx.record_item(path, *size);
+        }
+        ctx.log_info(&format!("Found {} .DS_Store files ({})", files.len(), format_size(files_size, BINARY).red()));
+        show_space_preview(files_size);
+
+        if ctx.should_proceed(&format!("Delete {} .DS_Store files?", files.len()),
+            Some("Finder recreates them, forgetting folders' custom view settings".to_string())) {
+            let paths: Vec<String> = files.into_iter().map(|(path, _)| path).collect();
+            return ctx.run_target(|| clean_paths(ctx, &paths, ".DS_Store files"));
+        }
+        CleanupStats::new()
+    }
+}
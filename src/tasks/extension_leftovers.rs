@@ -0,0 +1,103 @@
+use crate::*;
+
+/// Chrome's built-in component extensions keep storage without an `Extensions/<id>` folder.
+pub const CHROME_COMPONENT_EXTENSIONS: &[&str] = &[
+    "mhjfbmdgcfjbbpaeojofohoefgiehjai", // PDF viewer
+    "ahfgeienlihckogmohjhadlkjgocpleb", // Web Store
+    "nmmhkkegccagdldgiimedpiccmgmieda", // Payments
+    "pkedcjkdefgpdelpbcmbmeomcjbeemfm", // Cast / Media Router
+    "nkeimhogjdpnpccoofpliimaahmaaome", // Hangouts services
+    "fignfifoniblkonapihmkfakmlgkbkcf", // Cast media
+];
+
+/// Per-extension storage whose extension is no longer installed.
+pub fn find_orphaned_extension_storage() -> Vec<String> {
+    let home = home_dir();
+    let mut orphaned = Vec::new();
+
+    // Chrome: every profile lists installed extensions under Extensions/<id>
+    let chrome_dir = format!("{}/Library/Application Support/Google/Chrome", home);
+    for profile in fs::read_dir(&chrome_dir).into_iter().flat_map(|entries| entries.flatten()) {
+        let profile = profile.path();
+        let Ok(extensions) = fs::read_dir(profile.join("Extensions")) else { continue };
+        let installed: Vec<String> = extensions.flatten()
+            .map(|entry| entry.file_name().to_str().unwrap_or("").to_string())
+            .collect();
+        let is_orphan = |id: &str| {
+            id.len() == 32 && !installed.iter().any(|i| i == id) && !CHROME_COMPONENT_EXTENSIONS.contains(&id)
+        };
+
+        for storage in ["Local Extension Settings", "Sync Extension Settings", "Managed Extension Settings", "Storage/ext"] {
+            for entry in fs::read_dir(profile.join(storage)).into_iter().flat_map(|entries| entries.flatten()) {
+                if is_orphan(entry.file_name().to_str().unwrap_or("")) && !is_ignored(&entry.path()) {
+                    orphaned.push(entry.path().to_str().unwrap_or("").to_string());
+                }
+            }
+        }
+
+        // IndexedDB/chrome-extension_<id>_0.indexeddb.leveldb
+        for entry in fs::read_dir(profile.join("IndexedDB")).into_iter().flat_map(|entries| entries.flatten()) {
+            let name = entry.file_name().to_str().unwrap_or("").to_string();
+            if let Some(id) = name.strip_prefix("chrome-extension_").and_then(|rest| rest.split('_').next()) {
+                if is_orphan(id) && !is_ignored(&entry.path()) {
+                    orphaned.push(entry.path().to_str().unwrap_or("").to_string());
+                }
+            }
+        }
+    }
+
+    // Safari: legacy .safariextz extensions stopped loading in Safari 13, so their data is dead weight
+    let legacy_extensions = format!("{}/Library/Safari/Extensions", home);
+    if Path::new(&legacy_extensions).exists() && !is_ignored(Path::new(&legacy_extensions)) {
+        orphaned.push(legacy_extensions);
+    }
+    for entry in fs::read_dir(format!("{}/Library/Safari/LocalStorage", home)).into_iter().flat_map(|entries| entries.flatten()) {
+        if entry.file_name().to_str().unwrap_or("").starts_with("safari-extension_") && !is_ignored(&entry.path()) {
+            orphaned.push(entry.path().to_str().unwrap_or("").to_string());
+        }
+    }
+
+    orphaned
+}
+
+/// Storage left behind by uninstalled browser extensions.
+pub struct ExtensionLeftovers;
+
+impl CleanupTask for ExtensionLeftovers {
+    fn id(&self) -> &'static str {
+        "extension_leftovers"
+    }
+
+    fn estimate(&self) -> u64 {
+        get_paths_size(&find_orphaned_extension_storage())
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_paths(ctx, &find_orphaned_extension_storage(), "extension storage folders")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let extension_leftovers = find_orphaned_extension_storage();
+        if extension_leftovers.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🧩 Browser Extension Leftovers".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let leftovers_size = get_paths_size(&extension_leftovers);
+        ctx.record_target("extension_leftovers", leftovers_size);
+        for path in &extension_leftovers {
+            ctx.record_item(path, get_paths_size(std::slice::from_ref(path)));
+        }
+        ctx.log_info(&format!("{} storage folders from uninstalled extensions: {}",
+            extension_leftovers.len(),
+            format_size(leftovers_size, BINARY).red()));
+        show_space_preview(leftovers_size);
+
+        if ctx.should_proceed("Remove storage left by uninstalled browser extensions?",
+            Some(format!("This will free approximately {}", format_size(leftovers_size, BINARY)))) {
+            return ctx.run_target(|| clean_paths(ctx, &extension_leftovers, "extension storage folders"));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,72 @@
+use crate::*;
+
+pub fn estimate_extra_paths_size() -> u64 {
+    let config = load_config().unwrap_or_default();
+    let days = config.max_age("extra_paths", 7);
+    config.extra_paths().iter()
+        .filter(|path| Path::new(path).exists())
+        .map(|path| get_old_files_size(path, days))
+        .sum()
+}
+
+pub fn clean_extra_paths(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let days = ctx.max_age("extra_paths", 7);
+
+    for path in &ctx.extra_paths {
+        if Path::new(path).exists() {
+            ctx.log_action(&format!("Cleaning {}", path));
+            stats.add(&clean_directory(path, Some(days), ctx));
+        }
+    }
+
+    ctx.log_success(&format!("Cleaned {} files, freed {}",
+        stats.files_removed(),
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Directories the user added to the config themselves.
+pub struct ExtraPaths;
+
+impl CleanupTask for ExtraPaths {
+    fn id(&self) -> &'static str {
+        "extra_paths"
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_extra_paths_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_extra_paths(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        if ctx.extra_paths.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "📌 Extra Paths".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let days = ctx.max_age("extra_paths", 7);
+        let extra_size = estimate_extra_paths_size();
+        ctx.record_target("extra_paths", extra_size);
+        for path in &ctx.extra_paths {
+            println!("    {} {}", "•".dimmed(), path);
+            if ctx.backup_command.is_some() {
+                for (candidate, size) in directory_candidates(path, Some(days)) {
+                    ctx.record_item(&candidate, size);
+                }
+            }
+        }
+        ctx.log_info(&format!("Old files ({}+ days): {}", days, format_size(extra_size, BINARY).red()));
+        show_space_preview(extra_size);
+
+        if extra_size > 0 && ctx.should_proceed(&format!("Clean files older than {} days in these folders?", days),
+            Some(format!("This will free approximately {}", format_size(extra_size, BINARY)))) {
+            return ctx.run_target(|| clean_extra_paths(ctx));
+        }
+        CleanupStats::new()
+    }
+}
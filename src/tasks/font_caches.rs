@@ -0,0 +1,76 @@
+use crate::*;
+
+/// The per-user font registry `atsutil` rebuilds, in the per-user temporary cache dir.
+pub fn font_registry_dir() -> Option<String> {
+    if TARGET_USER.get().is_some() {
+        return None;
+    }
+    let output = Command::new("getconf").arg("DARWIN_USER_CACHE_DIR").output().ok()?;
+    let cache_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let dir = format!("{}/com.apple.FontRegistry", cache_dir.trim_end_matches('/'));
+    (output.status.success() && Path::new(&dir).exists()).then_some(dir)
+}
+
+/// Drop the font databases with `atsutil databases -remove` and restart the font server so
+/// it rebuilds them from the installed fonts.
+pub fn reset_font_caches(ctx: &CleanupContext) {
+    ctx.log_action("Removing font cache databases...");
+    if ctx.dry_run {
+        ctx.log_info("Would run atsutil databases -remove and restart the font server");
+        return;
+    }
+
+    let removed = match Command::new("atsutil").args(["databases", "-remove"]).output() {
+        Ok(output) => output.status.success(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            ctx.log_missing_tool("atsutil");
+            return;
+        }
+        Err(_) => false,
+    };
+    if !removed {
+        ctx.log_error("Failed to reset font caches with atsutil");
+        return;
+    }
+    let _ = Command::new("atsutil").args(["server", "-shutdown"]).output();
+    let _ = Command::new("atsutil").args(["server", "-ping"]).output();
+    ctx.audit("reset font caches");
+    ctx.log_success("Font caches reset; log out and back in so every app picks up the rebuilt caches");
+}
+
+/// Reset font caches that went corrupt or bloated: garbled text, missing or duplicate fonts.
+pub struct FontCaches;
+
+impl CleanupTask for FontCaches {
+    fn id(&self) -> &'static str {
+        "font_caches"
+    }
+
+    fn estimate(&self) -> u64 {
+        0
+    }
+
+    /// Only reset when confirmed, so unattended runs leave it alone
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        ctx.begin_target("font_caches");
+        println!("\n{}", "🔤 Font Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        if let Some(dir) = font_registry_dir() {
+            ctx.log_info(&format!("Font registry cache: {}", format_size(get_directory_size(&dir), BINARY)));
+        }
+        if !ctx.interactive && !ctx.dry_run {
+            ctx.log_info("Font caches are only reset when confirmed interactively");
+            return CleanupStats::new();
+        }
+
+        if ctx.should_proceed("Reset font caches (atsutil databases -remove)?",
+            Some("Fixes garbled text and missing or duplicate fonts; log out afterwards".to_string())) {
+            reset_font_caches(ctx);
+        }
+        CleanupStats::new()
+    }
+}
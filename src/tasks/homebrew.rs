@@ -0,0 +1,186 @@
+use crate::*;
+
+pub fn estimate_homebrew_size() -> u64 {
+    app_pack("homebrew").existing_paths().iter()
+        .map(|(path, _)| {
+            let size = estimate_path_size(path);
+            claim_path("homebrew", path, size);
+            size
+        })
+        .sum()
+}
+
+pub fn check_homebrew_installed() -> bool {
+    app_pack("homebrew").is_installed()
+}
+
+pub fn clean_homebrew(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    
+    ctx.log_action("Running brew cleanup");
+    
+    if ctx.delete_cap_reached() {
+        ctx.log_info("Skipping brew cleanup: --max-delete cap reached");
+        return stats;
+    }
+
+    if !ctx.dry_run {
+        // Get size before cleanup
+        let before_size = estimate_homebrew_size();
+        
+        if let Ok(output) = Command::new("brew")
+            .args(["cleanup", "-s"])
+            .output() {
+            if output.status.success() {
+                ctx.audit("ran brew cleanup -s");
+                // Estimate freed space
+                let after_size = estimate_homebrew_size();
+                stats.add_freed(if before_size > after_size {
+                    before_size - after_size
+                } else {
+                    before_size / 2 // Estimate half was cleaned
+                });
+                
+                ctx.log_success(&format!("Homebrew cleanup completed, freed approximately {}", 
+                    format_size(stats.space_freed(), BINARY)));
+            }
+        }
+    }
+
+    stats
+}
+
+/// Formulae unused for this long are suggested for removal.
+pub const BREW_STALE_DAYS: u64 = 90;
+
+/// Report-only package hygiene: services whose formula is gone and stale leaf formulae.
+pub fn show_homebrew_advice() {
+    let brew = |args: &[&str]| -> String {
+        Command::new("brew").args(args).output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default()
+    };
+
+    let installed: Vec<String> = brew(&["list", "--formula", "-1"]).lines().map(|l| l.trim().to_string()).collect();
+    let services: Vec<serde_json::Value> = serde_json::from_str(&brew(&["services", "list", "--json"])).unwrap_or_default();
+    let orphaned_services: Vec<&str> = services.iter()
+        .filter_map(|service| service["name"].as_str())
+        .filter(|name| !installed.iter().any(|formula| formula == name))
+        .collect();
+
+    if !orphaned_services.is_empty() {
+        println!("  {} Services whose formula is no longer installed:", "⚠".yellow());
+        for name in &orphaned_services {
+            println!("    {} {} {}", "•".dimmed(), name, "(brew services cleanup)".dimmed());
+        }
+    }
+
+    // Leaves nothing depends on, judged by when their binaries were last run
+    let prefix = brew(&["--prefix"]).trim().to_string();
+    let mut stale_leaves = Vec::new();
+    for leaf in brew(&["leaves"]).lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        let bin_dir = format!("{}/opt/{}/bin", prefix, leaf);
+        let last_used = fs::read_dir(&bin_dir).into_iter()
+            .flat_map(|entries| entries.flatten())
+            .filter_map(|entry| fs::metadata(entry.path()).ok()?.accessed().ok())
+            .max();
+
+        let Some(last_used) = last_used else { continue };
+        let idle_days = last_used.elapsed().map(|e| e.as_secs() / 86400).unwrap_or(0);
+        if idle_days >= BREW_STALE_DAYS {
+            let name = leaf.rsplit('/').next().unwrap_or(leaf);
+            let size = get_directory_size(&format!("{}/Cellar/{}", prefix, name));
+            stale_leaves.push((leaf.to_string(), idle_days, size));
+        }
+    }
+
+    if !stale_leaves.is_empty() {
+        stale_leaves.sort_by_key(|(_, _, size)| std::cmp::Reverse(*size));
+        println!("  {} Formulae nothing depends on and unused for {}+ days:", "💡".yellow(), BREW_STALE_DAYS);
+        for (leaf, idle_days, size) in &stale_leaves {
+            println!("    {} {} ({}, last used {} days ago)", "•".dimmed(), leaf, format_size(*size, BINARY).red(), idle_days);
+        }
+        println!("    {}", "Remove with: brew uninstall <formula> && brew autoremove".dimmed());
+    }
+}
+
+/// Show exactly what `brew cleanup -s` would delete, using its own dry run.
+pub fn preview_homebrew_cleanup(ctx: &CleanupContext) {
+    let Ok(output) = Command::new("brew").args(["cleanup", "-s", "--dry-run"]).output() else {
+        return;
+    };
+    let output_str = String::from_utf8_lossy(&output.stdout);
+
+    // "Would remove: /path/to/file (12.3MB)"
+    let mut items = Vec::new();
+    for line in output_str.lines() {
+        if let Some(rest) = line.strip_prefix("Would remove: ") {
+            let (path, size) = match rest.rsplit_once(" (") {
+                Some((path, size)) => (path, parse_size_arg(size.trim_end_matches(')')).unwrap_or(0)),
+                None => (rest, 0),
+            };
+            ctx.record_item(path, size);
+            items.push((path.to_string(), size));
+        }
+    }
+
+    if items.is_empty() {
+        ctx.log_info("brew cleanup has nothing to remove");
+        return;
+    }
+
+    let total: u64 = items.iter().map(|(_, size)| size).sum();
+    ctx.log_info(&format!("brew cleanup would remove {} items ({}):", items.len(), format_size(total, BINARY).red()));
+    items.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    for (path, size) in items.iter().take(10) {
+        println!("    {} {} ({})", "•".dimmed(), path.dimmed(), format_size(*size, BINARY));
+    }
+    if items.len() > 10 {
+        println!("    {} ... and {} more", "•".dimmed(), items.len() - 10);
+    }
+}
+
+pub struct Homebrew;
+
+impl CleanupTask for Homebrew {
+    fn id(&self) -> &'static str {
+        "homebrew"
+    }
+
+    fn uses_invoking_account(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_homebrew_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_homebrew(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        if !check_homebrew_installed() {
+            return stats;
+        }
+
+        println!("\n{}", "🍺 Homebrew".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let brew_size = estimate_homebrew_size();
+        ctx.record_target("homebrew", brew_size);
+        ctx.log_info(&format!("Cache size: {}", format_size(brew_size, BINARY).red()));
+        show_space_preview(brew_size);
+
+        if ctx.dry_run {
+            preview_homebrew_cleanup(ctx);
+        }
+        
+        if ctx.should_proceed("Clean Homebrew cache and outdated formulae?", None) {
+            stats.add(&ctx.run_target(|| clean_homebrew(ctx)));
+        }
+
+        show_homebrew_advice();
+        stats
+    }
+}
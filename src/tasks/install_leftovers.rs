@@ -0,0 +1,135 @@
+use crate::*;
+
+/// Sandboxes the installer unpacks third-party packages into; failed or interrupted installs
+/// leave theirs behind. The `-SystemSoftware` sibling holds macOS updates and isn't touched.
+pub const INSTALL_SANDBOXES: &str = "/Library/InstallerSandboxes/.PKInstallSandboxManager";
+
+/// App Store downloads; entries still there after `APP_STORE_STALE_DAYS` belong to installs
+/// that never finished.
+pub const APP_STORE_CACHE: &str = "~/Library/Caches/com.apple.appstoreagent";
+pub const APP_STORE_STALE_DAYS: u64 = 7;
+
+pub struct InstallLeftover {
+    pub kind: &'static str,
+    pub path: String,
+    pub size: u64,
+    pub needs_sudo: bool,
+}
+
+/// Where Gatekeeper mounts translocated copies of quarantined apps, in the per-user temp dir.
+pub fn translocation_dir() -> Option<String> {
+    if TARGET_USER.get().is_some() {
+        return None;
+    }
+    let output = Command::new("getconf").arg("DARWIN_USER_TEMP_DIR").output().ok()?;
+    let temp_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let dir = format!("{}/AppTranslocation", temp_dir.trim_end_matches('/'));
+    (output.status.success() && Path::new(&dir).exists()).then_some(dir)
+}
+
+/// Translocation folders whose mount is gone. A mounted one is a running (or just quit)
+/// app's read-only view and is never touched.
+fn stale_translocations() -> Vec<String> {
+    let Some(dir) = translocation_dir() else { return Vec::new() };
+    let Ok(output) = Command::new("mount").output() else { return Vec::new() };
+    let mounts = String::from_utf8_lossy(&output.stdout).to_string();
+
+    fs::read_dir(&dir).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.path().is_dir() && !is_ignored(&entry.path()))
+        // mount lists the /private/var form of the path
+        .filter(|entry| {
+            let canonical = fs::canonicalize(entry.path()).unwrap_or_else(|_| entry.path());
+            !mounts.contains(canonical.to_str().unwrap_or("")) && !mounts.contains(entry.path().to_str().unwrap_or(""))
+        })
+        .filter_map(|entry| entry.path().to_str().map(String::from))
+        .collect()
+}
+
+/// Leftovers of interrupted installs and app translocation, user ones first.
+pub fn find_install_leftovers() -> Vec<InstallLeftover> {
+    let mut leftovers: Vec<InstallLeftover> = stale_translocations().into_iter()
+        .map(|path| {
+            let size = get_directory_size(&path);
+            InstallLeftover { kind: "App Translocation leftover", path, size, needs_sudo: false }
+        })
+        .collect();
+
+    for (path, size) in directory_candidates(&expand_home(APP_STORE_CACHE), Some(APP_STORE_STALE_DAYS)) {
+        claim_path("install_leftovers", &path, size);
+        leftovers.push(InstallLeftover { kind: "Unfinished App Store download", path, size, needs_sudo: false });
+    }
+
+    // An install still running has a sandbox less than a day old
+    for (path, _) in directory_candidates(INSTALL_SANDBOXES, Some(1)) {
+        let size = estimate_path_size(&path);
+        leftovers.push(InstallLeftover { kind: "Failed package install", path, size, needs_sudo: true });
+    }
+
+    leftovers
+}
+
+pub fn clean_install_leftovers(ctx: &CleanupContext, leftovers: &[InstallLeftover]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for leftover in leftovers {
+        ctx.log_action(&format!("Removing {} ({})", leftover.path, leftover.kind));
+        let removed = if leftover.needs_sudo {
+            ctx.remove_path_with_sudo(&leftover.path, leftover.size)
+        } else {
+            ctx.remove_path(Path::new(&leftover.path), leftover.size)
+        };
+        if removed {
+            stats.record_removed(leftover.size);
+        }
+    }
+
+    ctx.log_success(&format!("Removed {} install leftovers, freed {}",
+        stats.files_removed(),
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Stale app translocation folders, App Store downloads that never finished and sandboxes
+/// of failed package installs.
+pub struct InstallLeftovers;
+
+impl CleanupTask for InstallLeftovers {
+    fn id(&self) -> &'static str {
+        "install_leftovers"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_install_leftovers().iter().map(|leftover| leftover.size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_install_leftovers(ctx, &find_install_leftovers())
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let leftovers = find_install_leftovers();
+        if leftovers.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "📦 Install Leftovers".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let leftovers_size: u64 = leftovers.iter().map(|leftover| leftover.size).sum();
+        ctx.record_target("install_leftovers", leftovers_size);
+        for leftover in &leftovers {
+            ctx.record_item(&leftover.path, leftover.size);
+            println!("    {} {} {} ({})", "•".dimmed(), leftover.kind, leftover.path.dimmed(),
+                format_size(leftover.size, BINARY).red());
+        }
+        ctx.log_info(&format!("Install leftovers: {}", format_size(leftovers_size, BINARY).red()));
+        show_space_preview(leftovers_size);
+
+        let sudo = if leftovers.iter().any(|leftover| leftover.needs_sudo) { " (requires sudo)" } else { "" };
+        if ctx.should_proceed(&format!("Remove leftovers of failed installs and app translocation?{}", sudo),
+            Some(format!("This will free approximately {}", format_size(leftovers_size, BINARY)))) {
+            return ctx.run_target(|| clean_install_leftovers(ctx, &leftovers));
+        }
+        CleanupStats::new()
+    }
+}
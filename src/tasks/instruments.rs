@@ -0,0 +1,158 @@
+use crate::*;
+
+/// Instruments' symbol and template caches, rebuilt on the next profiling session.
+pub const INSTRUMENTS_CACHE_DIR: &str = "~/Library/Caches/com.apple.dt.Instruments";
+
+/// Crash and console logs Xcode pulls from connected devices.
+pub const DEVICE_LOGS_DIR: &str = "~/Library/Developer/Xcode/iOS Device Logs";
+
+pub struct TraceDocument {
+    pub path: String,
+    pub size: u64,
+    pub modified: String,
+}
+
+/// Days since `path` was last modified, if known.
+fn days_since_modified(path: &Path) -> Option<u64> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+        .and_then(|time| time.elapsed().ok())
+        .map(|elapsed| elapsed.as_secs() / 86400)
+}
+
+/// Instruments `.trace` documents under the home folder untouched for `days`, via Spotlight.
+pub fn find_trace_documents(days: u64) -> Vec<TraceDocument> {
+    let home = home_dir();
+    let Ok(output) = Command::new("mdfind")
+        .args(["-onlyin", &home, "kMDItemFSName == '*.trace'"])
+        .output() else { return Vec::new() };
+
+    let mut traces: Vec<TraceDocument> = String::from_utf8_lossy(&output.stdout).lines()
+        .map(Path::new)
+        // Skip traces kept inside another bundle, e.g. an Xcode result bundle
+        .filter(|path| extension_is(path, &["trace"]) && !path.ancestors().skip(1).any(is_bundle))
+        .filter(|path| !is_ignored(path))
+        .filter(|path| days_since_modified(path).is_some_and(|age| age >= days))
+        .map(|path| {
+            let path = path.to_str().unwrap_or("").to_string();
+            let size = get_paths_size(std::slice::from_ref(&path));
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()
+                .map(|time| chrono::DateTime::<Local>::from(time).format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| String::from("unknown"));
+            TraceDocument { path, size, modified }
+        })
+        .collect();
+
+    traces.sort_by_key(|trace| std::cmp::Reverse(trace.size));
+    traces
+}
+
+pub fn estimate_instruments_size() -> u64 {
+    let days = load_config().unwrap_or_default().max_age("instruments", 30);
+    let cache_dir = expand_home(INSTRUMENTS_CACHE_DIR);
+    let cache_size = get_directory_size(&cache_dir);
+    claim_path("instruments", &cache_dir, cache_size);
+    cache_size
+        + get_old_files_size(&expand_home(DEVICE_LOGS_DIR), days)
+        + find_trace_documents(days).iter().map(|trace| trace.size).sum::<u64>()
+}
+
+/// Clear the Instruments cache and device logs older than the target's age.
+pub fn clean_instruments_caches(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let days = ctx.max_age("instruments", 30);
+
+    let cache_dir = expand_home(INSTRUMENTS_CACHE_DIR);
+    if Path::new(&cache_dir).exists() {
+        ctx.log_action(&format!("Cleaning {}", cache_dir));
+        stats.add(&clean_directory(&cache_dir, None, ctx));
+    }
+    let logs_dir = expand_home(DEVICE_LOGS_DIR);
+    if Path::new(&logs_dir).exists() {
+        ctx.log_action(&format!("Cleaning {}", logs_dir));
+        stats.add(&clean_directory(&logs_dir, Some(days), ctx));
+    }
+
+    ctx.log_success(&format!("Cleaned Instruments cache and device logs, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Instruments caches, old trace documents and device logs left behind by profiling.
+pub struct Instruments;
+
+impl CleanupTask for Instruments {
+    fn id(&self) -> &'static str {
+        "instruments"
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_instruments_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = clean_instruments_caches(ctx);
+        let paths: Vec<String> = find_trace_documents(ctx.max_age("instruments", 30)).into_iter()
+            .map(|trace| trace.path)
+            .collect();
+        if !paths.is_empty() {
+            stats.add(&clean_paths(ctx, &paths, "trace documents"));
+        }
+        stats
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        let days = ctx.max_age("instruments", 30);
+        let cache_size = get_directory_size(&expand_home(INSTRUMENTS_CACHE_DIR))
+            + get_old_files_size(&expand_home(DEVICE_LOGS_DIR), days);
+        let traces = find_trace_documents(days);
+        if cache_size == 0 && traces.is_empty() {
+            return stats;
+        }
+
+        println!("\n{}", "🔬 Instruments & Device Logs".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let traces_size: u64 = traces.iter().map(|trace| trace.size).sum();
+        ctx.record_target("instruments", cache_size + traces_size);
+
+        if cache_size > 0 {
+            ctx.log_info(&format!("Instruments cache and device logs ({}+ days): {}",
+                days, format_size(cache_size, BINARY).red()));
+            show_space_preview(cache_size);
+            if ctx.should_proceed("Clean the Instruments cache and old device logs?",
+                Some(format!("This will free approximately {}", format_size(cache_size, BINARY)))) {
+                stats.add(&ctx.run_target(|| clean_instruments_caches(ctx)));
+            }
+        }
+
+        if !traces.is_empty() {
+            ctx.log_info(&format!("{} trace documents untouched for {}+ days: {}",
+                traces.len(), days, format_size(traces_size, BINARY).red()));
+            for (i, trace) in traces.iter().enumerate() {
+                ctx.record_item(&trace.path, trace.size);
+                println!("    {} {} ({}, modified {})",
+                    format!("{:>2}.", i + 1).dimmed(),
+                    trace.path,
+                    format_size(trace.size, BINARY).red(),
+                    trace.modified);
+            }
+
+            // Traces are recordings someone made on purpose, so pick them when there's a prompt
+            let chosen: Vec<usize> = if ctx.interactive && !ctx.dry_run {
+                select_items("Select trace documents to delete", traces.len())
+            } else {
+                (0..traces.len()).collect()
+            };
+            if !chosen.is_empty() {
+                let chosen_paths: Vec<String> = chosen.iter().map(|&i| traces[i].path.clone()).collect();
+                let chosen_size: u64 = chosen.iter().map(|&i| traces[i].size).sum();
+                show_space_preview(chosen_size);
+                if ctx.should_proceed(&format!("Delete {} trace documents?", chosen_paths.len()),
+                    Some(format!("This will free approximately {}", format_size(chosen_size, BINARY)))) {
+                    stats.add(&ctx.run_target(|| clean_paths(ctx, &chosen_paths, "trace documents")));
+                }
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,118 @@
+use crate::*;
+
+/// Where Finder (and iTunes before it) keeps iPhone and iPad backups, one folder per backup.
+pub const BACKUP_DIR: &str = "~/Library/Application Support/MobileSync/Backup";
+
+pub struct DeviceBackup {
+    pub path: String,
+    pub device: String,
+    /// Model and OS version, e.g. "iPhone15,2, iOS 17.4"
+    pub product: String,
+    pub date: String,
+    pub size: u64,
+}
+
+/// A string value from a backup's Info.plist.
+fn plist_value(plist: &Path, key: &str) -> Option<String> {
+    let output = Command::new("plutil").args(["-extract", key, "raw", "-o", "-"]).arg(plist).output().ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+/// Device backups, oldest first, described from their Info.plist.
+pub fn find_device_backups() -> Vec<DeviceBackup> {
+    let mut backups: Vec<(Option<chrono::DateTime<Local>>, DeviceBackup)> = Vec::new();
+
+    for entry in fs::read_dir(expand_home(BACKUP_DIR)).into_iter().flat_map(|entries| entries.flatten()) {
+        let path = entry.path();
+        if !path.is_dir() || is_ignored(&path) {
+            continue;
+        }
+        let info = path.join("Info.plist");
+        let device = plist_value(&info, "Device Name").unwrap_or_else(|| String::from("Unknown device"));
+        let product = [plist_value(&info, "Product Type"), plist_value(&info, "Product Version").map(|version| format!("iOS {}", version))]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ");
+        // "2024-05-01T10:15:00Z"; older backups without it fall back to the folder's date
+        let date = plist_value(&info, "Last Backup Date")
+            .and_then(|date| chrono::DateTime::parse_from_rfc3339(&date).ok())
+            .map(|date| date.with_timezone(&Local))
+            .or_else(|| entry.metadata().and_then(|m| m.modified()).ok().map(chrono::DateTime::<Local>::from));
+
+        let path = path.to_str().unwrap_or("").to_string();
+        let size = get_directory_size(&path);
+        let date_label = date.map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_else(|| String::from("unknown"));
+        backups.push((date, DeviceBackup { path, device, product, date: date_label, size }));
+    }
+
+    backups.sort_by_key(|(date, _)| *date);
+    backups.into_iter().map(|(_, backup)| backup).collect()
+}
+
+/// iPhone and iPad backups made by Finder; only the ones picked interactively are deleted.
+pub struct IosBackups;
+
+impl CleanupTask for IosBackups {
+    fn id(&self) -> &'static str {
+        "ios_backups"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Library/Application Support/MobileSync/Backup")
+    }
+
+    fn estimate(&self) -> u64 {
+        find_device_backups().iter().map(|backup| backup.size).sum()
+    }
+
+    /// A backup may be the only copy of a device's data, so none goes without being picked
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let backups = find_device_backups();
+        if backups.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "📱 iPhone & iPad Backups".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let backups_size: u64 = backups.iter().map(|backup| backup.size).sum();
+        ctx.record_target("ios_backups", backups_size);
+        for (i, backup) in backups.iter().enumerate() {
+            ctx.record_item(&backup.path, backup.size);
+            let product = if backup.product.is_empty() { String::new() } else { format!(" ({})", backup.product) };
+            println!("    {} {}{}, backed up {} ({})",
+                format!("{:>2}.", i + 1).dimmed(),
+                backup.device.bold(),
+                product.dimmed(),
+                backup.date,
+                format_size(backup.size, BINARY).red());
+        }
+        ctx.log_info(&format!("{} backups: {}", backups.len(), format_size(backups_size, BINARY).red()));
+
+        let chosen: Vec<usize> = if ctx.interactive && !ctx.dry_run {
+            select_items("Select backups to delete", backups.len())
+        } else if ctx.dry_run {
+            (0..backups.len()).collect()
+        } else {
+            ctx.log_info("Backups are only deleted when selected interactively");
+            Vec::new()
+        };
+        if chosen.is_empty() {
+            return CleanupStats::new();
+        }
+        let chosen_paths: Vec<String> = chosen.iter().map(|&i| backups[i].path.clone()).collect();
+        let chosen_size: u64 = chosen.iter().map(|&i| backups[i].size).sum();
+        show_space_preview(chosen_size);
+
+        if ctx.should_proceed(&format!("Delete {} device backups?", chosen_paths.len()),
+            Some("A deleted backup can't be restored to a device; make sure a newer one exists".to_string())) {
+            return ctx.run_target(|| clean_paths(ctx, &chosen_paths, "device backups"));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,74 @@
+use crate::*;
+
+/// Package manager packs in `apps.toml` whose download caches this target clears.
+pub const JS_PACKAGE_MANAGERS: &[&str] = &["npm", "yarn", "pnpm"];
+
+/// Existing cache folders as (tool name, path, size).
+pub fn find_js_caches() -> Vec<(&'static str, String, u64)> {
+    JS_PACKAGE_MANAGERS.iter()
+        .map(|id| app_pack(id))
+        .flat_map(|pack| pack.existing_paths().into_iter()
+            .filter(|(_, safety)| *safety == SafetyClass::Cache)
+            .map(move |(path, _)| (pack.name.as_str(), path)))
+        .map(|(name, path)| {
+            let size = get_directory_size(&path);
+            claim_path("js_caches", &path, size);
+            (name, path, size)
+        })
+        .filter(|(_, _, size)| *size > 0)
+        .collect()
+}
+
+pub fn clean_js_caches(ctx: &CleanupContext, caches: &[(&str, String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (name, path, _) in caches {
+        ctx.log_action(&format!("Cleaning {} ({} cache)", path, name));
+        stats.add(&clean_directory(path, None, ctx));
+    }
+
+    ctx.log_success(&format!("Cleaned package manager caches, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Download caches of npm, Yarn and pnpm.
+pub struct JsCaches;
+
+impl CleanupTask for JsCaches {
+    fn id(&self) -> &'static str {
+        "js_caches"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_js_caches().iter().map(|(_, _, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_js_caches(ctx, &find_js_caches())
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let caches = find_js_caches();
+        if caches.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "📦 npm, Yarn & pnpm Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let caches_size: u64 = caches.iter().map(|(_, _, size)| size).sum();
+        ctx.record_target("js_caches", caches_size);
+        for (name, path, size) in &caches {
+            ctx.record_item(path, *size);
+            println!("    {} {} {} ({})", "•".dimmed(), name, path.dimmed(), format_size(*size, BINARY).red());
+        }
+        ctx.log_info(&format!("Package manager caches: {}", format_size(caches_size, BINARY).red()));
+        show_space_preview(caches_size);
+
+        if ctx.should_proceed("Clean npm, Yarn and pnpm caches?",
+            Some("Packages are downloaded again on the next install".to_string())) {
+            return ctx.run_target(|| clean_js_caches(ctx, &caches));
+        }
+        CleanupStats::new()
+    }
+}
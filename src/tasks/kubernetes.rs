@@ -0,0 +1,138 @@
+use crate::*;
+
+/// kind and k3d clusters whose node containers aren't running, as (tool, cluster name).
+pub fn find_stopped_clusters() -> Vec<(String, String)> {
+    let mut stopped = Vec::new();
+
+    if let Ok(output) = Command::new("kind").args(["get", "clusters"]).output() {
+        let running = Command::new("docker")
+            .args(["ps", "--format", "{{.Names}}"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default();
+
+        for name in String::from_utf8_lossy(&output.stdout).lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            if !running.lines().any(|container| container == format!("{}-control-plane", name)) {
+                stopped.push(("kind".to_string(), name.to_string()));
+            }
+        }
+    }
+
+    if let Ok(output) = Command::new("k3d").args(["cluster", "list", "-o", "json"]).output() {
+        let clusters: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap_or_default();
+        for cluster in clusters {
+            let name = cluster["name"].as_str().unwrap_or("");
+            if !name.is_empty() && cluster["serversRunning"].as_u64() == Some(0) {
+                stopped.push(("k3d".to_string(), name.to_string()));
+            }
+        }
+    }
+
+    stopped
+}
+
+pub fn delete_stopped_clusters(ctx: &CleanupContext, clusters: &[(String, String)]) {
+    for (tool, name) in clusters {
+        ctx.log_action(&format!("Deleting {} cluster {}", tool, name));
+        if ctx.dry_run {
+            continue;
+        }
+
+        let args: Vec<&str> = match tool.as_str() {
+            "kind" => vec!["delete", "cluster", "--name", name],
+            _ => vec!["cluster", "delete", name],
+        };
+        let deleted = Command::new(tool)
+            .args(&args)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+
+        if deleted {
+            ctx.audit(&format!("deleted {} cluster {}", tool, name));
+            ctx.log_success(&format!("Deleted {} cluster {}", tool, name));
+        } else {
+            ctx.log_error(&format!("Failed to delete {} cluster {}", tool, name));
+        }
+    }
+}
+
+/// Older plugin versions kept in `~/.krew/store/<plugin>/<version>` after upgrades.
+pub fn find_old_krew_versions() -> Vec<String> {
+    let home = home_dir();
+    let store = format!("{}/.krew/store", home);
+    let mut old_versions = Vec::new();
+
+    let Ok(plugins) = fs::read_dir(&store) else { return old_versions };
+    for plugin in plugins.flatten() {
+        let mut versions: Vec<String> = fs::read_dir(plugin.path()).into_iter()
+            .flat_map(|versions| versions.flatten())
+            .map(|version| version.path())
+            .filter(|path| path.is_dir() && !is_ignored(path))
+            .map(|path| path.to_str().unwrap_or("").to_string())
+            .collect();
+        versions.sort_by(|a, b| compare_versions(b, a));
+        old_versions.extend(versions.into_iter().skip(1));
+    }
+
+    old_versions
+}
+
+/// Stopped kind/k3d clusters and superseded krew plugin versions.
+pub struct Kubernetes;
+
+impl CleanupTask for Kubernetes {
+    fn id(&self) -> &'static str {
+        "kubernetes"
+    }
+
+    fn uses_invoking_account(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        get_paths_size(&find_old_krew_versions())
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stopped_clusters = find_stopped_clusters();
+        if !stopped_clusters.is_empty() {
+            delete_stopped_clusters(ctx, &stopped_clusters);
+        }
+        clean_paths(ctx, &find_old_krew_versions(), "krew plugin versions")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        let stopped_clusters = find_stopped_clusters();
+        let krew_old_versions = find_old_krew_versions();
+        if stopped_clusters.is_empty() && krew_old_versions.is_empty() {
+            return stats;
+        }
+
+        println!("\n{}", "☸️  Local Kubernetes".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let krew_size = get_paths_size(&krew_old_versions);
+        ctx.record_target("kubernetes", krew_size);
+
+        if !stopped_clusters.is_empty() {
+            ctx.log_info(&format!("{} stopped test clusters:", stopped_clusters.len()));
+            for (tool, name) in &stopped_clusters {
+                println!("    {} {} {}", "•".dimmed(), name, format!("({})", tool).dimmed());
+            }
+            if ctx.should_proceed("Delete stopped kind/k3d clusters and their node containers?", None) {
+                delete_stopped_clusters(ctx, &stopped_clusters);
+            }
+        }
+
+        if !krew_old_versions.is_empty() {
+            ctx.log_info(&format!("Superseded krew plugin versions: {}", format_size(krew_size, BINARY).red()));
+            show_space_preview(krew_size);
+            if ctx.should_proceed("Remove superseded krew plugin versions?",
+                Some(format!("This will free approximately {}", format_size(krew_size, BINARY)))) {
+                stats.add(&ctx.run_target(|| clean_paths(ctx, &krew_old_versions, "krew plugin versions")));
+            }
+        }
+        stats
+    }
+}
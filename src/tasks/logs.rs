@@ -0,0 +1,78 @@
+use crate::*;
+
+pub fn estimate_logs_size() -> u64 {
+    let home = home_dir();
+    let days = load_config().unwrap_or_default().max_age("logs", 7);
+    let log_paths = vec![
+        format!("{}/Library/Logs", home),
+        "/Library/Logs".to_string(),
+        "/var/log".to_string(),
+    ];
+    
+    let mut total = 0;
+    for path in log_paths {
+        if Path::new(&path).exists() {
+            total += get_old_files_size(&path, days);
+        }
+    }
+    total
+}
+
+pub fn clean_logs(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let home = home_dir();
+    
+    let log_paths = vec![
+        format!("{}/Library/Logs", home),
+        format!("{}/.npm/_logs", home),
+        "/Library/Logs".to_string(),
+        "/var/log".to_string(),
+    ];
+
+    for path in log_paths {
+        if Path::new(&path).exists() {
+            ctx.log_action(&format!("Cleaning {}", path));
+            stats.add(&clean_directory(&path, Some(ctx.max_age("logs", 7)), ctx));
+        }
+    }
+
+    ctx.log_success(&format!("Cleaned {} log files, freed {}", 
+        stats.files_removed(), 
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct Logs;
+
+impl CleanupTask for Logs {
+    fn id(&self) -> &'static str {
+        "logs"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("/var/log")
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_logs_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_logs(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "📝 System Logs".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let log_size = estimate_logs_size();
+        ctx.record_target("logs", log_size);
+        ctx.log_info(&format!("Estimated size: {}", format_size(log_size, BINARY).red()));
+        show_space_preview(log_size);
+
+        if ctx.should_proceed(&format!("Clean system logs older than {} days?", ctx.max_age("logs", 7)),
+            Some(format!("This will free approximately {}", format_size(log_size, BINARY)))) {
+            return ctx.run_target(|| clean_logs(ctx));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,212 @@
+use crate::*;
+
+/// Account types whose server keeps every message, so attachments Mail downloaded can be
+/// fetched again. POP accounts and "On My Mac" mailboxes never qualify.
+pub const SERVER_KEPT_ACCOUNT_TYPES: &[&str] = &["IMAP", "iCloud", "Gmail", "Google", "Yahoo!", "AOL", "Exchange"];
+
+pub struct MailAccount {
+    pub name: String,
+    /// Account type from the Accounts database, e.g. "IMAP"; empty when unknown
+    pub kind: String,
+    pub size: u64,
+    /// `Attachments` folders inside the account's mailboxes
+    pub attachment_dirs: Vec<String>,
+    /// Attachments of messages older than the target's age
+    pub attachments_size: u64,
+}
+
+impl MailAccount {
+    pub fn server_keeps_originals(&self) -> bool {
+        SERVER_KEPT_ACCOUNT_TYPES.iter().any(|kind| kind.eq_ignore_ascii_case(&self.kind))
+    }
+}
+
+/// The newest of Mail's versioned data folders, e.g. ~/Library/Mail/V10.
+pub fn mail_data_dir() -> Option<PathBuf> {
+    fs::read_dir(expand_home("~/Library/Mail")).ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let version: u32 = entry.file_name().to_str()?.strip_prefix('V')?.parse().ok()?;
+            Some((version, entry.path()))
+        })
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, path)| path)
+}
+
+/// Mail's message index, which keeps the pages of deleted messages until it's vacuumed.
+pub fn envelope_index() -> Option<String> {
+    let index = mail_data_dir()?.join("MailData/Envelope Index");
+    index.exists().then(|| index.to_str().unwrap_or("").to_string())
+}
+
+/// Names and types of the accounts in Internet Accounts, keyed by identifier. Mail names
+/// each account's folder after one of these identifiers.
+pub fn account_descriptions() -> HashMap<String, (String, String)> {
+    let db = expand_home("~/Library/Accounts/Accounts4.sqlite");
+    let sql = "SELECT a.ZIDENTIFIER, a.ZACCOUNTDESCRIPTION, t.ZACCOUNTTYPEDESCRIPTION \
+        FROM ZACCOUNT a LEFT JOIN ZACCOUNTTYPE t ON a.ZACCOUNTTYPE = t.Z_PK";
+    let Ok(output) = Command::new("sqlite3").args(["-readonly", "-separator", "\t", &db, sql]).output() else {
+        return HashMap::new();
+    };
+    String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let id = fields.next()?.to_string();
+            let name = fields.next().unwrap_or("").to_string();
+            let kind = fields.next().unwrap_or("").to_string();
+            Some((id, (name, kind)))
+        })
+        .collect()
+}
+
+/// Per-account Mail storage, largest first, with the attachments older than `days`.
+pub fn find_mail_accounts(days: u64) -> Vec<MailAccount> {
+    let Some(data_dir) = mail_data_dir() else { return Vec::new() };
+    let descriptions = account_descriptions();
+    let mut accounts = Vec::new();
+
+    for entry in fs::read_dir(&data_dir).into_iter().flat_map(|entries| entries.flatten()) {
+        let id = entry.file_name().to_str().unwrap_or("").to_string();
+        // Account folders are UUIDs; MailData holds the index and settings
+        if !entry.path().is_dir() || id.len() != 36 || id.matches('-').count() != 4 {
+            continue;
+        }
+        let path = entry.path().to_str().unwrap_or("").to_string();
+        let size = get_directory_size(&path);
+        if size == 0 {
+            continue;
+        }
+
+        let mut attachment_dirs = Vec::new();
+        let mut walker = WalkDir::new(&path).into_iter();
+        while let Some(Ok(entry)) = walker.next() {
+            if entry.file_type().is_dir() && name_is(entry.path(), "Attachments") && !is_ignored(entry.path()) {
+                walker.skip_current_dir();
+                attachment_dirs.push(entry.path().to_str().unwrap_or("").to_string());
+            }
+        }
+        let attachments_size = attachment_dirs.iter().map(|dir| get_old_files_size(dir, days)).sum();
+
+        let (name, kind) = descriptions.get(&id).cloned()
+            .unwrap_or_else(|| (format!("Account {}", &id[..8]), String::new()));
+        accounts.push(MailAccount { name, kind, size, attachment_dirs, attachments_size });
+    }
+
+    accounts.sort_by_key(|account| std::cmp::Reverse(account.size));
+    accounts
+}
+
+pub fn remove_mail_attachments(ctx: &CleanupContext, accounts: &[&MailAccount], days: u64) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for account in accounts {
+        ctx.log_action(&format!("Removing downloaded attachments of {}", account.name));
+        for dir in &account.attachment_dirs {
+            stats.add(&clean_directory(dir, Some(days), ctx));
+        }
+    }
+
+    ctx.log_success(&format!("Removed downloaded attachments, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+
+    // Mail is quit at this point, so its index can be compacted safely
+    if let Some(index) = envelope_index() {
+        let before = fs::metadata(&index).map(|m| m.len()).unwrap_or(0);
+        ctx.log_action(&format!("Vacuuming {}", index));
+        if edit_database(ctx, &index, "VACUUM;") && !ctx.dry_run {
+            let after = fs::metadata(&index).map(|m| m.len()).unwrap_or(before);
+            ctx.log_success(&format!("Compacted Mail's Envelope Index from {} to {}",
+                format_size(before, BINARY), format_size(after, BINARY)));
+        }
+    }
+    stats
+}
+
+/// Mail's per-account storage, with advice on attachment downloads and, for accounts whose
+/// server keeps the originals, removal of the attachment copies picked interactively.
+pub struct MailAttachments;
+
+impl CleanupTask for MailAttachments {
+    fn id(&self) -> &'static str {
+        "mail_attachments"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Library/Mail")
+    }
+
+    fn estimate(&self) -> u64 {
+        let days = load_config().unwrap_or_default().max_age("mail_attachments", 30);
+        find_mail_accounts(days).iter()
+            .filter(|account| account.server_keeps_originals())
+            .map(|account| account.attachments_size)
+            .sum()
+    }
+
+    /// Which accounts lose their attachment copies is a per-account decision, never a blanket one
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let days = ctx.max_age("mail_attachments", 30);
+        let accounts = find_mail_accounts(days);
+        if accounts.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "✉️  Mail Storage".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let eligible: Vec<&MailAccount> = accounts.iter()
+            .filter(|account| account.server_keeps_originals() && account.attachments_size > 0)
+            .collect();
+        let eligible_size: u64 = eligible.iter().map(|account| account.attachments_size).sum();
+        ctx.record_target("mail_attachments", eligible_size);
+
+        for account in &accounts {
+            let kind = if account.kind.is_empty() { "unknown type" } else { account.kind.as_str() };
+            let number = eligible.iter().position(|eligible| std::ptr::eq(*eligible, account))
+                .map_or_else(|| String::from("   "), |i| format!("{:>2}.", i + 1));
+            let note = if account.server_keeps_originals() { "" } else { " — attachments kept, the server may not have them" };
+            println!("    {} {} ({}): {}, attachments older than {} days {}{}",
+                number.dimmed(), account.name.bold(), kind,
+                format_size(account.size, BINARY),
+                days, format_size(account.attachments_size, BINARY).red(),
+                note.dimmed());
+        }
+        for account in &eligible {
+            for dir in &account.attachment_dirs {
+                ctx.record_item(dir, get_old_files_size(dir, days));
+            }
+        }
+        ctx.log_info("To stop attachments piling up, open Mail ▸ Settings ▸ Accounts, pick the account and set Download Attachments to None or Recent");
+        if eligible.is_empty() {
+            return CleanupStats::new();
+        }
+        if tasks::sync_caches::process_running("Mail") {
+            ctx.log_info("Mail is running; quit it to remove downloaded attachments");
+            return CleanupStats::new();
+        }
+
+        let chosen: Vec<usize> = if ctx.interactive && !ctx.dry_run {
+            select_items("Select accounts to remove downloaded attachments from", eligible.len())
+        } else if ctx.dry_run {
+            (0..eligible.len()).collect()
+        } else {
+            ctx.log_info("Attachments are only removed for accounts selected interactively");
+            Vec::new()
+        };
+        if chosen.is_empty() {
+            return CleanupStats::new();
+        }
+        let chosen_accounts: Vec<&MailAccount> = chosen.iter().map(|&i| eligible[i]).collect();
+        let chosen_size: u64 = chosen_accounts.iter().map(|account| account.attachments_size).sum();
+        show_space_preview(chosen_size);
+
+        if ctx.should_proceed(&format!("Remove downloaded attachments from {} accounts?", chosen_accounts.len()),
+            Some("Mail downloads an attachment again from the server when you open its message".to_string())) {
+            return ctx.run_target(|| remove_mail_attachments(ctx, &chosen_accounts, days));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,108 @@
+use crate::*;
+
+pub struct MediaLibrary {
+    pub path: String,
+    pub size: u64,
+    pub modified: String,
+    pub likely_duplicate: bool,
+}
+
+/// `*.photoslibrary` and `*.imovielibrary` bundles in the usual places and on external volumes.
+pub fn find_media_libraries() -> Vec<MediaLibrary> {
+    let home = home_dir();
+    let mut roots: Vec<PathBuf> = ["Pictures", "Movies", "Desktop", "Documents"].iter()
+        .map(|dir| PathBuf::from(format!("{}/{}", home, dir)))
+        .collect();
+    roots.extend(fs::read_dir("/Volumes").into_iter().flat_map(|entries| entries.flatten()).map(|entry| entry.path()));
+
+    let mut libraries = Vec::new();
+    for root in roots {
+        let mut walker = WalkDir::new(&root).max_depth(3).into_iter();
+        while let Some(Ok(entry)) = walker.next() {
+            let path = entry.path();
+            if !extension_is(path, &["photoslibrary", "imovielibrary"]) {
+                if is_bundle(path) {
+                    walker.skip_current_dir();
+                }
+                continue;
+            }
+            walker.skip_current_dir();
+            if is_ignored(path) {
+                continue;
+            }
+
+            // The bundle's own mtime rarely changes; the database inside it does
+            let modified = [path.join("database/Photos.sqlite"), path.join("Database"), path.to_path_buf()].iter()
+                .filter_map(|candidate| fs::metadata(candidate).and_then(|m| m.modified()).ok())
+                .max();
+            libraries.push((path.to_path_buf(), modified));
+        }
+    }
+
+    // Photos and iMovie append " 2", " copy" etc. when a migration or restore collides with an existing library
+    let is_numbered_copy = |path: &Path| {
+        let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("").to_lowercase();
+        stem.ends_with(" copy") || stem.rsplit_once(' ').is_some_and(|(_, suffix)| suffix.parse::<u32>().is_ok())
+    };
+    let newest = |extension: &str| libraries.iter()
+        .filter(|(path, _)| path.extension().is_some_and(|ext| ext == extension))
+        .max_by_key(|(_, modified)| *modified)
+        .map(|(path, _)| path.clone());
+    let newest_photos = newest("photoslibrary");
+    let newest_imovie = newest("imovielibrary");
+
+    let mut result: Vec<MediaLibrary> = libraries.iter()
+        .map(|(path, modified)| {
+            let newest = if path.extension().is_some_and(|ext| ext == "photoslibrary") { &newest_photos } else { &newest_imovie };
+            MediaLibrary {
+                path: path.to_str().unwrap_or("").to_string(),
+                size: get_directory_size(path.to_str().unwrap_or("")),
+                modified: modified
+                    .map(|time| chrono::DateTime::<Local>::from(time).format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| String::from("unknown")),
+                likely_duplicate: newest.as_ref() != Some(path) && is_numbered_copy(path),
+            }
+        })
+        .collect();
+    result.sort_by_key(|library| std::cmp::Reverse(library.size));
+    result
+}
+
+/// Photos & iMovie libraries, reported with likely duplicates flagged; nothing is deleted.
+pub struct MediaLibraries;
+
+impl CleanupTask for MediaLibraries {
+    fn id(&self) -> &'static str {
+        "media_libraries"
+    }
+
+    fn estimate(&self) -> u64 {
+        0
+    }
+
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let media_libraries = find_media_libraries();
+        if media_libraries.len() > 1 {
+            println!("\n{}", "🖼️  Photos & iMovie Libraries".bold());
+            println!("{}", "─".repeat(40).dimmed());
+            let stale_size: u64 = media_libraries.iter().filter(|library| library.likely_duplicate).map(|library| library.size).sum();
+            for library in &media_libraries {
+                let line = format!("{} ({}, modified {})", library.path, format_size(library.size, BINARY), library.modified);
+                if library.likely_duplicate {
+                    println!("    {} {} {}", "•".dimmed(), line.yellow(), "likely stale duplicate".red());
+                } else {
+                    println!("    {} {}", "•".dimmed(), line.dimmed());
+                }
+            }
+            if stale_size > 0 {
+                ctx.log_info(&format!("Likely duplicates: {} — open each in Photos/iMovie to check before deleting it yourself",
+                    format_size(stale_size, BINARY).yellow()));
+            }
+        }
+        CleanupStats::new()
+    }
+}
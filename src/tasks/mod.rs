@@ -0,0 +1,154 @@
+//! Cleanup targets, one module per target id in `TARGETS`.
+
+use crate::*;
+
+pub mod budgets;
+pub mod cargo;
+pub mod cargo_targets;
+pub mod caches;
+pub mod chrome;
+pub mod chromium_caches;
+pub mod configurator;
+pub mod cookies;
+pub mod coreml_caches;
+pub mod device_support;
+pub mod disk_images;
+pub mod docker;
+pub mod downloads;
+pub mod ds_store;
+pub mod extension_leftovers;
+pub mod extra_paths;
+pub mod font_caches;
+pub mod homebrew;
+pub mod install_leftovers;
+pub mod instruments;
+pub mod ios_backups;
+pub mod js_caches;
+pub mod kubernetes;
+pub mod logs;
+pub mod mail;
+pub mod media_libraries;
+pub mod node_modules;
+pub mod partial_downloads;
+pub mod podman;
+pub mod printer_drivers;
+pub mod provisioning_profiles;
+pub mod python;
+pub mod ram;
+pub mod recent_items;
+pub mod runtimes;
+pub mod safari;
+pub mod shell_history;
+pub mod simulators;
+pub mod sound_packs;
+pub mod speech_assets;
+pub mod swift_packages;
+pub mod sync_caches;
+pub mod trash;
+pub mod unquarantine;
+pub mod vagrant;
+pub mod xcode;
+pub mod xcode_previews;
+
+/// One cleanup target: how much it can free, how to free it without asking, and the
+/// section it shows in an interactive run.
+pub trait CleanupTask: Sync {
+    /// Stable id used by the config, `--max-risk`, `explain` and manifests
+    fn id(&self) -> &'static str;
+
+    /// Name, risk level and rules from the `TARGETS` registry
+    fn info(&self) -> Option<&'static TargetInfo> {
+        target_info(self.id())
+    }
+
+    /// The registry name, or the id for a task missing from `TARGETS`
+    fn name(&self) -> &'static str {
+        self.info().map_or(self.id(), |info| info.name)
+    }
+
+    /// A task missing from `TARGETS` counts as Aggressive, so it's only offered when everything is
+    fn risk(&self) -> Risk {
+        self.info().map_or(Risk::Aggressive, |info| info.risk)
+    }
+
+    /// Only offered when asked for with its flag, e.g. `--shell-history`
+    fn opt_in(&self) -> bool {
+        false
+    }
+
+    /// Works through a tool (brew, docker, ...) that acts on the invoking account,
+    /// so it's skipped when cleaning another user
+    fn uses_invoking_account(&self) -> bool {
+        false
+    }
+
+    /// A folder the target deletes in, probed up front for permission problems
+    fn probe_path(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Space `clean` would free
+    fn estimate(&self) -> u64;
+
+    /// Clean without prompts or selection, as the socket server does. Items that are only
+    /// ever removed when picked by hand are left alone.
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats;
+
+    /// The section of a normal run: show what was found, ask, then clean.
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats;
+}
+
+/// Every target in the order a normal run offers them.
+pub const TASKS: &[&dyn CleanupTask] = &[
+    &budgets::Budgets,
+    &caches::Caches,
+    &logs::Logs,
+    &downloads::Downloads,
+    &partial_downloads::PartialDownloads,
+    &extra_paths::ExtraPaths,
+    &unquarantine::Unquarantine,
+    &trash::Trash,
+    &xcode::Xcode,
+    &xcode_previews::XcodePreviews,
+    &device_support::DeviceSupportSymbols,
+    &swift_packages::SwiftPackages,
+    &instruments::Instruments,
+    &provisioning_profiles::ProvisioningProfiles,
+    &simulators::Simulators,
+    &configurator::Configurator,
+    &homebrew::Homebrew,
+    &node_modules::NodeModules,
+    &js_caches::JsCaches,
+    &cargo::Cargo,
+    &cargo_targets::CargoTargets,
+    &docker::Docker,
+    &podman::Podman,
+    &vagrant::Vagrant,
+    &kubernetes::Kubernetes,
+    &disk_images::DiskImages,
+    &safari::Safari,
+    &chrome::Chrome,
+    &chromium_caches::ChromiumCaches,
+    &coreml_caches::CoremlCaches,
+    &sync_caches::SyncCaches,
+    &extension_leftovers::ExtensionLeftovers,
+    &python::Python,
+    &runtimes::Runtimes,
+    &mail::MailAttachments,
+    &ios_backups::IosBackups,
+    &cookies::Cookies,
+    &recent_items::RecentItems,
+    &printer_drivers::PrinterDrivers,
+    &install_leftovers::InstallLeftovers,
+    &sound_packs::SoundPacks,
+    &shell_history::ShellHistory,
+    &ds_store::DsStore,
+    &speech_assets::SpeechAssets,
+    &media_libraries::MediaLibraries,
+    &font_caches::FontCaches,
+    &ram::Ram,
+];
+
+pub fn task(id: &str) -> Option<&'static dyn CleanupTask> {
+    TASKS.iter().copied().find(|task| task.id() == id)
+}
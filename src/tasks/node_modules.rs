@@ -0,0 +1,158 @@
+use std::time::SystemTime;
+
+use crate::*;
+
+pub fn find_node_modules() -> Vec<String> {
+    let search_paths = project_roots();
+
+    let mut found_dirs = Vec::new();
+    for search_path in search_paths {
+        if Path::new(&search_path).exists() {
+            find_node_modules_recursive(&search_path, &mut found_dirs, 0, 3);
+        }
+    }
+    found_dirs
+}
+
+/// Remove `node_modules` folders with the sizes measured when they were found.
+pub fn remove_node_modules(ctx: &CleanupContext, dirs: &[(String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+    for ((_, size), removed) in dirs.iter().zip(ctx.remove_paths(dirs)) {
+        if removed {
+            stats.record_removed(*size);
+        }
+    }
+    ctx.log_success(&format!("Removed {} node_modules directories, freed {}", 
+        stats.files_removed(),
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// When the project holding `node_modules` was last worked on: the newest of its
+/// other top-level entries, so a fresh `npm install` doesn't count.
+pub fn project_last_modified(node_modules: &str) -> Option<SystemTime> {
+    let project = Path::new(node_modules).parent()?;
+    fs::read_dir(project).ok()?
+        .flatten()
+        .filter(|entry| !name_is(&entry.path(), "node_modules"))
+        .filter_map(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .max()
+}
+
+pub fn find_and_clean_node_modules(ctx: &CleanupContext) -> CleanupStats {
+    ctx.log_action("Searching for node_modules directories...");
+    let mut found_dirs: Vec<(String, u64, Option<SystemTime>)> = find_node_modules().into_iter()
+        .map(|dir| {
+            let size = get_directory_size(&dir);
+            let last_modified = project_last_modified(&dir);
+            (dir, size, last_modified)
+        })
+        .collect();
+
+    if !found_dirs.is_empty() {
+        // Least recently touched projects first, they're the likeliest to go
+        found_dirs.sort_by_key(|(_, _, last_modified)| *last_modified);
+        let total_size: u64 = found_dirs.iter().map(|(_, size, _)| size).sum();
+        
+        ctx.record_target("node_modules", total_size);
+
+        println!("\n  {} Found {} node_modules directories ({})", 
+            "ℹ".blue(), 
+            found_dirs.len().to_string().yellow(),
+            format_size(total_size, BINARY).red());
+        
+        for (i, (dir, size, last_modified)) in found_dirs.iter().enumerate() {
+            let project = Path::new(dir).parent().and_then(|p| p.to_str()).unwrap_or(dir);
+            let modified = last_modified
+                .map(|time| chrono::DateTime::<Local>::from(time).format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| String::from("unknown"));
+            println!("    {} {} ({}, last modified {})", 
+                format!("{:>2}.", i + 1).dimmed(),
+                project, 
+                format_size(*size, BINARY).red(),
+                modified);
+        }
+
+        // Active projects are kept by leaving them out of the selection
+        let chosen: Vec<usize> = if ctx.interactive && !ctx.dry_run {
+            select_items("Select projects to remove node_modules from", found_dirs.len())
+        } else {
+            (0..found_dirs.len()).collect()
+        };
+        if chosen.is_empty() {
+            return CleanupStats::new();
+        }
+        let chosen_dirs: Vec<(String, u64)> = chosen.iter().map(|&i| (found_dirs[i].0.clone(), found_dirs[i].1)).collect();
+        let chosen_size: u64 = chosen_dirs.iter().map(|(_, size)| size).sum();
+        // Only what's about to go is handed to the backup command and the manifest
+        for (dir, size) in &chosen_dirs {
+            ctx.record_item(dir, *size);
+        }
+        show_space_preview(chosen_size);
+        
+        if ctx.should_proceed(&format!("Remove {} node_modules directories?", chosen_dirs.len()), 
+            Some(format!("This will free approximately {}", format_size(chosen_size, BINARY)))) {
+            return ctx.run_target(|| remove_node_modules(ctx, &chosen_dirs));
+        }
+    } else {
+        ctx.log_info("No node_modules directories found");
+    }
+    CleanupStats::new()
+}
+
+pub fn find_node_modules_recursive(path: &str, found: &mut Vec<String>, depth: usize, max_depth: usize) {
+    if depth > max_depth {
+        return;
+    }
+
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if is_ignored(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+                
+                if name_is(&path, "node_modules") {
+                    found.push(path.to_str().unwrap_or("").to_string());
+                } else if !dir_name.starts_with('.') && !name_is(&path, "Library") && !is_bundle(&path) {
+                    find_node_modules_recursive(
+                        path.to_str().unwrap_or(""),
+                        found,
+                        depth + 1,
+                        max_depth
+                    );
+                }
+            }
+        }
+    }
+}
+
+pub struct NodeModules;
+
+impl CleanupTask for NodeModules {
+    fn id(&self) -> &'static str {
+        "node_modules"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_node_modules().iter().map(|dir| get_directory_size(dir)).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let dirs: Vec<(String, u64)> = find_node_modules().into_iter()
+            .map(|dir| {
+                let size = get_directory_size(&dir);
+                (dir, size)
+            })
+            .collect();
+        remove_node_modules(ctx, &dirs)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "📦 Node Modules".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        find_and_clean_node_modules(ctx)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::*;
+
+pub fn find_partial_downloads() -> Vec<String> {
+    let home = home_dir();
+    let search_paths = vec![
+        format!("{}/Downloads", home),
+        format!("{}/Desktop", home),
+    ];
+
+    let mut found = Vec::new();
+    for search_path in search_paths {
+        if Path::new(&search_path).exists() {
+            find_partial_downloads_recursive(&search_path, &mut found, 0, 2);
+        }
+    }
+    found
+}
+
+pub fn find_partial_downloads_recursive(path: &str, found: &mut Vec<String>, depth: usize, max_depth: usize) {
+    if depth > max_depth {
+        return;
+    }
+
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if is_ignored(&path) {
+                continue;
+            }
+            let is_partial = extension_is(&path, &["download", "crdownload", "part"]);
+
+            // Safari's .download is a bundle directory, so match before descending
+            if is_partial {
+                found.push(path.to_str().unwrap_or("").to_string());
+            } else if path.is_dir() {
+                let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+                if !dir_name.starts_with('.') && !is_bundle(&path) {
+                    find_partial_downloads_recursive(path.to_str().unwrap_or(""), found, depth + 1, max_depth);
+                }
+            }
+        }
+    }
+}
+
+pub struct PartialDownloads;
+
+impl CleanupTask for PartialDownloads {
+    fn id(&self) -> &'static str {
+        "partial_downloads"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Downloads")
+    }
+
+    fn estimate(&self) -> u64 {
+        get_paths_size(&find_partial_downloads())
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_paths(ctx, &find_partial_downloads(), "interrupted downloads")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let partial_downloads = find_partial_downloads();
+        if partial_downloads.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "⏸️  Interrupted Downloads".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let partial_size = get_paths_size(&partial_downloads);
+        ctx.record_target("partial_downloads", partial_size);
+        for path in &partial_downloads {
+            ctx.record_item(path, get_paths_size(std::slice::from_ref(path)));
+        }
+        ctx.log_info(&format!("{} unfinished downloads: {}",
+            partial_downloads.len(),
+            format_size(partial_size, BINARY).red()));
+        show_space_preview(partial_size);
+
+        if ctx.should_proceed("Delete interrupted downloads (.download, .crdownload, .part)?",
+            Some(format!("This will free approximately {}", format_size(partial_size, BINARY)))) {
+            return ctx.run_target(|| clean_paths(ctx, &partial_downloads, "interrupted downloads"));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,107 @@
+use crate::*;
+
+pub fn check_podman_installed() -> bool {
+    Command::new("podman").arg("--version").output().is_ok()
+}
+
+pub fn podman_storage_path() -> String {
+    let home = home_dir();
+    format!("{}/.local/share/containers", home)
+}
+
+/// Downloaded VM images live in `podman/machine/<provider>/cache`.
+pub fn podman_machine_cache_paths() -> Vec<String> {
+    let machine_dir = format!("{}/podman/machine", podman_storage_path());
+    let mut caches = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(&machine_dir) {
+        for entry in entries.flatten() {
+            let cache = entry.path().join("cache");
+            if cache.is_dir() {
+                caches.push(cache.to_str().unwrap_or("").to_string());
+            }
+        }
+    }
+    caches
+}
+
+pub fn estimate_podman_size() -> u64 {
+    get_paths_size(&podman_machine_cache_paths())
+        + system_df_reclaimable("podman").iter().map(|(_, size)| size).sum::<u64>()
+}
+
+pub fn clean_podman(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    ctx.log_action("Running podman system prune");
+
+    if ctx.delete_cap_reached() {
+        ctx.log_info("Skipping Podman prune: --max-delete cap reached");
+        return stats;
+    }
+
+    if !ctx.dry_run {
+        let storage = podman_storage_path();
+        let before_size = get_directory_size(&storage);
+
+        match Command::new("podman")
+            .args(["system", "prune", "-a", "-f", "--volumes"])
+            .output() {
+            Ok(output) if output.status.success() => {
+                ctx.audit("ran podman system prune -a -f --volumes");
+                stats.add_freed(before_size.saturating_sub(get_directory_size(&storage)));
+                ctx.log_success("Podman prune completed");
+            }
+            Ok(_) => ctx.log_error("podman system prune failed - is the machine running?"),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => ctx.log_missing_tool("podman"),
+            Err(_) => {}
+        }
+    }
+
+    for cache in podman_machine_cache_paths() {
+        ctx.log_action(&format!("Cleaning {}", cache));
+        stats.add(&clean_directory(&cache, None, ctx));
+    }
+
+    ctx.log_success(&format!("Cleaned Podman data, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct Podman;
+
+impl CleanupTask for Podman {
+    fn id(&self) -> &'static str {
+        "podman"
+    }
+
+    fn uses_invoking_account(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_podman_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_podman(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        if !check_podman_installed() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🦭 Podman".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let podman_size = estimate_podman_size();
+        ctx.record_target("podman", podman_size);
+        ctx.log_info(&format!("Reclaimable storage & machine image cache: {}", format_size(podman_size, BINARY).red()));
+        show_space_preview(podman_size);
+
+        if podman_size > 0 && ctx.should_proceed("Clean Podman unused containers, images, volumes and cached machine images?",
+            Some(format!("This will free approximately {}", format_size(podman_size, BINARY)))) {
+            return ctx.run_target(|| clean_podman(ctx));
+        }
+        CleanupStats::new()
+    }
+}
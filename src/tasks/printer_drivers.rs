@@ -0,0 +1,132 @@
+use crate::*;
+
+pub fn find_unused_printer_drivers() -> Vec<String> {
+    let printers_dir = "/Library/Printers";
+    let mut unused = Vec::new();
+
+    // Without lpstat we can't tell which drivers are in use, so flag nothing
+    let configured = match get_configured_printers() {
+        Some(configured) => configured,
+        None => return unused,
+    };
+
+    if let Ok(entries) = fs::read_dir(printers_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() || is_ignored(&path) {
+                continue;
+            }
+
+            let vendor = path.file_name().unwrap_or_default().to_str().unwrap_or("").to_lowercase();
+            // PPDs and the shared scanner/printer plumbing belong to macOS itself
+            if vendor.is_empty() || vendor == "ppds" || vendor == "ppd plugins" || vendor == "installedprinters" {
+                continue;
+            }
+
+            if !configured.contains(&vendor) {
+                unused.push(path.to_str().unwrap_or("").to_string());
+            }
+        }
+    }
+
+    unused
+}
+
+pub fn get_configured_printers() -> Option<String> {
+    let output = Command::new("lpstat")
+        .args(["-p", "-v"])
+        .output()
+        .ok()?;
+
+    let mut configured = String::from_utf8_lossy(&output.stdout).to_string();
+
+    // Queue names are user-editable, but the PPD always names the manufacturer
+    if let Ok(entries) = fs::read_dir("/etc/cups/ppd") {
+        for entry in entries.flatten() {
+            if let Ok(contents) = fs::read_to_string(entry.path()) {
+                for line in contents.lines() {
+                    if line.starts_with("*Manufacturer:") || line.starts_with("*NickName:") {
+                        configured.push_str(line);
+                        configured.push('\n');
+                    }
+                }
+            }
+        }
+    }
+
+    Some(configured.to_lowercase())
+}
+
+pub fn clean_printer_drivers(ctx: &CleanupContext, drivers: &[String]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for dir in drivers {
+        ctx.log_action(&format!("Removing {}", dir));
+        let size = get_directory_size(dir);
+
+        if ctx.remove_path_with_sudo(dir, size) {
+            stats.record_removed(size);
+        } else {
+            ctx.log_error(&format!("Failed to remove {}", dir));
+        }
+    }
+
+    ctx.log_success(&format!("Removed {} printer driver suites, freed {}",
+        stats.files_removed(),
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct PrinterDrivers;
+
+impl CleanupTask for PrinterDrivers {
+    fn id(&self) -> &'static str {
+        "printer_drivers"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("/Library/Printers")
+    }
+
+    fn estimate(&self) -> u64 {
+        find_unused_printer_drivers().iter().map(|dir| get_directory_size(dir)).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let unused_drivers = find_unused_printer_drivers();
+        if unused_drivers.is_empty() {
+            return CleanupStats::new();
+        }
+        clean_printer_drivers(ctx, &unused_drivers)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        if !Path::new("/Library/Printers").exists() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🖨️  Printer Drivers".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let unused_drivers = find_unused_printer_drivers();
+        let drivers_size: u64 = unused_drivers.iter()
+            .map(|dir| get_directory_size(dir))
+            .sum();
+        ctx.record_target("printer_drivers", drivers_size);
+
+        if unused_drivers.is_empty() {
+            ctx.log_info("No unused printer drivers found");
+        } else {
+            ctx.log_info(&format!("Unused driver suites: {}", format_size(drivers_size, BINARY).red()));
+            for dir in &unused_drivers {
+                println!("    {} {}", "•".dimmed(), dir.dimmed());
+            }
+            show_space_preview(drivers_size);
+
+            if ctx.should_proceed("Remove printer drivers for printers no longer configured? (requires sudo)",
+                Some(format!("This will free approximately {}", format_size(drivers_size, BINARY)))) {
+                return ctx.run_target(|| clean_printer_drivers(ctx, &unused_drivers));
+            }
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,199 @@
+use crate::*;
+
+/// Where Xcode installs provisioning profiles; Xcode 16 moved them to the second.
+pub const PROFILE_DIRS: &[&str] = &[
+    "~/Library/MobileDevice/Provisioning Profiles",
+    "~/Library/Developer/Xcode/UserData/Provisioning Profiles",
+];
+
+/// Common name of Apple's intermediate signing certificates, one per generation (G1–G6).
+pub const WWDR_NAME: &str = "Apple Worldwide Developer Relations";
+
+pub struct Profile {
+    pub path: String,
+    pub name: String,
+    pub team: String,
+    /// Why the profile can't sign anything any more
+    pub reason: String,
+    pub size: u64,
+}
+
+/// The element following `<key>key</key>` in an XML plist, tags included.
+fn plist_xml_after<'a>(xml: &'a str, key: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<key>{}</key>", key))? + key.len() + 11;
+    Some(xml[start..].trim_start())
+}
+
+/// The text of the single string or date element following `key`.
+fn plist_xml_value(xml: &str, key: &str) -> Option<String> {
+    let rest = plist_xml_after(xml, key)?;
+    let start = rest.find('>')? + 1;
+    let end = rest.find("</")?;
+    (start <= end).then(|| rest[start..end].trim().to_string())
+}
+
+/// Base64 data elements of the array following `key`.
+fn plist_xml_data(xml: &str, key: &str) -> Vec<String> {
+    let Some(rest) = plist_xml_after(xml, key) else { return Vec::new() };
+    let array = rest.split("</array>").next().unwrap_or("");
+    array.split("<data>").skip(1)
+        .filter_map(|data| data.split("</data>").next())
+        .map(|data| data.split_whitespace().collect())
+        .collect()
+}
+
+/// SHA-1 of a DER certificate given as base64, in the uppercase hex `security` prints.
+fn certificate_sha1(base64: &str) -> Option<String> {
+    let lines: Vec<&str> = base64.as_bytes().chunks(64).filter_map(|line| std::str::from_utf8(line).ok()).collect();
+    let pem = format!("-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n", lines.join("\n"));
+    let mut child = Command::new("openssl").args(["x509", "-noout", "-fingerprint", "-sha1"])
+        .stdin(std::process::Stdio::piped()).stdout(std::process::Stdio::piped()).stderr(std::process::Stdio::null())
+        .spawn().ok()?;
+    child.stdin.take()?.write_all(pem.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    // "SHA1 Fingerprint=AB:CD:…"
+    let text = String::from_utf8_lossy(&output.stdout);
+    Some(text.split('=').nth(1)?.trim().replace(':', "").to_uppercase())
+}
+
+/// SHA-1 hashes of the code signing identities in the keychain that Apple revoked.
+pub fn revoked_identities() -> std::collections::HashSet<String> {
+    let Ok(output) = Command::new("security").args(["find-identity", "-p", "codesigning"]).output() else {
+        return std::collections::HashSet::new();
+    };
+    // "  1) 0A1B… "Apple Development: Jane Appleseed (ABCDE12345)" (CSSMERR_TP_CERT_REVOKED)"
+    String::from_utf8_lossy(&output.stdout).lines()
+        .filter(|line| line.contains("CSSMERR_TP_CERT_REVOKED"))
+        .filter_map(|line| line.split_whitespace().nth(1).map(str::to_uppercase))
+        .collect()
+}
+
+/// Installed profiles that expired or whose certificates were all revoked, oldest first.
+pub fn find_stale_profiles() -> Vec<Profile> {
+    let now = Local::now();
+    let revoked = revoked_identities();
+    let mut profiles = Vec::new();
+
+    for dir in PROFILE_DIRS {
+        for entry in fs::read_dir(expand_home(dir)).into_iter().flat_map(|entries| entries.flatten()) {
+            let path = entry.path();
+            if !matches!(path.extension().and_then(|ext| ext.to_str()), Some("mobileprovision" | "provisionprofile"))
+                || is_ignored(&path) {
+                continue;
+            }
+            // Profiles are signed plists; `security cms -D` checks the signature and prints the plist
+            let Ok(output) = Command::new("security").args(["cms", "-D", "-i"]).arg(&path).output() else { continue };
+            let xml = String::from_utf8_lossy(&output.stdout);
+            if !output.status.success() || xml.is_empty() {
+                continue;
+            }
+
+            let expires = plist_xml_value(&xml, "ExpirationDate")
+                .and_then(|date| chrono::DateTime::parse_from_rfc3339(&date).ok());
+            let certificates = plist_xml_data(&xml, "DeveloperCertificates");
+            let reason = match expires {
+                Some(expires) if expires < now => format!("expired {}", expires.format("%Y-%m-%d")),
+                _ if !revoked.is_empty() && !certificates.is_empty()
+                    && certificates.iter().all(|cert| certificate_sha1(cert).is_some_and(|hash| revoked.contains(&hash))) =>
+                    String::from("certificate revoked"),
+                _ => continue,
+            };
+
+            let name = plist_xml_value(&xml, "Name").unwrap_or_else(|| entry.file_name().to_string_lossy().to_string());
+            let team = plist_xml_value(&xml, "TeamName").unwrap_or_default();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            profiles.push((expires, Profile { path: path.to_str().unwrap_or("").to_string(), name, team, reason, size }));
+        }
+    }
+
+    profiles.sort_by_key(|(expires, _)| *expires);
+    profiles.into_iter().map(|(_, profile)| profile).collect()
+}
+
+/// WWDR intermediates installed more than once, as (name, keychains holding a copy).
+pub fn duplicate_wwdr_certificates() -> Vec<(String, Vec<String>)> {
+    let Ok(output) = Command::new("security").args(["find-certificate", "-a", "-Z", "-c", WWDR_NAME]).output() else {
+        return Vec::new();
+    };
+    // One block per copy: "SHA-1 hash: …", then "keychain: "…"", then its attributes with "labl"
+    let mut copies: Vec<(String, String, String)> = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let line = line.trim();
+        if let Some(hash) = line.strip_prefix("SHA-1 hash:") {
+            copies.push((hash.trim().to_string(), String::new(), String::new()));
+        } else if let (Some(keychain), Some(copy)) = (line.strip_prefix("keychain:"), copies.last_mut()) {
+            let keychain = keychain.trim().trim_matches('"');
+            copy.1 = Path::new(keychain).file_name().and_then(|name| name.to_str()).unwrap_or(keychain).to_string();
+        } else if let (Some(label), Some(copy)) = (line.strip_prefix("\"labl\"<blob>="), copies.last_mut()) {
+            copy.2 = label.trim_matches('"').to_string();
+        }
+    }
+
+    let mut by_hash: Vec<(String, String, Vec<String>)> = Vec::new();
+    for (hash, keychain, label) in copies {
+        match by_hash.iter_mut().find(|(seen, _, _)| *seen == hash) {
+            Some((_, _, keychains)) => keychains.push(keychain),
+            None => by_hash.push((hash, label, vec![keychain])),
+        }
+    }
+    by_hash.into_iter()
+        .filter(|(_, _, keychains)| keychains.len() > 1)
+        .map(|(_, label, keychains)| (label, keychains))
+        .collect()
+}
+
+pub fn remove_profiles(ctx: &CleanupContext, profiles: &[Profile]) -> CleanupStats {
+    let paths: Vec<String> = profiles.iter().map(|profile| profile.path.clone()).collect();
+    clean_paths(ctx, &paths, "provisioning profiles")
+}
+
+/// Expired and revoked provisioning profiles, plus a report of WWDR certificates installed twice.
+pub struct ProvisioningProfiles;
+
+impl CleanupTask for ProvisioningProfiles {
+    fn id(&self) -> &'static str {
+        "provisioning_profiles"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_stale_profiles().iter().map(|profile| profile.size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        remove_profiles(ctx, &find_stale_profiles())
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let profiles = find_stale_profiles();
+        let duplicates = duplicate_wwdr_certificates();
+        if profiles.is_empty() && duplicates.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🪪 Provisioning Profiles & Certificates".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        for (label, keychains) in &duplicates {
+            ctx.log_info(&format!("{} is installed {} times ({}); delete the extra copies in Keychain Access",
+                label, keychains.len(), keychains.join(", ")));
+        }
+        if profiles.is_empty() {
+            return CleanupStats::new();
+        }
+
+        let profiles_size: u64 = profiles.iter().map(|profile| profile.size).sum();
+        ctx.record_target("provisioning_profiles", profiles_size);
+        for profile in &profiles {
+            ctx.record_item(&profile.path, profile.size);
+            let team = if profile.team.is_empty() { String::new() } else { format!(" ({})", profile.team) };
+            println!("    {} {}{} {}", "•".dimmed(), profile.name, team.dimmed(), profile.reason.red());
+        }
+        ctx.log_info(&format!("{} stale profiles: {}", profiles.len(), format_size(profiles_size, BINARY).red()));
+        show_space_preview(profiles_size);
+
+        if ctx.should_proceed(&format!("Remove {} expired or revoked provisioning profiles?", profiles.len()),
+            Some("None of them can sign a build any more; Xcode downloads current profiles when needed".to_string())) {
+            return ctx.run_target(|| remove_profiles(ctx, &profiles));
+        }
+        CleanupStats::new()
+    }
+}
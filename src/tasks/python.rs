@@ -0,0 +1,140 @@
+use crate::*;
+
+pub fn estimate_python_cache_size() -> u64 {
+    let search_paths = project_roots();
+    
+    let mut total = 0;
+    for search_path in search_paths {
+        if Path::new(&search_path).exists() {
+            total += find_python_cache_size(&search_path, 0, 4);
+        }
+    }
+    total
+}
+
+pub fn clean_python_cache(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let search_paths = project_roots();
+
+    ctx.log_action("Searching for Python cache files...");
+    let mut found_files = Vec::new();
+
+    for search_path in search_paths {
+        if Path::new(&search_path).exists() {
+            find_python_cache_files(&search_path, &mut found_files, 0, 4);
+        }
+    }
+
+    let sized: Vec<(String, u64)> = found_files.into_iter()
+        .map(|file| {
+            let size = get_paths_size(std::slice::from_ref(&file));
+            (file, size)
+        })
+        .collect();
+    for ((_, size), removed) in sized.iter().zip(ctx.remove_paths(&sized)) {
+        if removed {
+            stats.record_removed(*size);
+        }
+    }
+
+    ctx.log_success(&format!("Cleaned {} Python cache files, freed {}", 
+        stats.files_removed(), 
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub fn find_python_cache_size(path: &str, depth: usize, max_depth: usize) -> u64 {
+    if depth > max_depth {
+        return 0;
+    }
+
+    let mut size = 0;
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if is_ignored(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+                
+                if name_is(&path, "__pycache__") {
+                    size += get_directory_size(path.to_str().unwrap_or(""));
+                } else if !dir_name.starts_with('.') && !name_is(&path, "Library") && !is_bundle(&path) {
+                    size += find_python_cache_size(
+                        path.to_str().unwrap_or(""),
+                        depth + 1,
+                        max_depth
+                    );
+                }
+            } else if extension_is(&path, &["pyc", "pyo"]) {
+                if let Ok(metadata) = entry.metadata() {
+                    size += metadata.len();
+                }
+            }
+        }
+    }
+    size
+}
+
+pub fn find_python_cache_files(path: &str, found: &mut Vec<String>, depth: usize, max_depth: usize) {
+    if depth > max_depth {
+        return;
+    }
+
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if is_ignored(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                let dir_name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+                
+                if name_is(&path, "__pycache__") {
+                    found.push(path.to_str().unwrap_or("").to_string());
+                } else if !dir_name.starts_with('.') && !name_is(&path, "Library") && !is_bundle(&path) {
+                    find_python_cache_files(
+                        path.to_str().unwrap_or(""),
+                        found,
+                        depth + 1,
+                        max_depth
+                    );
+                }
+            } else if extension_is(&path, &["pyc", "pyo"]) {
+                found.push(path.to_str().unwrap_or("").to_string());
+            }
+        }
+    }
+}
+
+pub struct Python;
+
+impl CleanupTask for Python {
+    fn id(&self) -> &'static str {
+        "python"
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_python_cache_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_python_cache(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "🐍 Python Cache".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let python_size = estimate_python_cache_size();
+        ctx.record_target("python", python_size);
+        ctx.log_info(&format!("__pycache__ & .pyc files: {}", format_size(python_size, BINARY).red()));
+        show_space_preview(python_size);
+
+        if python_size > 0 && ctx.should_proceed("Clean Python cache files?",
+            Some(format!("This will free approximately {}", format_size(python_size, BINARY)))) {
+            return ctx.run_target(|| clean_python_cache(ctx));
+        }
+        CleanupStats::new()
+    }
+}
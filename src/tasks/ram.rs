@@ -0,0 +1,97 @@
+use crate::*;
+
+pub fn clean_ram(ctx: &CleanupContext) {
+    ctx.log_action("Purging inactive memory...");
+    
+    if !ctx.dry_run {
+        println!("  {} This requires sudo password", "ℹ".blue());
+        
+        // Show before state
+        let before_output = Command::new("vm_stat")
+            .output()
+            .expect("Failed to get RAM info");
+        let before_str = String::from_utf8_lossy(&before_output.stdout);
+        let before_inactive = extract_inactive_pages(&before_str);
+        
+        // Run purge command
+        let output = Command::new("sudo")
+            .args(["purge"])
+            .output();
+        
+        match output {
+            Ok(result) => {
+                if result.status.success() {
+                    // Wait a moment for the purge to complete
+                    thread::sleep(Duration::from_secs(2));
+                    
+                    // Show after state
+                    let after_output = Command::new("vm_stat")
+                        .output()
+                        .expect("Failed to get RAM info");
+                    let after_str = String::from_utf8_lossy(&after_output.stdout);
+                    let after_inactive = extract_inactive_pages(&after_str);
+                    
+                    let freed_pages = if before_inactive > after_inactive {
+                        before_inactive - after_inactive
+                    } else {
+                        before_inactive // Assume all inactive was freed
+                    };
+                    
+                    let freed_mb = (freed_pages * 4096) / 1_048_576;
+                    
+                    ctx.log_success(&format!("RAM purged successfully! Freed approximately {} MB", freed_mb));
+                    
+                    // Show updated RAM status
+                    println!("\n  {} Updated RAM status:", "ℹ".blue());
+                    show_ram_status();
+                } else {
+                    ctx.log_error("Failed to purge RAM - may need sudo privileges");
+                }
+            },
+            Err(_) => {
+                ctx.log_error("Failed to run purge command - sudo may not be available");
+            }
+        }
+    } else {
+        ctx.log_info("Would purge inactive RAM memory");
+    }
+}
+
+pub fn extract_inactive_pages(vm_stat_output: &str) -> u64 {
+    for line in vm_stat_output.lines() {
+        if line.contains("Pages inactive:") {
+            return extract_number_from_line(line);
+        }
+    }
+    0
+}
+
+pub struct Ram;
+
+impl CleanupTask for Ram {
+    fn id(&self) -> &'static str {
+        "ram"
+    }
+
+    fn estimate(&self) -> u64 {
+        0
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_ram(ctx);
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        ctx.begin_target("ram");
+        println!("\n{}", "🧠 RAM Memory".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        show_ram_status();
+
+        if ctx.should_proceed("Clean RAM memory (purge inactive memory)?", 
+            Some("This will free up inactive RAM".to_string())) {
+            clean_ram(ctx);
+        }
+        CleanupStats::new()
+    }
+}
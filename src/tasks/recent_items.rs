@@ -0,0 +1,85 @@
+use crate::*;
+
+/// Shared file list formats across macOS versions.
+pub const SHARED_FILE_LIST_EXTENSIONS: &[&str] = &["sfl", "sfl2", "sfl3"];
+
+/// Recent documents, apps and servers lists, and each app's Open Recent list unless its
+/// bundle id is in `keep`. The sidebar favorites, volumes and tags share the folder and stay.
+pub fn find_recent_items(keep: &[String]) -> Vec<String> {
+    let dir = format!("{}/Library/Application Support/com.apple.sharedfilelist", home_dir());
+    let mut items = Vec::new();
+
+    for entry in fs::read_dir(&dir).into_iter().flat_map(|entries| entries.flatten()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_ignored(&path) {
+            continue;
+        }
+
+        if name == "com.apple.LSSharedFileList.ApplicationRecentDocuments" && path.is_dir() {
+            for list in fs::read_dir(&path).into_iter().flat_map(|entries| entries.flatten()) {
+                let list_path = list.path();
+                let bundle_id = list_path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+                if extension_is(&list_path, SHARED_FILE_LIST_EXTENSIONS)
+                    && !keep.iter().any(|kept| kept.eq_ignore_ascii_case(bundle_id))
+                    && !is_ignored(&list_path) {
+                    items.push(list_path.to_str().unwrap_or("").to_string());
+                }
+            }
+        } else if name.starts_with("com.apple.LSSharedFileList.Recent") && extension_is(&path, SHARED_FILE_LIST_EXTENSIONS) {
+            items.push(path.to_str().unwrap_or("").to_string());
+        }
+    }
+
+    items
+}
+
+/// sharedfilelistd keeps the lists in memory and would write them back, so it's restarted
+/// (launchd relaunches it) once they're gone.
+pub fn clean_recent_items(ctx: &CleanupContext, items: &[String]) -> CleanupStats {
+    let stats = clean_paths(ctx, items, "recent items lists");
+    if !ctx.dry_run && stats.files_removed() > 0 && TARGET_USER.get().is_none() {
+        let _ = Command::new("killall").arg("sharedfilelistd").output();
+    }
+    stats
+}
+
+pub struct RecentItems;
+
+impl CleanupTask for RecentItems {
+    fn id(&self) -> &'static str {
+        "recent_items"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Library/Application Support/com.apple.sharedfilelist")
+    }
+
+    fn estimate(&self) -> u64 {
+        get_paths_size(&find_recent_items(&load_config().unwrap_or_default().keep_recents))
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_recent_items(ctx, &find_recent_items(&ctx.keep_recents))
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "🕘 Recent Items".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let recent_items = find_recent_items(&ctx.keep_recents);
+        let recent_size = get_paths_size(&recent_items);
+        ctx.record_target("recent_items", recent_size);
+        for path in &recent_items {
+            ctx.record_item(path, get_paths_size(std::slice::from_ref(path)));
+        }
+        ctx.log_info(&format!("{} recent items lists: {}", recent_items.len(), format_size(recent_size, BINARY).yellow()));
+        if !ctx.keep_recents.is_empty() {
+            ctx.log_info(&format!("Keeping recents of {}", ctx.keep_recents.join(", ")));
+        }
+
+        if !recent_items.is_empty() && ctx.should_proceed("Clear recent documents and Open Recent menus?", None) {
+            return ctx.run_target(|| clean_recent_items(ctx, &recent_items));
+        }
+        CleanupStats::new()
+    }
+}
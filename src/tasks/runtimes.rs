@@ -0,0 +1,129 @@
+use crate::*;
+
+/// Runtime versions unused for this long (and not the default) count as stale.
+pub const RUNTIME_STALE_DAYS: u64 = 90;
+
+pub struct RuntimeVersion {
+    pub manager: String,
+    pub version: String,
+    pub path: String,
+    pub size: u64,
+    pub last_used_days: Option<u64>,
+    pub is_default: bool,
+}
+
+impl RuntimeVersion {
+    pub fn is_stale(&self) -> bool {
+        !self.is_default && self.last_used_days.is_none_or(|days| days >= RUNTIME_STALE_DAYS)
+    }
+}
+
+pub fn list_runtime_versions() -> Vec<RuntimeVersion> {
+    let home = home_dir();
+    let read_default = |file: String| fs::read_to_string(file).unwrap_or_default().trim().to_string();
+
+    // (manager, versions dir, interpreter inside each version, default version)
+    let managers = [
+        ("pyenv", format!("{}/.pyenv/versions", home), "bin/python", read_default(format!("{}/.pyenv/version", home))),
+        ("rbenv", format!("{}/.rbenv/versions", home), "bin/ruby", read_default(format!("{}/.rbenv/version", home))),
+        ("nvm", format!("{}/.nvm/versions/node", home), "bin/node", read_default(format!("{}/.nvm/alias/default", home))),
+    ];
+
+    let mut runtimes = Vec::new();
+    for (manager, versions_dir, binary, default) in managers {
+        let mut versions: Vec<RuntimeVersion> = fs::read_dir(&versions_dir).into_iter()
+            .flat_map(|entries| entries.flatten())
+            .filter(|entry| entry.path().is_dir() && !is_ignored(&entry.path()))
+            .map(|entry| {
+                let path = entry.path();
+                let version = entry.file_name().to_str().unwrap_or("").to_string();
+                // Running the interpreter updates its access time
+                let last_used_days = fs::metadata(path.join(binary)).and_then(|m| m.accessed()).ok()
+                    .and_then(|accessed| accessed.elapsed().ok())
+                    .map(|elapsed| elapsed.as_secs() / 86400);
+                // nvm's default alias may omit the "v" or give only a major version
+                let is_default = !default.is_empty()
+                    && (version == default || version.trim_start_matches('v').starts_with(default.trim_start_matches('v')));
+
+                RuntimeVersion {
+                    manager: manager.to_string(),
+                    size: get_directory_size(path.to_str().unwrap_or("")),
+                    path: path.to_str().unwrap_or("").to_string(),
+                    version,
+                    last_used_days,
+                    is_default,
+                }
+            })
+            .collect();
+        versions.sort_by(|a, b| compare_versions(&a.version, &b.version));
+        runtimes.extend(versions);
+    }
+
+    runtimes
+}
+
+/// pyenv / rbenv / nvm versions nothing has used in a while.
+pub struct Runtimes;
+
+impl CleanupTask for Runtimes {
+    fn id(&self) -> &'static str {
+        "runtimes"
+    }
+
+    fn estimate(&self) -> u64 {
+        list_runtime_versions().iter().filter(|runtime| runtime.is_stale()).map(|runtime| runtime.size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let paths: Vec<String> = list_runtime_versions().into_iter()
+            .filter(|runtime| runtime.is_stale())
+            .map(|runtime| runtime.path)
+            .collect();
+        clean_paths(ctx, &paths, "runtime versions")
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let runtimes = list_runtime_versions();
+        if runtimes.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🧬 Language Runtime Versions".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let stale: Vec<usize> = (0..runtimes.len()).filter(|&i| runtimes[i].is_stale()).collect();
+        let stale_size: u64 = stale.iter().map(|&i| runtimes[i].size).sum();
+        ctx.record_target("runtimes", stale_size);
+
+        for (i, runtime) in runtimes.iter().enumerate() {
+            println!("    {} {} {} ({}, {}){}",
+                format!("{:>2}.", i + 1).dimmed(),
+                runtime.manager.dimmed(),
+                runtime.version.bold(),
+                format_size(runtime.size, BINARY).red(),
+                match runtime.last_used_days {
+                    Some(days) => format!("last used {} days ago", days),
+                    None => "never used".to_string(),
+                },
+                if runtime.is_default { " default".green() } else if runtime.is_stale() { " stale".yellow() } else { "".normal() });
+        }
+        ctx.log_info(&format!("{} stale versions (unused {}+ days, not default): {}",
+            stale.len(), RUNTIME_STALE_DAYS, format_size(stale_size, BINARY).red()));
+
+        let chosen: Vec<usize> = if ctx.interactive && !ctx.dry_run {
+            select_items("Select runtime versions to remove", runtimes.len())
+        } else {
+            stale
+        };
+
+        if !chosen.is_empty() {
+            let paths: Vec<String> = chosen.iter().map(|&i| runtimes[i].path.clone()).collect();
+            let chosen_size: u64 = chosen.iter().map(|&i| runtimes[i].size).sum();
+            show_space_preview(chosen_size);
+            if ctx.should_proceed(&format!("Remove {} runtime versions?", paths.len()),
+                Some(format!("This will free approximately {}", format_size(chosen_size, BINARY)))) {
+                return ctx.run_target(|| clean_paths(ctx, &paths, "runtime versions"));
+            }
+        }
+        CleanupStats::new()
+    }
+}
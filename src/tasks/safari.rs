@@ -0,0 +1,57 @@
+use crate::*;
+
+pub fn estimate_safari_size() -> u64 {
+    let paths: Vec<String> = app_pack("safari").existing_paths().into_iter().map(|(path, _)| path).collect();
+    claim_paths_size("safari", &paths)
+}
+
+pub fn clean_safari(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (path, safety) in app_pack("safari").existing_paths() {
+        ctx.log_action(&format!("Cleaning {} ({})", path, safety));
+        let size = get_paths_size(std::slice::from_ref(&path));
+        if ctx.remove_path(Path::new(&path), size) {
+            stats.record_removed(size);
+        }
+    }
+
+    ctx.log_success(&format!("Cleaned Safari data, freed {}", 
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct Safari;
+
+impl CleanupTask for Safari {
+    fn id(&self) -> &'static str {
+        "safari"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Library/Safari")
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_safari_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_safari(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "🌐 Safari".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let safari_size = estimate_safari_size();
+        ctx.record_target("safari", safari_size);
+        ctx.log_info(&format!("Cache & History: {}", format_size(safari_size, BINARY).red()));
+        show_space_preview(safari_size);
+
+        if safari_size > 0 && ctx.should_proceed("Clean Safari cache and history?",
+            Some(format!("This will free approximately {}", format_size(safari_size, BINARY)))) {
+            return ctx.run_target(|| clean_safari(ctx));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,192 @@
+use crate::*;
+
+/// History files above this size are compacted by `--shell-history`.
+pub const HISTORY_COMPACT_THRESHOLD: u64 = 5 * 1_048_576;
+
+pub const HISTORY_KEEP_LINES: usize = 50_000;
+
+pub fn find_giant_histories() -> Vec<(String, u64)> {
+    let home = home_dir();
+    [".zsh_history", ".bash_history", ".local/share/fish/fish_history"].iter()
+        .map(|name| format!("{}/{}", home, name))
+        .filter_map(|path| {
+            let size = fs::metadata(&path).ok()?.len();
+            (size > HISTORY_COMPACT_THRESHOLD && !is_ignored(Path::new(&path))).then_some((path, size))
+        })
+        .collect()
+}
+
+/// Old zsh sessions, superseded `.zcompdump*` files and stale iTerm2 saved state.
+pub fn find_shell_junk() -> Vec<String> {
+    let home = home_dir();
+    let mut junk = Vec::new();
+    let is_old = |path: &Path, days: u64| {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|elapsed| elapsed.as_secs() / 86400 >= days)
+    };
+
+    for (dir, days) in [
+        (format!("{}/.zsh_sessions", home), 7),
+        (format!("{}/Library/Application Support/iTerm2/SavedState", home), 7),
+    ] {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if is_old(&path, days) && !is_ignored(&path) {
+                    junk.push(path.to_str().unwrap_or("").to_string());
+                }
+            }
+        }
+    }
+
+    // zsh regenerates its completion dump; only the newest one (and its .zwc) is used
+    let mut dumps: Vec<(PathBuf, std::time::SystemTime)> = fs::read_dir(&home).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.file_name().to_str().unwrap_or("").starts_with(".zcompdump"))
+        .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?.modified().ok()?)))
+        .filter(|(path, _)| path.extension().is_none_or(|ext| ext != "zwc"))
+        .collect();
+    dumps.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    for (dump, _) in dumps.into_iter().skip(1) {
+        let compiled = PathBuf::from(format!("{}.zwc", dump.display()));
+        if compiled.exists() {
+            junk.push(compiled.to_str().unwrap_or("").to_string());
+        }
+        junk.push(dump.to_str().unwrap_or("").to_string());
+    }
+
+    junk
+}
+
+/// A zsh EXTENDED_HISTORY entry's first line, `: <start>:<elapsed>;<command>`.
+fn is_extended_entry(line: &[u8]) -> bool {
+    let Some(rest) = line.strip_prefix(b": ") else { return false };
+    let Some(semicolon) = rest.iter().position(|&b| b == b';') else { return false };
+    let mut fields = rest[..semicolon].split(|&b| b == b':');
+    let numeric = |field: Option<&[u8]>| field.is_some_and(|field| !field.is_empty() && field.iter().all(u8::is_ascii_digit));
+    numeric(fields.next()) && numeric(fields.next()) && fields.next().is_none()
+}
+
+/// The first line kept when compacting: `HISTORY_KEEP_LINES` from the end, moved forward to
+/// where an entry starts in formats whose entries span lines (zsh extended history, fish).
+/// Without such a line after the cut, e.g. plain zsh history, the cut stays where it was.
+pub fn history_cut_point(path: &str, lines: &[&[u8]]) -> usize {
+    let start = lines.len().saturating_sub(HISTORY_KEEP_LINES);
+    let entry_start: fn(&[u8]) -> bool = if path.ends_with(".zsh_history") {
+        is_extended_entry
+    } else if path.ends_with("fish_history") {
+        |line| line.starts_with(b"- cmd:")
+    } else {
+        return start;
+    };
+    (start..lines.len()).find(|&i| entry_start(lines[i])).unwrap_or(start)
+}
+
+/// Write `contents` next to `path` with its mode and owner, ready to be renamed over it.
+fn write_replacement(path: &str, temp_path: &str, contents: &[u8]) -> io::Result<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let metadata = fs::metadata(path)?;
+    fs::write(temp_path, contents)?;
+    fs::set_permissions(temp_path, fs::Permissions::from_mode(metadata.mode() & 0o7777))?;
+    // Root cleaning another account's history mustn't leave it owned by root
+    std::os::unix::fs::chown(temp_path, Some(metadata.uid()), Some(metadata.gid()))
+}
+
+/// Keep only the most recent history lines. The original is set aside (quarantine, or the
+/// Trash with `--to-trash`) before the compacted copy takes its place.
+pub fn compact_histories(ctx: &CleanupContext, histories: &[(String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (path, size) in histories {
+        ctx.log_action(&format!("Compacting {}", path));
+        if ctx.dry_run {
+            continue;
+        }
+
+        // zsh metafies non-ASCII bytes, so work on raw lines rather than UTF-8
+        let Ok(contents) = fs::read(path) else { continue };
+        let lines: Vec<&[u8]> = contents.split(|&b| b == b'\n').collect();
+        if lines.len() <= HISTORY_KEEP_LINES {
+            continue;
+        }
+
+        let start = history_cut_point(path, &lines);
+        let compacted = lines[start..].join(&b'\n');
+        let temp_path = format!("{}.maccleanup-tmp", path);
+        if let Err(err) = write_replacement(path, &temp_path, &compacted) {
+            let _ = fs::remove_file(&temp_path);
+            ctx.log_error(&format!("Failed to compact {}: {}", path, err));
+            continue;
+        }
+        if !ctx.set_aside(Path::new(path), *size) {
+            let _ = fs::remove_file(&temp_path);
+            continue;
+        }
+        if let Err(err) = fs::rename(&temp_path, path) {
+            ctx.log_error(&format!("Failed to put the compacted {} in place ({}); `maccleanup-rust restore --last` brings back the original",
+                path, err));
+            continue;
+        }
+        ctx.audit(&format!("compacted {} to {} lines", path, lines.len() - start));
+        stats.record_removed(size.saturating_sub(compacted.len() as u64));
+    }
+
+    ctx.log_success(&format!("Compacted {} history files, freed {}",
+        stats.files_removed(),
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Giant shell histories, old zsh sessions and terminal leftovers (opt-in).
+pub struct ShellHistory;
+
+impl CleanupTask for ShellHistory {
+    fn id(&self) -> &'static str {
+        "shell_history"
+    }
+
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        let history_excess: u64 = find_giant_histories().iter().map(|(_, size)| size.saturating_sub(HISTORY_COMPACT_THRESHOLD)).sum();
+        get_paths_size(&find_shell_junk()) + history_excess
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = compact_histories(ctx, &find_giant_histories());
+        stats.add(&clean_paths(ctx, &find_shell_junk(), "shell and terminal files"));
+        stats
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        println!("\n{}", "🐚 Shell History & Terminal Bloat".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let giant_histories = find_giant_histories();
+        let shell_junk = find_shell_junk();
+        let junk_size = get_paths_size(&shell_junk);
+        let history_excess: u64 = giant_histories.iter().map(|(_, size)| size.saturating_sub(HISTORY_COMPACT_THRESHOLD)).sum();
+        ctx.record_target("shell_history", junk_size + history_excess);
+
+        for (path, size) in &giant_histories {
+            println!("    {} {} ({}, will keep the last {} lines)",
+                "•".dimmed(), path.dimmed(), format_size(*size, BINARY).red(), HISTORY_KEEP_LINES);
+        }
+        ctx.log_info(&format!("Old sessions, zcompdump duplicates & iTerm2 saved state: {}",
+            format_size(junk_size, BINARY).red()));
+        show_space_preview(junk_size + history_excess);
+
+        if !giant_histories.is_empty() && ctx.should_proceed("Compact giant shell history files?", None) {
+            stats.add(&ctx.run_target(|| compact_histories(ctx, &giant_histories)));
+        }
+        if !shell_junk.is_empty() && ctx.should_proceed("Remove old shell sessions, zcompdump duplicates and iTerm2 saved state?",
+            Some(format!("This will free approximately {}", format_size(junk_size, BINARY)))) {
+            stats.add(&ctx.run_target(|| clean_paths(ctx, &shell_junk, "shell and terminal files")));
+        }
+        stats
+    }
+}
@@ -0,0 +1,328 @@
+use crate::*;
+use super::xcode::check_xcode_installed;
+
+pub struct Simulator {
+    pub udid: String,
+    pub name: String,
+    /// e.g. "iOS 17.2"
+    pub runtime: String,
+    pub booted: bool,
+    /// False when the device's runtime is no longer installed
+    pub available: bool,
+    /// Days since the simulator was last booted; `None` if it never was
+    pub days_unused: Option<i64>,
+    pub data_size: u64,
+}
+
+impl Simulator {
+    pub fn rarely_used(&self, days: u64) -> bool {
+        !self.booted && self.days_unused.is_none_or(|unused| unused >= days as i64)
+    }
+}
+
+/// Simulator devices from `simctl`, with the size of each one's data folder.
+pub fn list_simulators() -> Vec<Simulator> {
+    let Ok(output) = Command::new("xcrun").args(["simctl", "list", "devices", "-j"]).output() else { return Vec::new() };
+    let listing: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_default();
+    let Some(runtimes) = listing["devices"].as_object() else { return Vec::new() };
+
+    let devices_dir = format!("{}/Library/Developer/CoreSimulator/Devices", home_dir());
+    let mut simulators = Vec::new();
+    for (runtime_id, devices) in runtimes {
+        // "com.apple.CoreSimulator.SimRuntime.iOS-17-2" -> "iOS 17.2"
+        let runtime = runtime_id.rsplit('.').next().unwrap_or(runtime_id)
+            .replacen('-', " ", 1)
+            .replace('-', ".");
+        for device in devices.as_array().into_iter().flatten() {
+            let udid = device["udid"].as_str().unwrap_or("").to_string();
+            if udid.is_empty() {
+                continue;
+            }
+            let data_path = device["dataPath"].as_str()
+                .map(String::from)
+                .unwrap_or_else(|| format!("{}/{}/data", devices_dir, udid));
+            if is_ignored(Path::new(&data_path)) {
+                continue;
+            }
+            // "lastBootedAt": "2024-05-01T10:15:00Z", missing for devices never booted
+            let days_unused = device["lastBootedAt"].as_str()
+                .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+                .map(|date| (Local::now() - date.with_timezone(&Local)).num_days());
+            simulators.push(Simulator {
+                name: device["name"].as_str().unwrap_or(&udid).to_string(),
+                runtime: runtime.clone(),
+                booted: device["state"].as_str() == Some("Booted"),
+                available: device["isAvailable"].as_bool().unwrap_or(true),
+                days_unused,
+                data_size: get_directory_size(&data_path),
+                udid,
+            });
+        }
+    }
+
+    simulators.sort_by_key(|simulator| std::cmp::Reverse(simulator.data_size));
+    simulators
+}
+
+/// Folders in CoreSimulator/Devices that `simctl` no longer lists, left behind by devices
+/// deleted outside Xcode, as (path, size). Empty when `simctl` listed nothing, since a
+/// failed listing can't be told apart from an empty one.
+pub fn find_orphaned_device_dirs(simulators: &[Simulator]) -> Vec<(String, u64)> {
+    if simulators.is_empty() {
+        return Vec::new();
+    }
+    let devices_dir = format!("{}/Library/Developer/CoreSimulator/Devices", home_dir());
+    fs::read_dir(&devices_dir).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| entry.path().is_dir() && !is_ignored(&entry.path()))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            // Device folders are named after their UDID; anything else belongs to CoreSimulator
+            let is_udid = name.len() == 36 && name.matches('-').count() == 4;
+            (is_udid && !simulators.iter().any(|simulator| simulator.udid.eq_ignore_ascii_case(&name)))
+                .then(|| entry.path().to_str().map(String::from))?
+        })
+        .map(|path| {
+            let size = get_directory_size(&path);
+            (path, size)
+        })
+        .collect()
+}
+
+/// Delete simulators whose runtime is gone with `simctl delete unavailable`, and remove
+/// device folders `simctl` no longer knows about.
+pub fn delete_unavailable_simulators(ctx: &CleanupContext, unavailable: &[&Simulator], orphans: &[(String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    if !unavailable.is_empty() && ctx.delete_cap_reached() {
+        ctx.log_info("Skipping unavailable simulators: --max-delete cap reached");
+    } else if !unavailable.is_empty() {
+        ctx.log_action(&format!("Deleting {} unavailable simulators", unavailable.len()));
+        if !ctx.dry_run {
+            let deleted = Command::new("xcrun")
+                .args(["simctl", "delete", "unavailable"])
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false);
+            if deleted {
+                ctx.audit(&format!("deleted {} unavailable simulators", unavailable.len()));
+                let freed: u64 = unavailable.iter().map(|simulator| simulator.data_size).sum();
+                ctx.deleted_bytes.set(ctx.deleted_bytes.get() + freed);
+                stats.add_freed(freed);
+            } else {
+                ctx.log_error("Failed to delete unavailable simulators");
+                stats.record_error();
+            }
+        }
+    }
+    for (path, size) in orphans {
+        ctx.log_action(&format!("Removing {} (device no longer listed by simctl)", path));
+        if ctx.remove_path(Path::new(path), *size) {
+            stats.record_removed(*size);
+        }
+    }
+
+    ctx.log_success(&format!("Deleted unavailable simulators, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Delete simulators with `simctl delete`; unlike erasing, the devices are gone afterwards.
+pub fn delete_simulators(ctx: &CleanupContext, simulators: &[&Simulator]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    let mut deleted_count = 0;
+    for simulator in simulators {
+        if ctx.delete_cap_reached() {
+            ctx.log_info("Skipping the remaining simulators: --max-delete cap reached");
+            break;
+        }
+        ctx.log_action(&format!("Deleting {} ({})", simulator.name, simulator.runtime));
+        if ctx.dry_run {
+            deleted_count += 1;
+            continue;
+        }
+
+        let deleted = Command::new("xcrun")
+            .args(["simctl", "delete", &simulator.udid])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+        if !deleted {
+            ctx.log_error(&format!("Failed to delete {} ({})", simulator.name, simulator.runtime));
+            stats.record_error();
+            continue;
+        }
+        ctx.audit(&format!("deleted simulator {} ({})", simulator.name, simulator.udid));
+        ctx.deleted_bytes.set(ctx.deleted_bytes.get() + simulator.data_size);
+        stats.add_freed(simulator.data_size);
+        deleted_count += 1;
+    }
+
+    ctx.log_success(&format!("Deleted {} simulators, freed {}",
+        deleted_count,
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Reset simulators to factory state with `simctl erase`; they must be shut down.
+pub fn erase_simulators(ctx: &CleanupContext, simulators: &[&Simulator]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    let mut erased_count = 0;
+    for simulator in simulators {
+        if ctx.delete_cap_reached() {
+            ctx.log_info("Skipping the remaining simulators: --max-delete cap reached");
+            break;
+        }
+        ctx.log_action(&format!("Erasing {} ({})", simulator.name, simulator.runtime));
+        if ctx.dry_run {
+            erased_count += 1;
+            continue;
+        }
+
+        let erased = Command::new("xcrun")
+            .args(["simctl", "erase", &simulator.udid])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+        if !erased {
+            ctx.log_error(&format!("Failed to erase {} ({})", simulator.name, simulator.runtime));
+            stats.record_error();
+            continue;
+        }
+        ctx.audit(&format!("erased simulator {} ({})", simulator.name, simulator.udid));
+        ctx.deleted_bytes.set(ctx.deleted_bytes.get() + simulator.data_size);
+        stats.add_freed(simulator.data_size);
+        erased_count += 1;
+    }
+
+    ctx.log_success(&format!("Erased {} simulators, freed {}",
+        erased_count,
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Erasing keeps the devices but drops the apps and data inside them; unavailable devices and
+/// leftover device folders are deleted, rarely used devices only when picked.
+pub struct Simulators;
+
+impl CleanupTask for Simulators {
+    fn id(&self) -> &'static str {
+        "simulators"
+    }
+
+    fn uses_invoking_account(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        if !check_xcode_installed() {
+            return 0;
+        }
+        let simulators = list_simulators();
+        let orphans_size: u64 = find_orphaned_device_dirs(&simulators).iter().map(|(_, size)| size).sum();
+        simulators.iter().filter(|simulator| !simulator.booted).map(|simulator| simulator.data_size).sum::<u64>() + orphans_size
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        if !check_xcode_installed() {
+            return CleanupStats::new();
+        }
+        let simulators = list_simulators();
+        let unavailable: Vec<&Simulator> = simulators.iter().filter(|simulator| !simulator.available).collect();
+        let erasable: Vec<&Simulator> = simulators.iter().filter(|simulator| simulator.available && !simulator.booted).collect();
+        let stats = delete_unavailable_simulators(ctx, &unavailable, &find_orphaned_device_dirs(&simulators));
+        stats.add(&erase_simulators(ctx, &erasable));
+        stats
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        let simulators = if check_xcode_installed() { list_simulators() } else { Vec::new() };
+        let orphans = find_orphaned_device_dirs(&simulators);
+        if !simulators.iter().any(|simulator| simulator.data_size > 0) && orphans.is_empty() {
+            return stats;
+        }
+
+        println!("\n{}", "📱 Simulator Content".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let days = ctx.max_age("simulators", 90);
+        let unavailable: Vec<&Simulator> = simulators.iter().filter(|simulator| !simulator.available).collect();
+        let erasable: Vec<&Simulator> = simulators.iter().filter(|simulator| simulator.available && !simulator.booted).collect();
+        let erasable_size: u64 = erasable.iter().map(|simulator| simulator.data_size).sum();
+        let unavailable_size: u64 = unavailable.iter().map(|simulator| simulator.data_size).sum::<u64>()
+            + orphans.iter().map(|(_, size)| size).sum::<u64>();
+        ctx.record_target("simulators", erasable_size + unavailable_size);
+
+        for (i, simulator) in simulators.iter().enumerate() {
+            let note = if simulator.booted {
+                " booted, skipped".yellow()
+            } else if !simulator.available {
+                " runtime missing, unavailable".yellow()
+            } else if simulator.rarely_used(days) {
+                match simulator.days_unused {
+                    Some(unused) => format!(" unused for {} days", unused).dimmed(),
+                    None => " never booted".dimmed(),
+                }
+            } else {
+                "".normal()
+            };
+            println!("    {} {} {} ({}){}",
+                format!("{:>2}.", i + 1).dimmed(),
+                simulator.name,
+                simulator.runtime.dimmed(),
+                format_size(simulator.data_size, BINARY).red(),
+                note);
+        }
+        for (path, size) in &orphans {
+            println!("    {} {} ({}) {}", "•".dimmed(), path.dimmed(), format_size(*size, BINARY).red(), "no longer listed by simctl".yellow());
+        }
+
+        if !unavailable.is_empty() || !orphans.is_empty() {
+            ctx.log_info(&format!("{} unavailable simulators and {} leftover device folders: {}",
+                unavailable.len(), orphans.len(), format_size(unavailable_size, BINARY).red()));
+            if ctx.should_proceed("Delete unavailable simulators (xcrun simctl delete unavailable) and leftover device folders?",
+                Some("Their runtimes are gone, so they can't be booted anyway".to_string())) {
+                stats.add(&ctx.run_target(|| delete_unavailable_simulators(ctx, &unavailable, &orphans)));
+            }
+        }
+
+        ctx.log_info(&format!("Data in {} shut-down simulators: {}", erasable.len(), format_size(erasable_size, BINARY).red()));
+        show_space_preview(erasable_size);
+
+        if !erasable.is_empty() && ctx.should_proceed(&format!("Erase content and settings of all {} shut-down simulators (xcrun simctl erase)?", erasable.len()),
+            Some("Devices stay installed; apps and data inside them are reset".to_string())) {
+            stats.add(&ctx.run_target(|| erase_simulators(ctx, &erasable)));
+        } else if !erasable.is_empty() && ctx.interactive && !ctx.dry_run {
+            let selected = select_items("Select simulators to erase instead", simulators.len());
+            let chosen: Vec<&Simulator> = selected.iter()
+                .map(|&i| &simulators[i])
+                .filter(|simulator| simulator.available && !simulator.booted)
+                .collect();
+            if !chosen.is_empty() {
+                let chosen_size: u64 = chosen.iter().map(|simulator| simulator.data_size).sum();
+                if ctx.should_proceed(&format!("Erase {} selected simulators?", chosen.len()),
+                    Some(format!("This will free approximately {}", format_size(chosen_size, BINARY)))) {
+                    stats.add(&ctx.run_target(|| erase_simulators(ctx, &chosen)));
+                }
+            }
+        }
+
+        // Deleting removes the device itself, so it's only offered for ones picked by hand
+        let rarely_used = erasable.iter().filter(|simulator| simulator.rarely_used(days)).count();
+        if rarely_used > 0 && ctx.interactive && !ctx.dry_run {
+            ctx.log_info(&format!("{} simulators haven't been booted for {} days", rarely_used, days));
+            let selected = select_items("Select simulators to delete entirely (xcrun simctl delete)", simulators.len());
+            let chosen: Vec<&Simulator> = selected.iter()
+                .map(|&i| &simulators[i])
+                .filter(|simulator| simulator.available && !simulator.booted)
+                .collect();
+            if !chosen.is_empty() && ctx.should_proceed(&format!("Delete {} selected simulators?", chosen.len()),
+                Some("Xcode can create them again from Devices and Simulators".to_string())) {
+                stats.add(&ctx.run_target(|| delete_simulators(ctx, &chosen)));
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,103 @@
+use crate::*;
+
+pub fn find_sound_library_packs() -> Vec<(String, u64)> {
+    let library_roots = vec![
+        "/Library/Application Support/GarageBand",
+        "/Library/Application Support/Logic",
+        "/Library/Audio/Apple Loops/Apple",
+    ];
+
+    let mut packs = Vec::new();
+    for root in library_roots {
+        if let Ok(entries) = fs::read_dir(root) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() && !is_ignored(&path) {
+                    let size = get_directory_size(path.to_str().unwrap_or(""));
+                    if size > 0 {
+                        packs.push((path.to_str().unwrap_or("").to_string(), size));
+                    }
+                }
+            }
+        }
+    }
+
+    packs.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    packs
+}
+
+pub fn clean_sound_packs(ctx: &CleanupContext, packs: &[(String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (path, size) in packs {
+        ctx.log_action(&format!("Removing {}", path));
+        if ctx.remove_path_with_sudo(path, *size) {
+            stats.record_removed(*size);
+        } else {
+            ctx.log_error(&format!("Failed to remove {}", path));
+        }
+    }
+
+    ctx.log_success(&format!("Removed {} sound packs, freed {}",
+        stats.files_removed(),
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// GarageBand / Logic sound libraries, re-downloadable in-app.
+pub struct SoundPacks;
+
+impl CleanupTask for SoundPacks {
+    fn id(&self) -> &'static str {
+        "sound_packs"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("/Library/Application Support/GarageBand")
+    }
+
+    fn estimate(&self) -> u64 {
+        0
+    }
+
+    /// Sound packs are only ever removed when picked by hand
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let sound_packs = find_sound_library_packs();
+        if sound_packs.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🎸 GarageBand & Logic Sound Libraries".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let packs_size: u64 = sound_packs.iter().map(|(_, size)| size).sum();
+        ctx.record_target("sound_packs", packs_size);
+        ctx.log_info(&format!("Installed sound packs: {} (re-downloadable in-app)", format_size(packs_size, BINARY).red()));
+        for (i, (path, size)) in sound_packs.iter().enumerate() {
+            println!("    {} {} ({})",
+                format!("{:>2}.", i + 1).dimmed(),
+                path.dimmed(),
+                format_size(*size, BINARY).red());
+        }
+
+        if ctx.interactive && !ctx.dry_run {
+            let selected = select_items("Select sound packs to remove", sound_packs.len());
+            if !selected.is_empty() {
+                let chosen: Vec<(String, u64)> = selected.iter().map(|&i| sound_packs[i].clone()).collect();
+                let chosen_size: u64 = chosen.iter().map(|(_, size)| size).sum();
+                show_space_preview(chosen_size);
+
+                if ctx.should_proceed(&format!("Remove {} selected sound packs? (requires sudo)", chosen.len()),
+                    Some(format!("This will free approximately {}", format_size(chosen_size, BINARY)))) {
+                    return ctx.run_target(|| clean_sound_packs(ctx, &chosen));
+                }
+            }
+        } else {
+            ctx.log_info("Sound packs are only removed when selected interactively");
+        }
+        CleanupStats::new()
+    }
+}
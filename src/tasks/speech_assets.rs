@@ -0,0 +1,66 @@
+use crate::*;
+
+pub fn find_speech_assets() -> Vec<(String, u64)> {
+    let home = home_dir();
+    let assets_dir = "/System/Library/AssetsV2";
+    let keywords = ["speech", "dictation", "siri", "tts", "voiceservices", "voicetrigger"];
+    let mut assets = Vec::new();
+
+    // AssetsV2 is SIP-protected: readable for sizing, but never deleted by us
+    if let Ok(entries) = fs::read_dir(assets_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = path.file_name().unwrap_or_default().to_str().unwrap_or("").to_lowercase();
+            if path.is_dir() && keywords.iter().any(|keyword| name.contains(keyword)) {
+                let size = get_directory_size(path.to_str().unwrap_or(""));
+                if size > 0 {
+                    assets.push((path.to_str().unwrap_or("").to_string(), size));
+                }
+            }
+        }
+    }
+
+    let user_speech = format!("{}/Library/Speech", home);
+    if Path::new(&user_speech).exists() {
+        let size = get_directory_size(&user_speech);
+        if size > 0 {
+            assets.push((user_speech, size));
+        }
+    }
+
+    assets.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    assets
+}
+
+/// Speech & Siri assets, reported so "System Data" makes sense; nothing is deleted.
+pub struct SpeechAssets;
+
+impl CleanupTask for SpeechAssets {
+    fn id(&self) -> &'static str {
+        "speech_assets"
+    }
+
+    fn estimate(&self) -> u64 {
+        0
+    }
+
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let speech_assets = find_speech_assets();
+        if !speech_assets.is_empty() {
+            println!("\n{}", "🗣️  Speech, Dictation & Siri Assets".bold());
+            println!("{}", "─".repeat(40).dimmed());
+            let speech_size: u64 = speech_assets.iter().map(|(_, size)| size).sum();
+            ctx.log_info(&format!("Downloaded assets: {} (managed by macOS, counted in \"System Data\")",
+                format_size(speech_size, BINARY).yellow()));
+            for (path, size) in &speech_assets {
+                println!("    {} {} ({})", "•".dimmed(), path.dimmed(), format_size(*size, BINARY));
+            }
+            ctx.log_info("Remove unused voices and languages in System Settings → Accessibility → Spoken Content");
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,74 @@
+use crate::*;
+
+/// Dependency manager packs in `apps.toml` whose caches this target clears, each asked about on its own.
+pub const SWIFT_PACKAGE_MANAGERS: &[&str] = &["cocoapods", "carthage", "swiftpm"];
+
+/// Existing caches as (tool name, path, size).
+pub fn find_swift_package_caches() -> Vec<(&'static str, String, u64)> {
+    SWIFT_PACKAGE_MANAGERS.iter()
+        .map(|id| app_pack(id))
+        .flat_map(|pack| pack.existing_paths().into_iter()
+            .filter(|(_, safety)| *safety == SafetyClass::Cache)
+            .map(move |(path, _)| (pack.name.as_str(), path)))
+        .map(|(name, path)| {
+            let size = get_directory_size(&path);
+            claim_path("swift_packages", &path, size);
+            (name, path, size)
+        })
+        .filter(|(_, _, size)| *size > 0)
+        .collect()
+}
+
+pub fn clean_swift_package_cache(ctx: &CleanupContext, name: &str, path: &str) -> CleanupStats {
+    ctx.log_action(&format!("Cleaning {} ({} cache)", path, name));
+    let stats = clean_directory(path, None, ctx);
+    ctx.log_success(&format!("Cleaned {} cache, freed {}", name, format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// CocoaPods, Carthage and SwiftPM download caches.
+pub struct SwiftPackages;
+
+impl CleanupTask for SwiftPackages {
+    fn id(&self) -> &'static str {
+        "swift_packages"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_swift_package_caches().iter().map(|(_, _, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        for (name, path, _) in find_swift_package_caches() {
+            stats.add(&clean_swift_package_cache(ctx, name, &path));
+        }
+        stats
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let caches = find_swift_package_caches();
+        if caches.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🧩 CocoaPods, Carthage & SwiftPM Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let caches_size: u64 = caches.iter().map(|(_, _, size)| size).sum();
+        ctx.record_target("swift_packages", caches_size);
+        for (_, path, size) in &caches {
+            ctx.record_item(path, *size);
+        }
+
+        let stats = CleanupStats::new();
+        for (name, path, size) in &caches {
+            ctx.log_info(&format!("{} cache: {}", name, format_size(*size, BINARY).red()));
+            if ctx.should_proceed(&format!("Clean the {} cache?", name),
+                Some(format!("This will free approximately {}; dependencies are fetched again on the next resolve",
+                    format_size(*size, BINARY)))) {
+                stats.add(&ctx.run_target(|| clean_swift_package_cache(ctx, name, path)));
+            }
+        }
+        stats
+    }
+}
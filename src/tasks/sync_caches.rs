@@ -0,0 +1,91 @@
+use crate::*;
+
+/// Sync client packs in `apps.toml` and the process that has to be quit before its cache goes.
+pub const SYNC_CLIENTS: &[(&str, &str)] = &[
+    ("dropbox", "Dropbox"),
+    ("google_drive", "Google Drive"),
+    ("onedrive", "OneDrive"),
+];
+
+/// Whether a process named exactly `name` is running.
+pub fn process_running(name: &str) -> bool {
+    Command::new("pgrep").args(["-x", name]).output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Existing cache folders as (client name, process, path, size).
+pub fn find_sync_caches() -> Vec<(&'static str, &'static str, String, u64)> {
+    SYNC_CLIENTS.iter()
+        .map(|(id, process)| (app_pack(id), *process))
+        .flat_map(|(pack, process)| pack.existing_paths().into_iter()
+            .filter(|(_, safety)| *safety == SafetyClass::Cache)
+            .map(move |(path, _)| (pack.name.as_str(), process, path)))
+        .map(|(name, process, path)| {
+            let size = get_directory_size(&path);
+            claim_path("sync_caches", &path, size);
+            (name, process, path, size)
+        })
+        .filter(|(_, _, _, size)| *size > 0)
+        .collect()
+}
+
+/// Clear the caches of clients that aren't running; a running client may still be
+/// uploading from its cache, so it's left alone.
+pub fn clean_sync_caches(ctx: &CleanupContext, caches: &[(&str, &str, String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (name, process, path, _) in caches {
+        if process_running(process) {
+            ctx.log_info(&format!("Skipping {}: {} is running, quit it first", path, name));
+            continue;
+        }
+        ctx.log_action(&format!("Cleaning {} ({} cache)", path, name));
+        stats.add(&clean_directory(path, None, ctx));
+    }
+
+    ctx.log_success(&format!("Cleaned sync client caches, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+/// Local caches of Dropbox, Google Drive and OneDrive; the synced files are never touched.
+pub struct SyncCaches;
+
+impl CleanupTask for SyncCaches {
+    fn id(&self) -> &'static str {
+        "sync_caches"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_sync_caches().iter().map(|(_, _, _, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_sync_caches(ctx, &find_sync_caches())
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let caches = find_sync_caches();
+        if caches.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "☁️  Dropbox, Google Drive & OneDrive Caches".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let caches_size: u64 = caches.iter().map(|(_, _, _, size)| size).sum();
+        ctx.record_target("sync_caches", caches_size);
+        for (name, process, path, size) in &caches {
+            ctx.record_item(path, *size);
+            let running = if process_running(process) { " — running, will be skipped".yellow().to_string() } else { String::new() };
+            println!("    {} {} {} ({}){}", "•".dimmed(), name, path.dimmed(), format_size(*size, BINARY).red(), running);
+        }
+        ctx.log_info(&format!("Sync client caches: {}", format_size(caches_size, BINARY).red()));
+        show_space_preview(caches_size);
+
+        if ctx.should_proceed("Clean Dropbox, Google Drive and OneDrive caches?",
+            Some("Synced files stay put; files kept only in the cache are downloaded again when opened".to_string())) {
+            return ctx.run_target(|| clean_sync_caches(ctx, &caches));
+        }
+        CleanupStats::new()
+    }
+}
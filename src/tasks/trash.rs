@@ -0,0 +1,130 @@
+use crate::*;
+
+pub fn estimate_trash_size() -> u64 {
+    trash_locations().iter()
+        .map(|trash| estimate_path_size(&trash.path))
+        .sum()
+}
+
+pub struct TrashLocation {
+    pub label: String,
+    pub path: String,
+    pub needs_sudo: bool,
+}
+
+/// The home trash, this user's trash on every mounted volume, and root's trash when escalated.
+pub fn trash_locations() -> Vec<TrashLocation> {
+    let home = home_dir();
+    let mut trashes = vec![TrashLocation {
+        label: String::from("Home"),
+        path: format!("{}/.Trash", home),
+        needs_sudo: false,
+    }];
+
+    // External drives keep a separate trash per user id
+    let uid = target_uid();
+    for volume in fs::read_dir("/Volumes").into_iter().flat_map(|entries| entries.flatten()) {
+        let path = volume.path().join(".Trashes").join(uid.to_string());
+        // The boot volume is symlinked into /Volumes and its trash is the home one
+        if volume.path().canonicalize().is_ok_and(|target| target == Path::new("/")) {
+            continue;
+        }
+        trashes.push(TrashLocation {
+            label: volume.file_name().to_str().unwrap_or("").to_string(),
+            path: path.to_str().unwrap_or("").to_string(),
+            needs_sudo: false,
+        });
+    }
+
+    // Files deleted from Finder windows opened as root; unreadable without escalation
+    if uid == 0 || SUDO_ESTIMATES.load(Ordering::Relaxed) {
+        trashes.push(TrashLocation {
+            label: String::from("root"),
+            path: String::from("/private/var/root/.Trash"),
+            needs_sudo: true,
+        });
+    }
+
+    trashes.retain(|trash| Path::new(&trash.path).exists() && !is_ignored(Path::new(&trash.path)));
+    trashes
+}
+
+pub fn empty_trash(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for trash in trash_locations() {
+        ctx.log_action(&format!("Emptying trash ({})", trash.label));
+        let trash_stats = if trash.needs_sudo {
+            empty_trash_with_sudo(ctx, &trash.path)
+        } else {
+            clean_directory(&trash.path, None, ctx)
+        };
+        ctx.log_success(&format!("Emptied {} trash, freed {}",
+            trash.label,
+            format_size(trash_stats.space_freed(), BINARY)));
+        stats.add(&trash_stats);
+    }
+
+    stats
+}
+
+pub fn empty_trash_with_sudo(ctx: &CleanupContext, trash_path: &str) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let size = estimate_path_size(trash_path);
+
+    // Finder recreates .Trash on demand, so removing the folder itself is fine
+    if ctx.remove_path_with_sudo(trash_path, size) {
+        stats.record_removed(size);
+    } else {
+        ctx.log_error(&format!("Failed to empty {}", trash_path));
+    }
+    stats
+}
+
+pub struct Trash;
+
+impl CleanupTask for Trash {
+    fn id(&self) -> &'static str {
+        "trash"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/.Trash")
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_trash_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        empty_trash(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "🗑️  Trash".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let trash_size = estimate_trash_size();
+        ctx.record_target("trash", trash_size);
+        ctx.log_info(&format!("Current size: {}", format_size(trash_size, BINARY).red()));
+        let trashes = trash_locations();
+        if trashes.len() > 1 {
+            for trash in &trashes {
+                println!("    {} {} ({})", "•".dimmed(), trash.label, format_size(estimate_path_size(&trash.path), BINARY));
+            }
+        }
+        show_space_preview(trash_size);
+        if ctx.backup_command.is_some() {
+            for trash in trashes.iter().filter(|trash| !trash.needs_sudo) {
+                for (path, size) in directory_candidates(&trash.path, None) {
+                    ctx.record_item(&path, size);
+                }
+            }
+        }
+
+        if trash_size > 0 && ctx.should_proceed("Empty trash?",
+            Some(format!("This will permanently delete {} of files", format_size(trash_size, BINARY)))) {
+            return ctx.run_target(|| empty_trash(ctx));
+        }
+        CleanupStats::new()
+    }
+}
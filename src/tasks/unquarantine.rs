@@ -0,0 +1,106 @@
+use crate::*;
+
+pub const QUARANTINE_XATTR: &std::ffi::CStr = c"com.apple.quarantine";
+
+pub fn has_quarantine_flag(path: &Path) -> bool {
+    let Ok(c_path) = std::ffi::CString::new(path.as_os_str().as_encoded_bytes()) else { return false };
+    // SAFETY: both names are NUL-terminated; a null buffer with size 0 only asks for the length
+    #[cfg(target_os = "macos")]
+    let length = unsafe {
+        libc::getxattr(c_path.as_ptr(), QUARANTINE_XATTR.as_ptr(), std::ptr::null_mut(), 0, 0, libc::XATTR_NOFOLLOW)
+    };
+    // SAFETY: as above
+    #[cfg(not(target_os = "macos"))]
+    let length = unsafe { libc::lgetxattr(c_path.as_ptr(), QUARANTINE_XATTR.as_ptr(), std::ptr::null_mut(), 0) };
+    length >= 0
+}
+
+pub fn remove_quarantine_flag(path: &Path) -> io::Result<()> {
+    let c_path = std::ffi::CString::new(path.as_os_str().as_encoded_bytes()).map_err(io::Error::other)?;
+    // SAFETY: both names are valid NUL-terminated strings
+    #[cfg(target_os = "macos")]
+    let result = unsafe { libc::removexattr(c_path.as_ptr(), QUARANTINE_XATTR.as_ptr(), libc::XATTR_NOFOLLOW) };
+    // SAFETY: as above
+    #[cfg(not(target_os = "macos"))]
+    let result = unsafe { libc::lremovexattr(c_path.as_ptr(), QUARANTINE_XATTR.as_ptr()) };
+    if result == 0 { Ok(()) } else { Err(io::Error::last_os_error()) }
+}
+
+/// Files and folders directly in ~/Downloads that Gatekeeper will still ask about.
+pub fn find_quarantined_downloads() -> Vec<String> {
+    let downloads = format!("{}/Downloads", home_dir());
+    let mut quarantined: Vec<String> = fs::read_dir(&downloads).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| !is_ignored(path) && has_quarantine_flag(path))
+        .map(|path| path.to_str().unwrap_or("").to_string())
+        .collect();
+    quarantined.sort();
+    quarantined
+}
+
+pub fn unquarantine_files(ctx: &CleanupContext, paths: &[String]) {
+    let mut cleared = 0;
+    for path in paths {
+        ctx.log_action(&format!("Clearing quarantine flag on {}", path));
+        match remove_quarantine_flag(Path::new(path)) {
+            Ok(()) => {
+                ctx.audit(&format!("cleared quarantine flag on {}", path));
+                cleared += 1;
+            }
+            Err(err) => ctx.log_error(&format!("Failed to clear quarantine flag on {}: {}", path, err)),
+        }
+    }
+    ctx.log_success(&format!("Cleared the quarantine flag on {} files", cleared));
+}
+
+/// Stop Gatekeeper asking about downloads the user keeps (opt-in, frees nothing).
+pub struct Unquarantine;
+
+impl CleanupTask for Unquarantine {
+    fn id(&self) -> &'static str {
+        "unquarantine"
+    }
+
+    fn probe_path(&self) -> Option<&'static str> {
+        Some("~/Downloads")
+    }
+
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        0
+    }
+
+    /// Skipping Gatekeeper's check is a per-file decision, never a blanket one
+    fn clean(&self, _ctx: &CleanupContext) -> CleanupStats {
+        CleanupStats::new()
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        println!("\n{}", "🛂 Quarantined Downloads".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let quarantined = find_quarantined_downloads();
+        ctx.begin_target("unquarantine");
+        if quarantined.is_empty() {
+            ctx.log_info("No kept downloads carry the quarantine flag");
+        } else {
+            for (i, path) in quarantined.iter().enumerate() {
+                println!("    {} {}", format!("{:>2}.", i + 1).dimmed(), path.dimmed());
+            }
+            if ctx.interactive && !ctx.dry_run {
+                let selected = select_items("Select downloads you trust to clear their quarantine flag", quarantined.len());
+                let chosen: Vec<String> = selected.iter().map(|&i| quarantined[i].clone()).collect();
+                if !chosen.is_empty() && ctx.should_proceed(&format!("Clear the quarantine flag from {} files?", chosen.len()),
+                    Some("macOS won't ask before opening them the first time".to_string())) {
+                    unquarantine_files(ctx, &chosen);
+                }
+            } else {
+                ctx.log_info("Quarantine flags are only cleared for files selected interactively");
+            }
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,195 @@
+use crate::*;
+
+pub struct VagrantBox {
+    pub name: String,
+    pub version: String,
+    pub size: u64,
+    pub outdated: bool,
+}
+
+pub fn list_vagrant_boxes() -> Vec<VagrantBox> {
+    let home = home_dir();
+    let boxes_dir = format!("{}/.vagrant.d/boxes", home);
+    let mut boxes = Vec::new();
+
+    let Ok(entries) = fs::read_dir(&boxes_dir) else { return boxes };
+    for entry in entries.flatten() {
+        let box_dir = entry.path();
+        if !box_dir.is_dir() {
+            continue;
+        }
+        let name = box_dir.file_name().unwrap_or_default().to_str().unwrap_or("")
+            .replace("-VAGRANTSLASH-", "/");
+
+        let mut versions: Vec<(String, PathBuf)> = fs::read_dir(&box_dir).into_iter()
+            .flat_map(|versions| versions.flatten())
+            .map(|version| version.path())
+            .filter(|path| path.is_dir())
+            .map(|path| (path.file_name().unwrap_or_default().to_str().unwrap_or("").to_string(), path))
+            .collect();
+        versions.sort_by(|a, b| compare_versions(&b.0, &a.0));
+
+        for (i, (version, path)) in versions.into_iter().enumerate() {
+            boxes.push(VagrantBox {
+                name: name.clone(),
+                version,
+                size: get_directory_size(path.to_str().unwrap_or("")),
+                outdated: i > 0,
+            });
+        }
+    }
+
+    boxes
+}
+
+pub fn prune_vagrant_boxes(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+    let home = home_dir();
+    let boxes_dir = format!("{}/.vagrant.d/boxes", home);
+    ctx.log_action("Running vagrant box prune");
+
+    if ctx.dry_run || ctx.delete_cap_reached() {
+        return stats;
+    }
+
+    let before_size = get_directory_size(&boxes_dir);
+    let pruned = match Command::new("vagrant").args(["box", "prune", "--keep-active-boxes", "--force"]).status() {
+        Ok(status) => status.success(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            ctx.log_missing_tool("vagrant");
+            return stats;
+        }
+        Err(_) => false,
+    };
+
+    if pruned {
+        ctx.audit("ran vagrant box prune");
+        stats.add_freed(before_size.saturating_sub(get_directory_size(&boxes_dir)));
+        ctx.log_success(&format!("Pruned outdated Vagrant boxes, freed {}",
+            format_size(stats.space_freed(), BINARY)));
+    } else {
+        ctx.log_error("vagrant box prune failed - is Vagrant installed?");
+    }
+    stats
+}
+
+/// VM folders in `~/VirtualBox VMs` that VirtualBox no longer has registered.
+pub fn find_orphaned_virtualbox_vms() -> Vec<(String, u64)> {
+    let home = home_dir();
+    let vms_dir = format!("{}/VirtualBox VMs", home);
+    let mut orphaned = Vec::new();
+
+    if !Path::new(&vms_dir).exists() {
+        return orphaned;
+    }
+
+    // Without VBoxManage we can't tell which VMs are registered, so flag nothing
+    let Ok(output) = Command::new("VBoxManage").args(["list", "vms"]).output() else {
+        return orphaned;
+    };
+    let registered = String::from_utf8_lossy(&output.stdout).to_string();
+
+    if let Ok(entries) = fs::read_dir(&vms_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = path.file_name().unwrap_or_default().to_str().unwrap_or("");
+            if path.is_dir() && !is_ignored(&path) && !registered.contains(&format!("\"{}\"", name)) {
+                let size = get_directory_size(path.to_str().unwrap_or(""));
+                orphaned.push((path.to_str().unwrap_or("").to_string(), size));
+            }
+        }
+    }
+
+    orphaned.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    orphaned
+}
+
+/// Outdated Vagrant boxes and VirtualBox VM folders VirtualBox no longer knows about.
+pub struct Vagrant;
+
+impl CleanupTask for Vagrant {
+    fn id(&self) -> &'static str {
+        "vagrant"
+    }
+
+    fn uses_invoking_account(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self) -> u64 {
+        list_vagrant_boxes().iter()
+            .filter(|vagrant_box| vagrant_box.outdated)
+            .map(|vagrant_box| vagrant_box.size)
+            .sum()
+    }
+
+    /// Orphaned VMs are only ever deleted when picked by hand
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        if list_vagrant_boxes().iter().any(|vagrant_box| vagrant_box.outdated) {
+            prune_vagrant_boxes(ctx)
+        } else {
+            CleanupStats::new()
+        }
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let stats = CleanupStats::new();
+        let vagrant_boxes = list_vagrant_boxes();
+        let orphaned_vms = find_orphaned_virtualbox_vms();
+        if vagrant_boxes.is_empty() && orphaned_vms.is_empty() {
+            return stats;
+        }
+
+        println!("\n{}", "🧳 Vagrant & VirtualBox".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let outdated_size: u64 = vagrant_boxes.iter()
+            .filter(|vagrant_box| vagrant_box.outdated)
+            .map(|vagrant_box| vagrant_box.size)
+            .sum();
+        let orphaned_size: u64 = orphaned_vms.iter().map(|(_, size)| size).sum();
+        ctx.record_target("vagrant", outdated_size + orphaned_size);
+
+        for vagrant_box in &vagrant_boxes {
+            println!("    {} {} {} ({}){}",
+                "•".dimmed(),
+                vagrant_box.name,
+                vagrant_box.version.dimmed(),
+                format_size(vagrant_box.size, BINARY).red(),
+                if vagrant_box.outdated { " outdated".yellow() } else { "".normal() });
+        }
+
+        if outdated_size > 0 {
+            ctx.log_info(&format!("Outdated box versions: {}", format_size(outdated_size, BINARY).red()));
+            show_space_preview(outdated_size);
+            if ctx.should_proceed("Prune outdated Vagrant box versions (vagrant box prune)?",
+                Some(format!("This will free approximately {}", format_size(outdated_size, BINARY)))) {
+                stats.add(&ctx.run_target(|| prune_vagrant_boxes(ctx)));
+            }
+        }
+
+        if !orphaned_vms.is_empty() {
+            ctx.log_info(&format!("VirtualBox VMs not registered with VirtualBox: {}", format_size(orphaned_size, BINARY).red()));
+            for (i, (path, size)) in orphaned_vms.iter().enumerate() {
+                println!("    {} {} ({})",
+                    format!("{:>2}.", i + 1).dimmed(),
+                    path.dimmed(),
+                    format_size(*size, BINARY).red());
+            }
+
+            if ctx.interactive && !ctx.dry_run {
+                let selected = select_items("Select orphaned VMs to delete", orphaned_vms.len());
+                if !selected.is_empty() {
+                    let chosen: Vec<String> = selected.iter().map(|&i| orphaned_vms[i].0.clone()).collect();
+                    let chosen_size = get_paths_size(&chosen);
+                    if ctx.should_proceed(&format!("Delete {} selected VMs?", chosen.len()),
+                        Some(format!("This will free approximately {}", format_size(chosen_size, BINARY)))) {
+                        stats.add(&ctx.run_target(|| clean_paths(ctx, &chosen, "orphaned VMs")));
+                    }
+                }
+            } else {
+                ctx.log_info("Orphaned VMs are only deleted when selected interactively");
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,59 @@
+use crate::*;
+
+pub fn estimate_xcode_size() -> u64 {
+    app_pack("xcode").existing_paths().iter()
+        .map(|(path, _)| get_directory_size(path))
+        .sum()
+}
+
+pub fn check_xcode_installed() -> bool {
+    app_pack("xcode").is_installed()
+}
+
+pub fn clean_xcode(ctx: &CleanupContext) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (path, safety) in app_pack("xcode").existing_paths() {
+        ctx.log_action(&format!("Cleaning {} ({})", path, safety));
+        stats.add(&clean_directory(&path, None, ctx));
+    }
+
+    ctx.log_success(&format!("Cleaned Xcode data, freed {}", 
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct Xcode;
+
+impl CleanupTask for Xcode {
+    fn id(&self) -> &'static str {
+        "xcode"
+    }
+
+    fn estimate(&self) -> u64 {
+        estimate_xcode_size()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_xcode(ctx)
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        if !check_xcode_installed() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🛠️  Xcode".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let xcode_size = estimate_xcode_size();
+        ctx.record_target("xcode", xcode_size);
+        ctx.log_info(&format!("Derived Data & Archives: {}", format_size(xcode_size, BINARY).red()));
+        show_space_preview(xcode_size);
+
+        if xcode_size > 0 && ctx.should_proceed("Clean Xcode derived data and archives?",
+            Some(format!("This will free approximately {}", format_size(xcode_size, BINARY)))) {
+            return ctx.run_target(|| clean_xcode(ctx));
+        }
+        CleanupStats::new()
+    }
+}
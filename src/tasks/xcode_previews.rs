@@ -0,0 +1,74 @@
+use crate::*;
+
+/// Simulators and caches behind SwiftUI previews and playgrounds, recreated on next use.
+pub const XCODE_PREVIEW_DIRS: &[(&str, &str)] = &[
+    ("SwiftUI previews", "~/Library/Developer/Xcode/UserData/Previews"),
+    ("Playground devices", "~/Library/Developer/XCPGDevices"),
+    ("Swift Playgrounds caches", "~/Library/Containers/com.apple.PlaygroundsMac/Data/Library/Caches"),
+];
+
+/// Existing preview and playground folders as (label, path, size).
+pub fn find_xcode_previews() -> Vec<(&'static str, String, u64)> {
+    XCODE_PREVIEW_DIRS.iter()
+        .map(|(label, path)| (*label, expand_home(path)))
+        .filter(|(_, path)| Path::new(path).exists() && !is_ignored(Path::new(path)))
+        .map(|(label, path)| {
+            let size = get_directory_size(&path);
+            (label, path, size)
+        })
+        .filter(|(_, _, size)| *size > 0)
+        .collect()
+}
+
+pub fn clean_xcode_previews(ctx: &CleanupContext, previews: &[(&str, String, u64)]) -> CleanupStats {
+    let stats = CleanupStats::new();
+
+    for (label, path, _) in previews {
+        ctx.log_action(&format!("Cleaning {} ({})", path, label));
+        stats.add(&clean_directory(path, None, ctx));
+    }
+
+    ctx.log_success(&format!("Cleaned preview and playground data, freed {}",
+        format_size(stats.space_freed(), BINARY)));
+    stats
+}
+
+pub struct XcodePreviews;
+
+impl CleanupTask for XcodePreviews {
+    fn id(&self) -> &'static str {
+        "xcode_previews"
+    }
+
+    fn estimate(&self) -> u64 {
+        find_xcode_previews().iter().map(|(_, _, size)| size).sum()
+    }
+
+    fn clean(&self, ctx: &CleanupContext) -> CleanupStats {
+        clean_xcode_previews(ctx, &find_xcode_previews())
+    }
+
+    fn run(&self, ctx: &CleanupContext) -> CleanupStats {
+        let previews = find_xcode_previews();
+        if previews.is_empty() {
+            return CleanupStats::new();
+        }
+
+        println!("\n{}", "🖼️  Xcode Previews & Playgrounds".bold());
+        println!("{}", "─".repeat(40).dimmed());
+        let previews_size: u64 = previews.iter().map(|(_, _, size)| size).sum();
+        ctx.record_target("xcode_previews", previews_size);
+        for (label, path, size) in &previews {
+            ctx.record_item(path, *size);
+            println!("    {} {} ({})", "•".dimmed(), label, format_size(*size, BINARY).red());
+        }
+        ctx.log_info(&format!("Preview and playground data: {}", format_size(previews_size, BINARY).red()));
+        show_space_preview(previews_size);
+
+        if ctx.should_proceed("Clean SwiftUI preview and playground data?",
+            Some("Rebuilt the next time a preview or playground runs; quit Xcode first".to_string())) {
+            return ctx.run_target(|| clean_xcode_previews(ctx, &previews));
+        }
+        CleanupStats::new()
+    }
+}
@@ -0,0 +1,236 @@
+//! `--tui`: every target on one screen with its size and a checkbox, cleaned in one go.
+
+use std::fs::File;
+use std::os::fd::AsRawFd;
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use crate::*;
+
+type TuiTerminal = Terminal<CrosstermBackend<File>>;
+
+struct Row {
+    task: &'static dyn CleanupTask,
+    size: Option<u64>,
+    selected: bool,
+    result: Option<(usize, u64, usize)>,
+}
+
+/// Sends stdout to /dev/null while alive, so the targets' progress lines don't land on top
+/// of the screen, which is drawn on /dev/tty instead.
+struct SilencedStdout {
+    saved: i32,
+}
+
+impl SilencedStdout {
+    fn new() -> io::Result<SilencedStdout> {
+        io::stdout().flush()?;
+        let null = File::options().write(true).open("/dev/null")?;
+        // SAFETY: plain descriptor calls on descriptors we own; 1 is restored on drop
+        let saved = unsafe { libc::dup(1) };
+        if saved < 0 || unsafe { libc::dup2(null.as_raw_fd(), 1) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(SilencedStdout { saved })
+    }
+}
+
+impl Drop for SilencedStdout {
+    fn drop(&mut self) {
+        let _ = io::stdout().flush();
+        // SAFETY: `saved` is the descriptor duplicated in `new`
+        unsafe {
+            libc::dup2(self.saved, 1);
+            libc::close(self.saved);
+        }
+    }
+}
+
+/// Run the full-screen mode; returns what was cleaned, or nothing if the user quit.
+pub fn run_tui(ctx: &CleanupContext, tasks: &[&'static dyn CleanupTask]) -> io::Result<CleanupStats> {
+    let mut tty = File::options().read(true).write(true).open("/dev/tty")?;
+    let silenced = SilencedStdout::new()?;
+    enable_raw_mode()?;
+    execute!(tty, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(tty))?;
+
+    let result = run_screens(ctx, tasks, &mut terminal);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    drop(silenced);
+    result
+}
+
+fn run_screens(ctx: &CleanupContext, tasks: &[&'static dyn CleanupTask], terminal: &mut TuiTerminal) -> io::Result<CleanupStats> {
+    let mut rows: Vec<Row> = tasks.iter()
+        .map(|task| Row { task: *task, size: None, selected: false, result: None })
+        .collect();
+    let mut state = ListState::default();
+    state.select(Some(0));
+
+    // Sizes fill in one target at a time
+    for index in 0..rows.len() {
+        let status = format!("Scanning {}...", rows[index].task.name());
+        terminal.draw(|frame| draw_list(frame, ctx, &rows, &mut state, &status))?;
+        rows[index].size = Some(rows[index].task.estimate());
+    }
+    // Bytes in a folder two targets cover count for the narrower one only
+    let ids: Vec<&str> = rows.iter().map(|row| row.task.id()).collect();
+    for row in &mut rows {
+        let size = row.size.unwrap_or(0).saturating_sub(claimed_by_others(row.task.id(), &ids));
+        row.size = Some(size);
+        // Only targets that recreate themselves start out ticked
+        row.selected = size > 0 && row.task.risk() == Risk::Safe;
+    }
+    // Targets with nothing to clean unattended aren't worth a line
+    rows.retain(|row| row.size.is_some_and(|size| size > 0));
+    if rows.is_empty() {
+        terminal.draw(|frame| draw_message(frame, "Nothing to clean. Press any key to exit."))?;
+        event::read()?;
+        return Ok(CleanupStats::new());
+    }
+
+    let help = "↑/↓ move  space toggle  a all/none  enter clean  q quit";
+    loop {
+        terminal.draw(|frame| draw_list(frame, ctx, &rows, &mut state, help))?;
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let current = state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(CleanupStats::new()),
+            KeyCode::Up | KeyCode::Char('k') => state.select(Some(current.saturating_sub(1))),
+            KeyCode::Down | KeyCode::Char('j') => state.select(Some((current + 1).min(rows.len() - 1))),
+            KeyCode::Char(' ') => rows[current].selected = !rows[current].selected,
+            KeyCode::Char('a') => {
+                let select = !rows.iter().all(|row| row.selected);
+                rows.iter_mut().for_each(|row| row.selected = select);
+            }
+            KeyCode::Enter if rows.iter().any(|row| row.selected) => break,
+            _ => {}
+        }
+    }
+
+    rows.retain(|row| row.selected);
+    let total_stats = CleanupStats::new();
+    for index in 0..rows.len() {
+        terminal.draw(|frame| draw_progress(frame, ctx, &rows, index, &total_stats))?;
+        let task = rows[index].task;
+        ctx.record_target(task.id(), rows[index].size.unwrap_or(0));
+        let stats = ctx.run_target(|| task.clean(ctx));
+        rows[index].result = Some((stats.files_removed(), stats.space_freed(), stats.errors()));
+        total_stats.add(&stats);
+        if ctx.backup_failed.get() {
+            break;
+        }
+    }
+    ctx.current_risk.set(None);
+
+    loop {
+        terminal.draw(|frame| draw_summary(frame, ctx, &rows, &total_stats))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(total_stats);
+            }
+        }
+    }
+}
+
+fn title(ctx: &CleanupContext) -> String {
+    if ctx.dry_run {
+        String::from(" 🧹 Mac Cleanup — dry run, nothing is deleted ")
+    } else {
+        String::from(" 🧹 Mac Cleanup ")
+    }
+}
+
+fn risk_color(risk: Risk) -> Color {
+    match risk {
+        Risk::Safe => Color::Green,
+        Risk::Moderate => Color::Yellow,
+        Risk::Aggressive => Color::Red,
+    }
+}
+
+fn draw_list(frame: &mut Frame, ctx: &CleanupContext, rows: &[Row], state: &mut ListState, status: &str) {
+    let [list_area, footer_area] = Layout::vertical([Constraint::Min(3), Constraint::Length(3)]).areas(frame.area());
+
+    let items: Vec<ListItem> = rows.iter()
+        .map(|row| {
+            let size = row.size.map_or_else(|| String::from("…"), |size| format_size(size, BINARY));
+            let line = format!("[{}] {:<36} {:>11}  {}",
+                if row.selected { "x" } else { " " }, row.task.name(), size, row.task.risk().label().clear());
+            ListItem::new(line).style(Style::default().fg(risk_color(row.task.risk())))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title(ctx)))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, list_area, state);
+
+    let selected: u64 = rows.iter().filter(|row| row.selected).filter_map(|row| row.size).sum();
+    let footer = Paragraph::new(vec![
+        Line::from(format!("Selected: {}", format_size(selected, BINARY))),
+        Line::from(status.to_string()),
+    ]).block(Block::default().borders(Borders::TOP));
+    frame.render_widget(footer, footer_area);
+}
+
+fn draw_progress(frame: &mut Frame, ctx: &CleanupContext, rows: &[Row], index: usize, total_stats: &CleanupStats) {
+    let [gauge_area, list_area] = Layout::vertical([Constraint::Length(3), Constraint::Min(3)]).areas(frame.area());
+
+    let label = format!("{} ({} of {})", rows[index].task.name(), index + 1, rows.len());
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(title(ctx)))
+        .gauge_style(Style::default().fg(Color::Cyan))
+        .ratio(index as f64 / rows.len() as f64)
+        .label(label);
+    frame.render_widget(gauge, gauge_area);
+
+    let mut lines: Vec<Line> = rows.iter().enumerate()
+        .map(|(position, row)| match (&row.result, position == index) {
+            (Some((_, freed, _)), _) => Line::from(format!("  ✓ {:<36} {:>11}", row.task.name(), format_size(*freed, BINARY))),
+            (None, true) => Line::from(format!("  … {}", row.task.name())),
+            (None, false) => Line::from(format!("    {}", row.task.name())).style(Style::default().add_modifier(Modifier::DIM)),
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!("  Freed so far: {}", format_size(total_stats.space_freed(), BINARY))));
+    frame.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" Cleaning ")), list_area);
+}
+
+fn draw_summary(frame: &mut Frame, ctx: &CleanupContext, rows: &[Row], total_stats: &CleanupStats) {
+    let freed_label = if ctx.dry_run { "Would free" } else { "Freed" };
+    let mut lines: Vec<Line> = rows.iter()
+        .filter_map(|row| {
+            let (removed, freed, errors) = row.result?;
+            let errors = if errors > 0 { format!(", {} errors", errors) } else { String::new() };
+            Some(Line::from(format!("  {:<36} {:>6} items {:>11}{}", row.task.name(), removed, format_size(freed, BINARY), errors)))
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!("  {}: {} in {} items", freed_label, format_size(total_stats.space_freed(), BINARY), total_stats.files_removed()))
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)));
+    if total_stats.errors() > 0 {
+        lines.push(Line::from(format!("  Errors: {}", total_stats.errors())).style(Style::default().fg(Color::Red)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("  Press any key to exit").style(Style::default().add_modifier(Modifier::DIM)));
+    frame.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" ✨ Cleanup Complete ")), frame.area());
+}
+
+fn draw_message(frame: &mut Frame, message: &str) {
+    let block = Block::default().borders(Borders::ALL).title(" 🧹 Mac Cleanup ");
+    frame.render_widget(Paragraph::new(format!("\n  {}", message)).block(block), frame.area());
+}